@@ -76,6 +76,93 @@ mod circuits {
         pub total: u64,
     }
 
+    /// Per-ballot receipt returned to the voter, encrypted to their
+    /// `voter_pubkey`, so they can check privately that the ballot entered
+    /// the tally without anyone else learning how it was counted.
+    pub struct BallotReceipt {
+        /// Tally `total` right after this ballot — its inclusion position
+        pub position: u64,
+        /// Weight the ballot added (0 = rejected as an invalid choice)
+        pub counted: u64,
+    }
+
+    /// A re-vote: the choice plus the recast number the program assigns it
+    /// (`VoteRecord.revotes`), both encrypted, so the ciphertext is bound to
+    /// the one recast it was made for.
+    pub struct SequencedBallot {
+        pub choice: u8,
+        pub sequence: u64,
+    }
+
+    /// The ballot currently counted for a voter on a re-voting proposal,
+    /// held by the MXE between `recast_vote` computations.
+    pub struct CountedBallot {
+        pub choice: u8,
+        pub sequence: u64,
+        /// Weight fixed by the voter's first ballot
+        pub weight: u64,
+    }
+
+    /// Options on a ranked-choice ballot
+    pub const RANKED_OPTIONS: usize = 4;
+    /// Number of complete rankings of `RANKED_OPTIONS` options (4!)
+    pub const RANKINGS_COUNT: usize = 24;
+
+    /// Every complete ranking, most-preferred option first. A ranked ballot is
+    /// the encrypted index of the voter's ranking in this table.
+    pub const RANKINGS: [[u8; RANKED_OPTIONS]; RANKINGS_COUNT] = [
+        [0, 1, 2, 3],
+        [0, 1, 3, 2],
+        [0, 2, 1, 3],
+        [0, 2, 3, 1],
+        [0, 3, 1, 2],
+        [0, 3, 2, 1],
+        [1, 0, 2, 3],
+        [1, 0, 3, 2],
+        [1, 2, 0, 3],
+        [1, 2, 3, 0],
+        [1, 3, 0, 2],
+        [1, 3, 2, 0],
+        [2, 0, 1, 3],
+        [2, 0, 3, 1],
+        [2, 1, 0, 3],
+        [2, 1, 3, 0],
+        [2, 3, 0, 1],
+        [2, 3, 1, 0],
+        [3, 0, 1, 2],
+        [3, 0, 2, 1],
+        [3, 1, 0, 2],
+        [3, 1, 2, 0],
+        [3, 2, 0, 1],
+        [3, 2, 1, 0],
+    ];
+
+    /// Encrypted ranked-choice tally: how many ballots chose each ranking.
+    ///
+    /// Counting whole rankings (rather than first preferences) is what lets
+    /// instant-runoff redistribute eliminated options' ballots inside the MPC
+    /// without ever storing individual ballots.
+    pub struct RankedTally {
+        pub counts: [u64; RANKINGS_COUNT],
+    }
+
+    /// Ballots permuted and tallied together by `shuffle_ballots`
+    pub const BALLOT_BATCH: usize = 8;
+
+    /// A shuffled batch of ballots, owned by the MXE cluster.
+    pub struct BallotBatch {
+        pub choices: [u8; BALLOT_BATCH],
+    }
+
+    /// Stored ballots aggregated per conviction computation
+    pub const CONVICTION_BATCH: usize = 16;
+
+    /// Depth of the eligibility snapshot tree checked by `cast_vote_with_eligibility`
+    pub const SNAPSHOT_DEPTH: usize = 16;
+
+    /// Weight-mode flags accepted by `cast_vote_encrypted_weight`
+    pub const WEIGHT_MODE_QUADRATIC: u8 = 1;
+
     // ==================== INSTRUCTIONS ====================
 
     /// Initialize a new voting session with encrypted zero counts.
@@ -118,9 +205,13 @@ mod circuits {
     /// * `vote` - Voter's encrypted choice as `Enc<Shared, u8>` (0=NO, 1=YES, 2=ABSTAIN)
     ///
     /// ## Returns
-    /// Updated `Enc<Mxe, Tally>` with the vote accumulated into encrypted totals.
+    /// Updated `Enc<Mxe, Tally>` with the vote accumulated into encrypted totals,
+    /// and the voter's `BallotReceipt`.
     #[instruction]
-    pub fn cast_vote(state: Enc<Mxe, Tally>, vote: Enc<Shared, u8>) -> Enc<Mxe, Tally> {
+    pub fn cast_vote(
+        state: Enc<Mxe, Tally>,
+        vote: Enc<Shared, u8>,
+    ) -> (Enc<Mxe, Tally>, Enc<Shared, BallotReceipt>) {
         let tally = state.to_arcis();
 
         // Encrypted constants for comparison — public values wrapped in Enc
@@ -141,12 +232,303 @@ mod circuits {
 
         // All additions happen on encrypted values — MXE nodes perform
         // secret-shared arithmetic without decrypting any operand
-        state.owner.from_arcis(Tally {
-            yes: tally.yes + is_yes,
-            no: tally.no + is_no,
-            abstain: tally.abstain + is_abstain,
-            total: tally.total + is_valid,
-        })
+        let total = tally.total + is_valid.clone();
+        (
+            state.owner.from_arcis(Tally {
+                yes: tally.yes + is_yes,
+                no: tally.no + is_no,
+                abstain: tally.abstain + is_abstain,
+                total: total.clone(),
+            }),
+            vote.owner.from_arcis(BallotReceipt {
+                position: total,
+                counted: is_valid,
+            }),
+        )
+    }
+
+    /// Cast an encrypted vote carrying a public weight.
+    ///
+    /// Same constant-time pattern as `cast_vote`, but the matched category is
+    /// incremented by `weight` instead of 1. Used for token-weighted, quadratic
+    /// and reputation proposals: the weight is derived on-chain from public
+    /// account state, so only the choice itself is secret.
+    ///
+    /// ## Arguments
+    /// * `state` - Current `Enc<Mxe, Tally>` from the MXE cluster
+    /// * `vote` - Voter's encrypted choice as `Enc<Shared, u8>` (0=NO, 1=YES, 2=ABSTAIN)
+    /// * `weight` - Plaintext ballot weight computed by the Solana program
+    ///
+    /// ## Returns
+    /// Updated `Enc<Mxe, Tally>` where counters hold summed weight, and the
+    /// voter's `BallotReceipt`.
+    #[instruction]
+    pub fn cast_weighted_vote(
+        state: Enc<Mxe, Tally>,
+        vote: Enc<Shared, u8>,
+        weight: u64,
+    ) -> (Enc<Mxe, Tally>, Enc<Shared, BallotReceipt>) {
+        let tally = state.to_arcis();
+
+        let one_u8: Enc<Shared, u8> = Enc::new(1u8);
+        let zero_u8: Enc<Shared, u8> = Enc::new(0u8);
+        let two_u8: Enc<Shared, u8> = Enc::new(2u8);
+
+        let is_yes: Enc<Shared, u64> = vote.eq(&one_u8).cast();
+        let is_no: Enc<Shared, u64> = vote.eq(&zero_u8).cast();
+        let is_abstain: Enc<Shared, u64> = vote.eq(&two_u8).cast();
+
+        // Scale the 0/1 flags by the public weight; invalid choices add nothing
+        let yes_weight = is_yes * weight;
+        let no_weight = is_no * weight;
+        let abstain_weight = is_abstain * weight;
+        let valid_weight = yes_weight.clone() + no_weight.clone() + abstain_weight.clone();
+
+        let total = tally.total + valid_weight.clone();
+        (
+            state.owner.from_arcis(Tally {
+                yes: tally.yes + yes_weight,
+                no: tally.no + no_weight,
+                abstain: tally.abstain + abstain_weight,
+                total: total.clone(),
+            }),
+            vote.owner.from_arcis(BallotReceipt {
+                position: total,
+                counted: valid_weight,
+            }),
+        )
+    }
+
+    /// Cast an encrypted vote whose weight is also encrypted.
+    ///
+    /// Used for token-weighted and quadratic proposals: the voter commits an
+    /// encrypted amount of their gate balance together with a public
+    /// weight-mode flag. In quadratic mode the circuit takes the integer
+    /// square root of that amount, then caps the result at `max_weight`, the
+    /// weight the Solana program derived from the voter's full balance. How
+    /// much of their balance a voter puts behind a ballot is never visible
+    /// on-chain, yet no one can count more than they hold.
+    ///
+    /// ## Arguments
+    /// * `state` - Current `Enc<Mxe, Tally>` from the MXE cluster
+    /// * `vote` - Voter's encrypted choice as `Enc<Shared, u8>` (0=NO, 1=YES, 2=ABSTAIN)
+    /// * `weight` - Encrypted amount the voter commits
+    /// * `max_weight` - Plaintext cap computed by the Solana program
+    /// * `weight_mode` - Plaintext flag: 0 = linear, 1 = quadratic
+    ///
+    /// ## Returns
+    /// Updated `Enc<Mxe, Tally>` and the voter's `BallotReceipt`.
+    #[instruction]
+    pub fn cast_vote_encrypted_weight(
+        state: Enc<Mxe, Tally>,
+        vote: Enc<Shared, u8>,
+        weight: Enc<Shared, u64>,
+        max_weight: u64,
+        weight_mode: u8,
+    ) -> (Enc<Mxe, Tally>, Enc<Shared, BallotReceipt>) {
+        let tally = state.to_arcis();
+
+        // The mode is public, so branching on it leaks nothing
+        let weight = if weight_mode == WEIGHT_MODE_QUADRATIC {
+            encrypted_isqrt(weight)
+        } else {
+            weight
+        };
+
+        // Both comparisons always run; exactly one flag is set
+        let cap: Enc<Shared, u64> = Enc::new(max_weight);
+        let within: Enc<Shared, u64> = weight.le(&cap).cast();
+        let over: Enc<Shared, u64> = weight.gt(&cap).cast();
+        let weight = within * weight + over * max_weight;
+
+        let one_u8: Enc<Shared, u8> = Enc::new(1u8);
+        let zero_u8: Enc<Shared, u8> = Enc::new(0u8);
+        let two_u8: Enc<Shared, u8> = Enc::new(2u8);
+
+        let is_yes: Enc<Shared, u64> = vote.eq(&one_u8).cast();
+        let is_no: Enc<Shared, u64> = vote.eq(&zero_u8).cast();
+        let is_abstain: Enc<Shared, u64> = vote.eq(&two_u8).cast();
+
+        let yes_weight = is_yes * weight.clone();
+        let no_weight = is_no * weight.clone();
+        let abstain_weight = is_abstain * weight;
+        let valid_weight = yes_weight.clone() + no_weight.clone() + abstain_weight.clone();
+
+        let total = tally.total + valid_weight.clone();
+        (
+            state.owner.from_arcis(Tally {
+                yes: tally.yes + yes_weight,
+                no: tally.no + no_weight,
+                abstain: tally.abstain + abstain_weight,
+                total: total.clone(),
+            }),
+            vote.owner.from_arcis(BallotReceipt {
+                position: total,
+                counted: valid_weight,
+            }),
+        )
+    }
+
+    /// Cast one encrypted vote carrying the voter's own weight plus the weight
+    /// delegated to them.
+    ///
+    /// A delegate representing many delegators is tallied in a single MPC
+    /// round instead of one computation per delegator. Both weights are
+    /// derived on-chain from public balances; only the choice is secret.
+    ///
+    /// ## Arguments
+    /// * `vote` - Voter's encrypted choice as `Enc<Shared, u8>` (0=NO, 1=YES, 2=ABSTAIN)
+    /// * `own_weight` - The voter's own ballot weight
+    /// * `delegated_weight` - Summed weight of the voter's delegators
+    /// * `state` - Current `Enc<Mxe, Tally>` from the MXE cluster
+    ///
+    /// ## Returns
+    /// Updated `Enc<Mxe, Tally>` and the delegate's `BallotReceipt`.
+    #[instruction]
+    pub fn vote_with_delegations(
+        vote: Enc<Shared, u8>,
+        own_weight: u64,
+        delegated_weight: u64,
+        state: Enc<Mxe, Tally>,
+    ) -> (Enc<Mxe, Tally>, Enc<Shared, BallotReceipt>) {
+        let tally = state.to_arcis();
+        let weight = own_weight + delegated_weight;
+
+        let one_u8: Enc<Shared, u8> = Enc::new(1u8);
+        let zero_u8: Enc<Shared, u8> = Enc::new(0u8);
+        let two_u8: Enc<Shared, u8> = Enc::new(2u8);
+
+        let is_yes: Enc<Shared, u64> = vote.eq(&one_u8).cast();
+        let is_no: Enc<Shared, u64> = vote.eq(&zero_u8).cast();
+        let is_abstain: Enc<Shared, u64> = vote.eq(&two_u8).cast();
+
+        let yes_weight = is_yes * weight;
+        let no_weight = is_no * weight;
+        let abstain_weight = is_abstain * weight;
+        let valid_weight = yes_weight.clone() + no_weight.clone() + abstain_weight.clone();
+
+        let total = tally.total + valid_weight.clone();
+        (
+            state.owner.from_arcis(Tally {
+                yes: tally.yes + yes_weight,
+                no: tally.no + no_weight,
+                abstain: tally.abstain + abstain_weight,
+                total: total.clone(),
+            }),
+            vote.owner.from_arcis(BallotReceipt {
+                position: total,
+                counted: valid_weight,
+            }),
+        )
+    }
+
+    /// Cast the first ballot on a re-voting proposal.
+    ///
+    /// Tallies like `cast_weighted_vote`, and also returns the ballot as an
+    /// MXE-held `CountedBallot` with sequence 0, so any later `recast_vote`
+    /// can take it back out of the tally.
+    ///
+    /// ## Returns
+    /// Updated `Enc<Mxe, Tally>` and the voter's `CountedBallot`.
+    #[instruction]
+    pub fn cast_sequenced_vote(
+        state: Enc<Mxe, Tally>,
+        vote: Enc<Shared, u8>,
+        weight: u64,
+    ) -> (Enc<Mxe, Tally>, Enc<Mxe, CountedBallot>) {
+        let tally = state.to_arcis();
+        let choice = vote.to_arcis();
+        let (is_yes, is_no, is_abstain) = choice_flags(choice);
+
+        (
+            state.owner.from_arcis(Tally {
+                yes: tally.yes + is_yes * weight,
+                no: tally.no + is_no * weight,
+                abstain: tally.abstain + is_abstain * weight,
+                total: tally.total + (is_yes + is_no + is_abstain) * weight,
+            }),
+            state.owner.from_arcis(CountedBallot {
+                choice,
+                sequence: 0,
+                weight,
+            }),
+        )
+    }
+
+    /// Replace a voter's counted ballot with the recast numbered `sequence`.
+    ///
+    /// `sequence` is assigned by the program, one higher per recast, so the
+    /// voter's latest ballot always counts: a coerced voter can override any
+    /// ballot afterwards, and no ballot can claim a sequence that outranks
+    /// later ones. A ballot encrypted for another sequence, or replayed,
+    /// changes nothing. The swap — remove the counted choice, add the new
+    /// one — is computed on 0/1 flags, so whether it happened stays secret.
+    ///
+    /// ## Arguments
+    /// * `state` - Current `Enc<Mxe, Tally>` from the MXE cluster
+    /// * `ballot` - The voter's new `SequencedBallot`
+    /// * `current` - The voter's `CountedBallot` from the previous computation
+    /// * `sequence` - Recast number the program assigned this ballot
+    ///
+    /// ## Returns
+    /// Updated `Enc<Mxe, Tally>` and the voter's new `CountedBallot`.
+    #[instruction]
+    pub fn recast_vote(
+        state: Enc<Mxe, Tally>,
+        ballot: Enc<Shared, SequencedBallot>,
+        current: Enc<Mxe, CountedBallot>,
+        sequence: u64,
+    ) -> (Enc<Mxe, Tally>, Enc<Mxe, CountedBallot>) {
+        let tally = state.to_arcis();
+        let next = ballot.to_arcis();
+        let counted = current.to_arcis();
+
+        let newer = (next.sequence == sequence && sequence > counted.sequence) as u64;
+        let swap = counted.weight * newer;
+        let (old_yes, old_no, old_abstain) = choice_flags(counted.choice);
+        let (new_yes, new_no, new_abstain) = choice_flags(next.choice);
+
+        // Add before subtracting so the counters never dip below zero
+        (
+            state.owner.from_arcis(Tally {
+                yes: tally.yes + new_yes * swap - old_yes * swap,
+                no: tally.no + new_no * swap - old_no * swap,
+                abstain: tally.abstain + new_abstain * swap - old_abstain * swap,
+                total: tally.total + (new_yes + new_no + new_abstain) * swap
+                    - (old_yes + old_no + old_abstain) * swap,
+            }),
+            state.owner.from_arcis(CountedBallot {
+                choice: (next.choice as u64 * newer + counted.choice as u64 * (1 - newer)) as u8,
+                sequence: next.sequence * newer + counted.sequence * (1 - newer),
+                weight: counted.weight,
+            }),
+        )
+    }
+
+    /// 0/1 flags for a YES, NO and ABSTAIN choice; all zero when invalid.
+    fn choice_flags(choice: u8) -> (u64, u64, u64) {
+        (
+            (choice == 1) as u64,
+            (choice == 0) as u64,
+            (choice == 2) as u64,
+        )
+    }
+
+    /// Integer square root of an encrypted value.
+    ///
+    /// Builds the root bit by bit over a fixed 32 rounds, keeping each bit only
+    /// if the candidate's square still fits — constant-time regardless of input.
+    fn encrypted_isqrt(value: Enc<Shared, u64>) -> Enc<Shared, u64> {
+        let mut root: Enc<Shared, u64> = Enc::new(0u64);
+
+        for i in (0..32).rev() {
+            let bit = 1u64 << i;
+            let candidate = root.clone() + bit;
+            let fits: Enc<Shared, u64> = (candidate.clone() * candidate).le(&value).cast();
+            root = root + fits * bit;
+        }
+
+        root
     }
 
     /// Finalize voting and reveal aggregate results via threshold decryption.
@@ -228,8 +610,412 @@ mod circuits {
             quorum_met && threshold_met,
         )
     }
+
+    /// Resolve the winner of a tally, breaking an exact YES/NO tie with an
+    /// encrypted coin.
+    ///
+    /// The coin is drawn from the cluster's joint MPC randomness, so no single
+    /// node, the authority or the caller can bias or predict it. Only the
+    /// outcome is revealed — never the coin itself.
+    ///
+    /// ## Returns
+    /// `true` if YES wins (strictly ahead, or tied and the coin came up YES).
+    #[instruction]
+    pub fn resolve_tie(state: Enc<Mxe, Tally>) -> bool {
+        let tally = state.to_arcis();
+        let coin = ArcisRNG::bool();
+
+        let yes_ahead = tally.yes.gt(&tally.no);
+        let tied = tally.yes.eq(&tally.no);
+
+        (yes_ahead | (tied & coin)).reveal()
+    }
+
+    /// Fold a batch of stored ballots into the tally with conviction weighting.
+    ///
+    /// A ballot's weight grows by one for every full `conviction_period` it
+    /// stood before `voting_ends_at`, capped at `max_multiplier`. Timestamps
+    /// are public (they are on every VoteRecord); the multiplier is applied to
+    /// the encrypted choice flags, so individual weighted ballots are never
+    /// exposed. Slots with `voted_at == 0` are padding and add nothing.
+    #[instruction]
+    pub fn apply_conviction(
+        state: Enc<Mxe, Tally>,
+        ballots: [Enc<Shared, u8>; CONVICTION_BATCH],
+        voted_at: [i64; CONVICTION_BATCH],
+        voting_ends_at: i64,
+        conviction_period: i64,
+        max_multiplier: u64,
+    ) -> Enc<Mxe, Tally> {
+        let tally = accumulate_conviction(
+            state.to_arcis(),
+            ballots,
+            voted_at,
+            voting_ends_at,
+            conviction_period,
+            max_multiplier,
+        );
+        state.owner.from_arcis(tally)
+    }
+
+    /// Finalize variant of `apply_conviction`: folds the last batch and
+    /// reveals the conviction-weighted aggregates.
+    ///
+    /// ## Returns
+    /// `(yes, no, abstain, total)` in conviction-weighted units.
+    #[instruction]
+    pub fn finalize_with_conviction(
+        state: Enc<Mxe, Tally>,
+        ballots: [Enc<Shared, u8>; CONVICTION_BATCH],
+        voted_at: [i64; CONVICTION_BATCH],
+        voting_ends_at: i64,
+        conviction_period: i64,
+        max_multiplier: u64,
+    ) -> (u64, u64, u64, u64) {
+        let tally = accumulate_conviction(
+            state.to_arcis(),
+            ballots,
+            voted_at,
+            voting_ends_at,
+            conviction_period,
+            max_multiplier,
+        )
+        .reveal();
+        (tally.yes, tally.no, tally.abstain, tally.total)
+    }
+
+    /// Public conviction multiplier for a ballot cast at `voted_at`.
+    fn conviction_multiplier(
+        voted_at: i64,
+        voting_ends_at: i64,
+        conviction_period: i64,
+        max_multiplier: u64,
+    ) -> u64 {
+        if voted_at == 0 || voted_at > voting_ends_at {
+            return 0;
+        }
+        let periods = if conviction_period > 0 {
+            ((voting_ends_at - voted_at) / conviction_period) as u64
+        } else {
+            0
+        };
+        (1 + periods).min(max_multiplier.max(1))
+    }
+
+    fn accumulate_conviction(
+        mut tally: Tally,
+        ballots: [Enc<Shared, u8>; CONVICTION_BATCH],
+        voted_at: [i64; CONVICTION_BATCH],
+        voting_ends_at: i64,
+        conviction_period: i64,
+        max_multiplier: u64,
+    ) -> Tally {
+        let one_u8: Enc<Shared, u8> = Enc::new(1u8);
+        let zero_u8: Enc<Shared, u8> = Enc::new(0u8);
+        let two_u8: Enc<Shared, u8> = Enc::new(2u8);
+
+        for (vote, at) in ballots.iter().zip(voted_at.iter()) {
+            let multiplier =
+                conviction_multiplier(*at, voting_ends_at, conviction_period, max_multiplier);
+
+            let is_yes: Enc<Shared, u64> = vote.eq(&one_u8).cast();
+            let is_no: Enc<Shared, u64> = vote.eq(&zero_u8).cast();
+            let is_abstain: Enc<Shared, u64> = vote.eq(&two_u8).cast();
+
+            let yes_weight = is_yes * multiplier;
+            let no_weight = is_no * multiplier;
+            let abstain_weight = is_abstain * multiplier;
+
+            tally.total =
+                tally.total + yes_weight.clone() + no_weight.clone() + abstain_weight.clone();
+            tally.yes = tally.yes + yes_weight;
+            tally.no = tally.no + no_weight;
+            tally.abstain = tally.abstain + abstain_weight;
+        }
+
+        tally
+    }
+
+    /// Merge two tally shards into one.
+    ///
+    /// Sharded proposals route each ballot to one of K `Enc<Mxe, Tally>` shards
+    /// so votes don't all write-lock the same account. After the deadline each
+    /// secondary shard is folded into the primary tally with this computation.
+    /// Only ciphertext is added — nothing is revealed.
+    #[instruction]
+    pub fn combine_tallies(primary: Enc<Mxe, Tally>, shard: Enc<Mxe, Tally>) -> Enc<Mxe, Tally> {
+        let a = primary.to_arcis();
+        let b = shard.to_arcis();
+
+        primary.owner.from_arcis(Tally {
+            yes: a.yes + b.yes,
+            no: a.no + b.no,
+            abstain: a.abstain + b.abstain,
+            total: a.total + b.total,
+        })
+    }
+
+    /// Re-encrypt a tally for a different MXE cluster.
+    ///
+    /// If the cluster set changes mid-vote the old cluster's key can no longer
+    /// be used to reveal. Before it is retired, the old cluster runs this once
+    /// to hand the running totals to `target` — the counts are moved, never
+    /// revealed.
+    #[instruction]
+    pub fn migrate_tally(state: Enc<Mxe, Tally>, target: Mxe) -> Enc<Mxe, Tally> {
+        target.from_arcis(state.to_arcis())
+    }
+
+    /// Gather a batch of submitted ballots and permute them inside the MPC.
+    ///
+    /// The permutation is drawn from the cluster's joint randomness, so the
+    /// order ballots were submitted on-chain cannot be correlated with the
+    /// order any later step processes them in. Run before any per-ballot work
+    /// such as validity filtering.
+    #[instruction]
+    pub fn shuffle_ballots(ballots: [Enc<Shared, u8>; BALLOT_BATCH]) -> Enc<Mxe, BallotBatch> {
+        let mut choices = ballots.map(|ballot| ballot.to_arcis());
+        ArcisRNG::shuffle(&mut choices);
+
+        Mxe::get().from_arcis(BallotBatch { choices })
+    }
+
+    /// Validity-filter and accumulate a shuffled batch into the tally.
+    ///
+    /// Same constant-time encoding as `cast_vote`, applied to every ballot in
+    /// the (already permuted) batch.
+    #[instruction]
+    pub fn tally_ballot_batch(
+        state: Enc<Mxe, Tally>,
+        batch: Enc<Mxe, BallotBatch>,
+    ) -> Enc<Mxe, Tally> {
+        let mut tally = state.to_arcis();
+        let batch = batch.to_arcis();
+
+        for choice in batch.choices.iter() {
+            let is_yes: u64 = (*choice == 1).cast();
+            let is_no: u64 = (*choice == 0).cast();
+            let is_abstain: u64 = (*choice == 2).cast();
+
+            tally.yes = tally.yes + is_yes;
+            tally.no = tally.no + is_no;
+            tally.abstain = tally.abstain + is_abstain;
+            tally.total = tally.total + is_yes + is_no + is_abstain;
+        }
+
+        state.owner.from_arcis(tally)
+    }
+
+    /// Initialize an encrypted ranked-choice tally with all rankings at zero.
+    #[instruction]
+    pub fn initialize_ranked_voting() -> Enc<Mxe, RankedTally> {
+        Enc::new(RankedTally {
+            counts: [0; RANKINGS_COUNT],
+        })
+    }
+
+    /// Accumulate one encrypted ranked ballot.
+    ///
+    /// `ranking` is an index into `RANKINGS`. Every slot is compared
+    /// (constant-time), so the circuit never learns which ranking was chosen;
+    /// an out-of-range index matches nothing and is ignored.
+    #[instruction]
+    pub fn cast_ranked_vote(
+        state: Enc<Mxe, RankedTally>,
+        ranking: Enc<Shared, u8>,
+    ) -> Enc<Mxe, RankedTally> {
+        let tally = state.to_arcis();
+        let mut counts = tally.counts;
+
+        for (i, count) in counts.iter_mut().enumerate() {
+            let slot: Enc<Shared, u8> = Enc::new(i as u8);
+            let hit: Enc<Shared, u64> = ranking.eq(&slot).cast();
+            *count = *count + hit;
+        }
+
+        state.owner.from_arcis(RankedTally { counts })
+    }
+
+    /// Run instant-runoff elimination over an encrypted ranked tally.
+    ///
+    /// Each round every ranking's count is credited to its most-preferred
+    /// option still standing, and the option with the fewest votes is
+    /// eliminated (ties go to the lowest option index). The eliminated option
+    /// is revealed at the end of each round — the next round's redistribution
+    /// depends on it — but no per-option count is ever revealed.
+    ///
+    /// ## Returns
+    /// `(eliminated, winner)` — options in elimination order, and the last
+    /// option standing.
+    #[instruction]
+    pub fn run_instant_runoff(state: Enc<Mxe, RankedTally>) -> ([u8; RANKED_OPTIONS - 1], u8) {
+        let tally = state.to_arcis();
+        let mut eliminated_in_round = [0u8; RANKED_OPTIONS - 1];
+        let mut standing = [true; RANKED_OPTIONS];
+
+        for round in eliminated_in_round.iter_mut() {
+            // First preferences among standing options — which option a ranking
+            // credits depends only on public elimination state
+            let mut first_prefs = [Enc::new(0u64); RANKED_OPTIONS];
+            for (ranking, count) in RANKINGS.iter().zip(tally.counts.iter()) {
+                let top = ranking
+                    .iter()
+                    .find(|option| standing[**option as usize])
+                    .map(|option| *option as usize)
+                    .unwrap_or(0);
+                first_prefs[top] = first_prefs[top] + *count;
+            }
+
+            // Encrypted arg-min over standing options; both arms of each
+            // select are evaluated, so the comparison result never leaks
+            let mut lowest = Enc::new(u64::MAX);
+            let mut lowest_option = Enc::new(0u8);
+            for (option, votes) in first_prefs.iter().enumerate() {
+                if !standing[option] {
+                    continue;
+                }
+                let is_lower = votes.lt(&lowest);
+                lowest = is_lower.select(votes, &lowest);
+                lowest_option = is_lower.select(&Enc::new(option as u8), &lowest_option);
+            }
+
+            let option = lowest_option.reveal();
+            standing[option as usize] = false;
+            *round = option;
+        }
+
+        let winner = standing.iter().position(|s| *s).unwrap_or(0) as u8;
+        (eliminated_in_round, winner)
+    }
+
+    /// Cast an encrypted vote whose weight is proven against the proposal's
+    /// eligibility snapshot inside the MPC.
+    ///
+    /// The snapshot is a SHA3 Merkle tree over `SHA3(voter || weight_le)`
+    /// leaves, with `SHA3(left || right)` nodes. The leaf is hashed from the
+    /// *encrypted* weight, so a voter cannot claim a weight the snapshot does
+    /// not hold. A failed proof zeroes the weight in constant time; only the
+    /// eligibility bit is revealed.
+    ///
+    /// 32-byte public values arrive as little-endian `[u128; 2]` pairs.
+    ///
+    /// ## Returns
+    /// Updated `Enc<Mxe, Tally>` and whether the proof verified.
+    #[instruction]
+    pub fn cast_vote_with_eligibility(
+        vote: Enc<Shared, u8>,
+        state: Enc<Mxe, Tally>,
+        weight: Enc<Shared, u64>,
+        voter: [u128; 2],
+        leaf_index: u64,
+        proof: [[u128; 2]; SNAPSHOT_DEPTH],
+        root: [u128; 2],
+    ) -> (Enc<Mxe, Tally>, bool) {
+        let tally = state.to_arcis();
+        let secret_weight = weight.to_arcis();
+
+        let mut leaf_preimage = [0u8; 40];
+        leaf_preimage[..32].copy_from_slice(&bytes32(voter));
+        leaf_preimage[32..].copy_from_slice(&secret_weight.to_le_bytes());
+        let mut node = SHA3_256::new().digest(&leaf_preimage);
+
+        // The path is public (leaf position), only the leaf hash is secret
+        for (depth, sibling) in proof.iter().enumerate() {
+            let mut preimage = [0u8; 64];
+            if (leaf_index >> depth) & 1 == 0 {
+                preimage[..32].copy_from_slice(&node);
+                preimage[32..].copy_from_slice(&bytes32(*sibling));
+            } else {
+                preimage[..32].copy_from_slice(&bytes32(*sibling));
+                preimage[32..].copy_from_slice(&node);
+            }
+            node = SHA3_256::new().digest(&preimage);
+        }
+
+        let eligible = node == bytes32(root);
+        let weight = secret_weight * (eligible as u64);
+
+        let one_u8: Enc<Shared, u8> = Enc::new(1u8);
+        let zero_u8: Enc<Shared, u8> = Enc::new(0u8);
+        let two_u8: Enc<Shared, u8> = Enc::new(2u8);
+
+        let is_yes: Enc<Shared, u64> = vote.eq(&one_u8).cast();
+        let is_no: Enc<Shared, u64> = vote.eq(&zero_u8).cast();
+        let is_abstain: Enc<Shared, u64> = vote.eq(&two_u8).cast();
+
+        let yes_weight = is_yes * weight;
+        let no_weight = is_no * weight;
+        let abstain_weight = is_abstain * weight;
+        let valid_weight = yes_weight.clone() + no_weight.clone() + abstain_weight.clone();
+
+        (
+            state.owner.from_arcis(Tally {
+                yes: tally.yes + yes_weight,
+                no: tally.no + no_weight,
+                abstain: tally.abstain + abstain_weight,
+                total: tally.total + valid_weight,
+            }),
+            eligible.reveal(),
+        )
+    }
+
+    /// Reassemble a 32-byte value from its little-endian u128 halves.
+    fn bytes32(halves: [u128; 2]) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes[..16].copy_from_slice(&halves[0].to_le_bytes());
+        bytes[16..].copy_from_slice(&halves[1].to_le_bytes());
+        bytes
+    }
+
+    /// Derive a per-voter, per-proposal nullifier inside the MPC.
+    ///
+    /// `H(voter_secret, proposal_id)` is deterministic, so the same secret
+    /// always maps to the same nullifier for a proposal (double votes collide),
+    /// while nullifiers for different proposals are unlinkable. Only the hash
+    /// is revealed — the secret never leaves the secret-shared domain.
+    #[instruction]
+    pub fn derive_nullifier(voter_secret: Enc<Shared, u128>, proposal_id: u64) -> [u8; 32] {
+        let secret = voter_secret.to_arcis();
+
+        let mut preimage = [0u8; 24];
+        preimage[..16].copy_from_slice(&secret.to_le_bytes());
+        preimage[16..].copy_from_slice(&proposal_id.to_le_bytes());
+
+        SHA3_256::new().digest(&preimage).reveal()
+    }
+
+    /// Prove that a stored ballot was a NO vote, for ragequit eligibility.
+    ///
+    /// Reveals a single boolean — whether `vote == 0` — and nothing else.
+    /// Invoked only at the voter's own request (the voter signs the queueing
+    /// transaction), after a treasury-spending proposal has passed, so a
+    /// dissenting member can exit with their pro-rata share of the treasury.
+    ///
+    /// ## Arguments
+    /// * `vote` - The voter's stored `Enc<Shared, u8>` ballot from their VoteRecord
+    ///
+    /// ## Returns
+    /// `true` if the ballot was NO, `false` for YES, ABSTAIN, or invalid values.
+    #[instruction]
+    pub fn verify_no_vote(vote: Enc<Shared, u8>) -> bool {
+        let zero_u8: Enc<Shared, u8> = Enc::new(0u8);
+        vote.eq(&zero_u8).reveal()
+    }
 }
 
+// ==================== SHARED LAYOUT ====================
+
+// `#[encrypted]` modules only see `arcis_imports`, so the layout constants are
+// restated inside `circuits` and pinned to `voting-types` here: any drift from
+// the program / client encoding fails the build instead of corrupting tallies.
+const _: () = {
+    assert!(circuits::RANKED_OPTIONS == voting_types::RANKED_OPTIONS);
+    assert!(circuits::BALLOT_BATCH == voting_types::BALLOT_BATCH);
+    assert!(circuits::CONVICTION_BATCH == voting_types::CONVICTION_BATCH);
+    assert!(circuits::SNAPSHOT_DEPTH == voting_types::SNAPSHOT_DEPTH);
+    assert!(circuits::WEIGHT_MODE_QUADRATIC == voting_types::WEIGHT_MODE_QUADRATIC);
+};
+
 // ==================== TESTS ====================
 
 #[cfg(test)]
@@ -244,12 +1030,12 @@ mod tests {
 
         // Cast 3 YES, 2 NO, 1 ABSTAIN
         for _ in 0..3 {
-            state = cast_vote(state, Enc::new(1u8));
+            state = cast_vote(state, Enc::new(1u8)).0;
         }
         for _ in 0..2 {
-            state = cast_vote(state, Enc::new(0u8));
+            state = cast_vote(state, Enc::new(0u8)).0;
         }
-        state = cast_vote(state, Enc::new(2u8));
+        state = cast_vote(state, Enc::new(2u8)).0;
 
         let (yes, no, abstain, total) = finalize_and_reveal(state);
         assert_eq!(yes, 3);
@@ -258,13 +1044,428 @@ mod tests {
         assert_eq!(total, 6);
     }
 
+    #[test]
+    fn test_shared_choice_encoding() {
+        use voting_types::{CHOICE_ABSTAIN, CHOICE_COUNT, CHOICE_NO, CHOICE_YES};
+
+        let _ctx = TestContext::new();
+        let mut state = initialize_voting();
+
+        state = cast_vote(state, Enc::new(CHOICE_YES)).0;
+        state = cast_vote(state, Enc::new(CHOICE_NO)).0;
+        state = cast_vote(state, Enc::new(CHOICE_NO)).0;
+        state = cast_vote(state, Enc::new(CHOICE_ABSTAIN)).0;
+        state = cast_vote(state, Enc::new(CHOICE_COUNT)).0; // out of range
+
+        let (yes, no, abstain, total) = finalize_and_reveal(state);
+        let tally = voting_types::Tally {
+            yes,
+            no,
+            abstain,
+            total,
+        };
+        assert_eq!(
+            tally,
+            voting_types::Tally {
+                yes: 1,
+                no: 2,
+                abstain: 1,
+                total: 4
+            }
+        );
+        assert!(tally.is_consistent());
+    }
+
+    #[test]
+    fn test_weighted_voting_flow() {
+        let _ctx = TestContext::new();
+        let mut state = initialize_voting();
+
+        state = cast_weighted_vote(state, Enc::new(1u8), 100).0; // YES
+        state = cast_weighted_vote(state, Enc::new(0u8), 30).0; // NO
+        state = cast_weighted_vote(state, Enc::new(2u8), 5).0; // ABSTAIN
+        state = cast_weighted_vote(state, Enc::new(7u8), 1_000).0; // invalid
+
+        let (yes, no, abstain, total) = finalize_and_reveal(state);
+        assert_eq!(yes, 100);
+        assert_eq!(no, 30);
+        assert_eq!(abstain, 5);
+        assert_eq!(total, 135);
+    }
+
+    #[test]
+    fn test_ballot_receipts() {
+        let _ctx = TestContext::new();
+        let state = initialize_voting();
+
+        let (state, first) = cast_vote(state, Enc::new(1u8));
+        let (state, invalid) = cast_vote(state, Enc::new(5u8));
+        let (_, weighted) = cast_weighted_vote(state, Enc::new(0u8), 40);
+
+        let first = first.to_arcis();
+        assert_eq!((first.position, first.counted), (1, 1));
+        let invalid = invalid.to_arcis();
+        assert_eq!((invalid.position, invalid.counted), (1, 0));
+        let weighted = weighted.to_arcis();
+        assert_eq!((weighted.position, weighted.counted), (41, 40));
+    }
+
+    #[test]
+    fn test_revote_assigned_sequence_wins() {
+        let _ctx = TestContext::new();
+        let state = initialize_voting();
+
+        let (state, counted) = cast_sequenced_vote(state, Enc::new(1u8), 10); // YES
+        let (state, counted) = recast_vote(
+            state,
+            Enc::new(SequencedBallot {
+                choice: 0,
+                sequence: 1,
+            }),
+            counted,
+            1,
+        ); // NO
+           // A coerced ballot claiming an outranking sequence is ignored
+        let (state, counted) = recast_vote(
+            state,
+            Enc::new(SequencedBallot {
+                choice: 1,
+                sequence: u64::MAX,
+            }),
+            counted,
+            2,
+        );
+        // Replaying the counted ballot changes nothing either
+        let (state, counted) = recast_vote(
+            state,
+            Enc::new(SequencedBallot {
+                choice: 0,
+                sequence: 1,
+            }),
+            counted,
+            3,
+        );
+
+        let counted = counted.to_arcis();
+        assert_eq!(
+            (counted.choice, counted.sequence, counted.weight),
+            (0, 1, 10)
+        );
+        assert_eq!(finalize_and_reveal(state), (0, 10, 0, 10));
+    }
+
+    #[test]
+    fn test_revote_alongside_other_voters() {
+        let _ctx = TestContext::new();
+        let state = initialize_voting();
+
+        let (state, alice) = cast_sequenced_vote(state, Enc::new(1u8), 4); // YES
+        let (state, _bob) = cast_sequenced_vote(state, Enc::new(1u8), 6); // YES
+        let (state, alice) = recast_vote(
+            state,
+            Enc::new(SequencedBallot {
+                choice: 2,
+                sequence: 1,
+            }),
+            alice,
+            1,
+        ); // ABSTAIN
+           // A later invalid choice withdraws the counted ballot
+        let (state, _) = recast_vote(
+            state,
+            Enc::new(SequencedBallot {
+                choice: 9,
+                sequence: 2,
+            }),
+            alice,
+            2,
+        );
+
+        assert_eq!(finalize_and_reveal(state), (6, 0, 0, 6));
+    }
+
+    #[test]
+    fn test_encrypted_weight_voting_flow() {
+        let _ctx = TestContext::new();
+        let mut state = initialize_voting();
+
+        state = cast_vote_encrypted_weight(state, Enc::new(1u8), Enc::new(100u64), 500, 0).0; // YES, linear
+        state = cast_vote_encrypted_weight(state, Enc::new(0u8), Enc::new(50u64), 10, 1).0; // NO, quadratic → 7
+        state = cast_vote_encrypted_weight(state, Enc::new(2u8), Enc::new(80u64), 30, 0).0; // ABSTAIN, capped → 30
+        state = cast_vote_encrypted_weight(state, Enc::new(9u8), Enc::new(40u64), 40, 0).0; // invalid
+
+        let (yes, no, abstain, total) = finalize_and_reveal(state);
+        assert_eq!(yes, 100);
+        assert_eq!(no, 7);
+        assert_eq!(abstain, 30);
+        assert_eq!(total, 137);
+    }
+
+    #[test]
+    fn test_vote_with_delegations() {
+        let _ctx = TestContext::new();
+        let mut state = initialize_voting();
+
+        state = vote_with_delegations(Enc::new(1u8), 10, 90, state).0; // YES, 3 delegators
+        state = vote_with_delegations(Enc::new(0u8), 25, 0, state).0; // NO, no delegators
+        state = vote_with_delegations(Enc::new(4u8), 5, 50, state).0; // invalid
+
+        let (yes, no, abstain, total) = finalize_and_reveal(state);
+        assert_eq!(yes, 100);
+        assert_eq!(no, 25);
+        assert_eq!(abstain, 0);
+        assert_eq!(total, 125);
+    }
+
+    #[test]
+    fn test_resolve_tie() {
+        let _ctx = TestContext::new();
+
+        let mut yes_ahead = initialize_voting();
+        yes_ahead = cast_vote(yes_ahead, Enc::new(1u8)).0;
+        assert!(resolve_tie(yes_ahead));
+
+        let mut no_ahead = initialize_voting();
+        no_ahead = cast_vote(no_ahead, Enc::new(0u8)).0;
+        assert!(!resolve_tie(no_ahead));
+    }
+
+    #[test]
+    fn test_shuffled_batch_preserves_tally() {
+        let _ctx = TestContext::new();
+        let choices = [1u8, 1, 0, 2, 7, 1, 0, 9];
+
+        let batch = shuffle_ballots(choices.map(Enc::new));
+        let (yes, no, abstain, total) =
+            finalize_and_reveal(tally_ballot_batch(initialize_voting(), batch));
+        assert_eq!(yes, 3);
+        assert_eq!(no, 2);
+        assert_eq!(abstain, 1);
+        assert_eq!(total, 6); // two invalid ballots filtered out
+
+        // Same ballots cast one by one give the same result
+        let mut state = initialize_voting();
+        for choice in choices {
+            state = cast_vote(state, Enc::new(choice)).0;
+        }
+        assert_eq!(finalize_and_reveal(state), (3, 2, 1, 6));
+    }
+
+    #[test]
+    fn test_nullifier_is_deterministic_per_proposal() {
+        let _ctx = TestContext::new();
+        let secret = 0x5eed_u128 << 64 | 42;
+
+        let first = derive_nullifier(Enc::new(secret), 7);
+        assert_eq!(first, derive_nullifier(Enc::new(secret), 7));
+        assert_ne!(first, derive_nullifier(Enc::new(secret), 8));
+        assert_ne!(first, derive_nullifier(Enc::new(secret + 1), 7));
+    }
+
+    fn halves(bytes: [u8; 32]) -> [u128; 2] {
+        [
+            u128::from_le_bytes(bytes[..16].try_into().unwrap()),
+            u128::from_le_bytes(bytes[16..].try_into().unwrap()),
+        ]
+    }
+
+    fn sha3(data: &[u8]) -> [u8; 32] {
+        SHA3_256::new().digest(data)
+    }
+
+    /// Build a snapshot tree where only leaf 0 is populated: returns the
+    /// proof for leaf 0 and the root.
+    fn single_leaf_snapshot(
+        voter: [u8; 32],
+        weight: u64,
+    ) -> ([[u128; 2]; SNAPSHOT_DEPTH], [u128; 2]) {
+        let mut leaf = [0u8; 40];
+        leaf[..32].copy_from_slice(&voter);
+        leaf[32..].copy_from_slice(&weight.to_le_bytes());
+        let mut node = sha3(&leaf);
+        let mut empty = [0u8; 32];
+        let mut proof = [[0u128; 2]; SNAPSHOT_DEPTH];
+        for sibling in proof.iter_mut() {
+            *sibling = halves(empty);
+            node = sha3(&[node, empty].concat());
+            empty = sha3(&[empty, empty].concat());
+        }
+        (proof, halves(node))
+    }
+
+    #[test]
+    fn test_eligibility_proof_in_mpc() {
+        let _ctx = TestContext::new();
+        let voter = [3u8; 32];
+        let (proof, root) = single_leaf_snapshot(voter, 40);
+
+        // Correct weight: counted
+        let (state, eligible) = cast_vote_with_eligibility(
+            Enc::new(1u8),
+            initialize_voting(),
+            Enc::new(40u64),
+            halves(voter),
+            0,
+            proof,
+            root,
+        );
+        assert!(eligible);
+
+        // Inflated weight: proof fails, ballot adds nothing
+        let (state, eligible) = cast_vote_with_eligibility(
+            Enc::new(1u8),
+            state,
+            Enc::new(4_000u64),
+            halves(voter),
+            0,
+            proof,
+            root,
+        );
+        assert!(!eligible);
+
+        let (yes, no, abstain, total) = finalize_and_reveal(state);
+        assert_eq!(yes, 40);
+        assert_eq!(no, 0);
+        assert_eq!(abstain, 0);
+        assert_eq!(total, 40);
+    }
+
+    #[test]
+    fn test_conviction_weighting() {
+        let _ctx = TestContext::new();
+        let ends_at = 10_000i64;
+        let day = 1_000i64;
+
+        let mut choices = [0u8; CONVICTION_BATCH];
+        let mut voted_at = [0i64; CONVICTION_BATCH]; // padding by default
+        choices[0] = 1; // YES, 5 periods early → capped at 4x
+        voted_at[0] = ends_at - 5 * day;
+        choices[1] = 0; // NO, 1.5 periods early → 2x
+        voted_at[1] = ends_at - 3 * day / 2;
+        choices[2] = 2; // ABSTAIN, last minute → 1x
+        voted_at[2] = ends_at - 1;
+        choices[3] = 1; // YES, after the deadline → ignored
+        voted_at[3] = ends_at + 1;
+
+        let first = apply_conviction(
+            initialize_voting(),
+            choices.map(Enc::new),
+            voted_at,
+            ends_at,
+            day,
+            4,
+        );
+
+        let mut last = [0u8; CONVICTION_BATCH];
+        let mut last_at = [0i64; CONVICTION_BATCH];
+        last[0] = 0; // NO, 3 periods early → 4x
+        last_at[0] = ends_at - 3 * day;
+
+        let (yes, no, abstain, total) =
+            finalize_with_conviction(first, last.map(Enc::new), last_at, ends_at, day, 4);
+        assert_eq!(yes, 4);
+        assert_eq!(no, 6);
+        assert_eq!(abstain, 1);
+        assert_eq!(total, 11);
+    }
+
+    fn ranking_index(ranking: [u8; RANKED_OPTIONS]) -> u8 {
+        RANKINGS.iter().position(|r| *r == ranking).unwrap() as u8
+    }
+
+    #[test]
+    fn test_instant_runoff_redistributes() {
+        let _ctx = TestContext::new();
+        let mut state = initialize_ranked_voting();
+
+        // Option 0 leads on first preferences, but option 2's voters prefer 1
+        for _ in 0..4 {
+            state = cast_ranked_vote(state, Enc::new(ranking_index([0, 1, 2, 3])));
+        }
+        for _ in 0..3 {
+            state = cast_ranked_vote(state, Enc::new(ranking_index([1, 0, 2, 3])));
+        }
+        for _ in 0..2 {
+            state = cast_ranked_vote(state, Enc::new(ranking_index([2, 1, 0, 3])));
+        }
+
+        let (eliminated, winner) = run_instant_runoff(state);
+        assert_eq!(eliminated, [3, 2, 0]);
+        assert_eq!(winner, 1);
+    }
+
+    #[test]
+    fn test_instant_runoff_majority_wins_outright() {
+        let _ctx = TestContext::new();
+        let mut state = initialize_ranked_voting();
+
+        for _ in 0..5 {
+            state = cast_ranked_vote(state, Enc::new(ranking_index([3, 2, 1, 0])));
+        }
+        state = cast_ranked_vote(state, Enc::new(ranking_index([0, 1, 2, 3])));
+        state = cast_ranked_vote(state, Enc::new(ranking_index([1, 0, 2, 3])));
+
+        let (_, winner) = run_instant_runoff(state);
+        assert_eq!(winner, 3);
+    }
+
+    #[test]
+    fn test_instant_runoff_ignores_invalid_ballots() {
+        let _ctx = TestContext::new();
+        let mut state = initialize_ranked_voting();
+
+        state = cast_ranked_vote(state, Enc::new(ranking_index([2, 0, 1, 3])));
+        for _ in 0..10 {
+            state = cast_ranked_vote(state, Enc::new(99u8)); // out of range
+        }
+
+        let (eliminated, winner) = run_instant_runoff(state);
+        // Zero-vote options fall first, lowest index on ties
+        assert_eq!(eliminated, [0, 1, 3]);
+        assert_eq!(winner, 2);
+    }
+
+    #[test]
+    fn test_combine_tallies() {
+        let _ctx = TestContext::new();
+        let mut primary = initialize_voting();
+        let mut shard = initialize_voting();
+
+        primary = cast_vote(primary, Enc::new(1u8)).0; // YES
+        primary = cast_vote(primary, Enc::new(0u8)).0; // NO
+        shard = cast_vote(shard, Enc::new(1u8)).0; // YES
+        shard = cast_vote(shard, Enc::new(2u8)).0; // ABSTAIN
+
+        let (yes, no, abstain, total) = finalize_and_reveal(combine_tallies(primary, shard));
+        assert_eq!(yes, 2);
+        assert_eq!(no, 1);
+        assert_eq!(abstain, 1);
+        assert_eq!(total, 4);
+    }
+
+    #[test]
+    fn test_migrate_tally_preserves_counts() {
+        let _ctx = TestContext::new();
+        let mut state = initialize_voting();
+
+        state = cast_vote(state, Enc::new(1u8)).0; // YES
+        state = cast_vote(state, Enc::new(0u8)).0; // NO
+        state = cast_vote(state, Enc::new(1u8)).0; // YES
+
+        let (yes, no, abstain, total) = finalize_and_reveal(migrate_tally(state, Mxe::get()));
+        assert_eq!(yes, 2);
+        assert_eq!(no, 1);
+        assert_eq!(abstain, 0);
+        assert_eq!(total, 3);
+    }
+
     #[test]
     fn test_all_abstain() {
         let _ctx = TestContext::new();
         let mut state = initialize_voting();
 
         for _ in 0..5 {
-            state = cast_vote(state, Enc::new(2u8));
+            state = cast_vote(state, Enc::new(2u8)).0;
         }
 
         let (yes, no, abstain, total) = finalize_and_reveal(state);
@@ -290,7 +1491,7 @@ mod tests {
     fn test_single_yes_vote() {
         let _ctx = TestContext::new();
         let mut state = initialize_voting();
-        state = cast_vote(state, Enc::new(1u8));
+        state = cast_vote(state, Enc::new(1u8)).0;
 
         let (yes, no, abstain, total) = finalize_and_reveal(state);
         assert_eq!(yes, 1);
@@ -303,7 +1504,7 @@ mod tests {
     fn test_single_no_vote() {
         let _ctx = TestContext::new();
         let mut state = initialize_voting();
-        state = cast_vote(state, Enc::new(0u8));
+        state = cast_vote(state, Enc::new(0u8)).0;
 
         let (yes, no, abstain, total) = finalize_and_reveal(state);
         assert_eq!(yes, 0);
@@ -318,7 +1519,7 @@ mod tests {
         let mut state = initialize_voting();
 
         for _ in 0..10 {
-            state = cast_vote(state, Enc::new(1u8));
+            state = cast_vote(state, Enc::new(1u8)).0;
         }
 
         let (yes, no, abstain, total) = finalize_and_reveal(state);
@@ -334,7 +1535,7 @@ mod tests {
         let mut state = initialize_voting();
 
         for _ in 0..7 {
-            state = cast_vote(state, Enc::new(0u8));
+            state = cast_vote(state, Enc::new(0u8)).0;
         }
 
         let (yes, no, abstain, total) = finalize_and_reveal(state);
@@ -351,13 +1552,13 @@ mod tests {
 
         // Simulate 100 voters: 50 YES, 30 NO, 20 ABSTAIN
         for _ in 0..50 {
-            state = cast_vote(state, Enc::new(1u8));
+            state = cast_vote(state, Enc::new(1u8)).0;
         }
         for _ in 0..30 {
-            state = cast_vote(state, Enc::new(0u8));
+            state = cast_vote(state, Enc::new(0u8)).0;
         }
         for _ in 0..20 {
-            state = cast_vote(state, Enc::new(2u8));
+            state = cast_vote(state, Enc::new(2u8)).0;
         }
 
         let (yes, no, abstain, total) = finalize_and_reveal(state);
@@ -373,9 +1574,9 @@ mod tests {
         let mut state = initialize_voting();
 
         for _ in 0..4 {
-            state = cast_vote(state, Enc::new(1u8));
+            state = cast_vote(state, Enc::new(1u8)).0;
         }
-        state = cast_vote(state, Enc::new(0u8));
+        state = cast_vote(state, Enc::new(0u8)).0;
 
         let count = get_vote_count(state);
         assert_eq!(count, 5);
@@ -386,9 +1587,9 @@ mod tests {
         let _ctx = TestContext::new();
         let mut state = initialize_voting();
 
-        state = cast_vote(state, Enc::new(1u8)); // YES
-        state = cast_vote(state, Enc::new(1u8)); // YES
-        state = cast_vote(state, Enc::new(0u8)); // NO
+        state = cast_vote(state, Enc::new(1u8)).0; // YES
+        state = cast_vote(state, Enc::new(1u8)).0; // YES
+        state = cast_vote(state, Enc::new(0u8)).0; // NO
 
         let (yes, no, abstain, total) = get_live_tally(state.clone());
         assert_eq!(yes, 2);
@@ -396,7 +1597,7 @@ mod tests {
         assert_eq!(abstain, 0);
         assert_eq!(total, 3);
 
-        state = cast_vote(state, Enc::new(2u8)); // ABSTAIN
+        state = cast_vote(state, Enc::new(2u8)).0; // ABSTAIN
         let (yes, no, abstain, total) = get_live_tally(state);
         assert_eq!(yes, 2);
         assert_eq!(no, 1);
@@ -411,10 +1612,10 @@ mod tests {
 
         // 7 YES, 3 NO = 70% YES
         for _ in 0..7 {
-            state = cast_vote(state, Enc::new(1u8));
+            state = cast_vote(state, Enc::new(1u8)).0;
         }
         for _ in 0..3 {
-            state = cast_vote(state, Enc::new(0u8));
+            state = cast_vote(state, Enc::new(0u8)).0;
         }
 
         // Quorum = 5, threshold = 60% (6000 bps)
@@ -432,7 +1633,7 @@ mod tests {
         let mut state = initialize_voting();
 
         for _ in 0..3 {
-            state = cast_vote(state, Enc::new(1u8));
+            state = cast_vote(state, Enc::new(1u8)).0;
         }
 
         // Quorum = 5 (not met), threshold = 50%
@@ -448,10 +1649,10 @@ mod tests {
 
         // 4 YES, 6 NO = 40% YES
         for _ in 0..4 {
-            state = cast_vote(state, Enc::new(1u8));
+            state = cast_vote(state, Enc::new(1u8)).0;
         }
         for _ in 0..6 {
-            state = cast_vote(state, Enc::new(0u8));
+            state = cast_vote(state, Enc::new(0u8)).0;
         }
 
         // Quorum = 5 (met), threshold = 50% (not met)
@@ -469,13 +1670,13 @@ mod tests {
 
         // 3 YES, 2 NO, 5 ABSTAIN = 60% of non-abstain
         for _ in 0..3 {
-            state = cast_vote(state, Enc::new(1u8));
+            state = cast_vote(state, Enc::new(1u8)).0;
         }
         for _ in 0..2 {
-            state = cast_vote(state, Enc::new(0u8));
+            state = cast_vote(state, Enc::new(0u8)).0;
         }
         for _ in 0..5 {
-            state = cast_vote(state, Enc::new(2u8));
+            state = cast_vote(state, Enc::new(2u8)).0;
         }
 
         // Threshold = 60% of non-abstain (3/5 = 60%, exactly meets 6000 bps)
@@ -493,15 +1694,15 @@ mod tests {
         let mut state = initialize_voting();
 
         // Cast 2 valid votes
-        state = cast_vote(state, Enc::new(1u8)); // YES
-        state = cast_vote(state, Enc::new(0u8)); // NO
+        state = cast_vote(state, Enc::new(1u8)).0; // YES
+        state = cast_vote(state, Enc::new(0u8)).0; // NO
 
         // Cast invalid votes — should be silently ignored
-        state = cast_vote(state, Enc::new(3u8)); // invalid
-        state = cast_vote(state, Enc::new(255u8)); // invalid
+        state = cast_vote(state, Enc::new(3u8)).0; // invalid
+        state = cast_vote(state, Enc::new(255u8)).0; // invalid
 
         // Cast 1 more valid vote
-        state = cast_vote(state, Enc::new(2u8)); // ABSTAIN
+        state = cast_vote(state, Enc::new(2u8)).0; // ABSTAIN
 
         let (yes, no, abstain, total) = finalize_and_reveal(state);
         assert_eq!(yes, 1);
@@ -516,9 +1717,9 @@ mod tests {
         let _ctx = TestContext::new();
         let mut state = initialize_voting();
 
-        state = cast_vote(state, Enc::new(3u8));
-        state = cast_vote(state, Enc::new(4u8));
-        state = cast_vote(state, Enc::new(100u8));
+        state = cast_vote(state, Enc::new(3u8)).0;
+        state = cast_vote(state, Enc::new(4u8)).0;
+        state = cast_vote(state, Enc::new(100u8)).0;
 
         let (yes, no, abstain, total) = finalize_and_reveal(state);
         assert_eq!(yes, 0);
@@ -532,11 +1733,11 @@ mod tests {
         let _ctx = TestContext::new();
         let mut state = initialize_voting();
 
-        state = cast_vote(state, Enc::new(1u8)); // YES
-        state = cast_vote(state, Enc::new(0u8)); // NO
-        state = cast_vote(state, Enc::new(2u8)); // ABSTAIN
-        state = cast_vote(state, Enc::new(1u8)); // YES
-        state = cast_vote(state, Enc::new(0u8)); // NO
+        state = cast_vote(state, Enc::new(1u8)).0; // YES
+        state = cast_vote(state, Enc::new(0u8)).0; // NO
+        state = cast_vote(state, Enc::new(2u8)).0; // ABSTAIN
+        state = cast_vote(state, Enc::new(1u8)).0; // YES
+        state = cast_vote(state, Enc::new(0u8)).0; // NO
 
         let (yes, no, abstain, total) = finalize_and_reveal(state);
 
@@ -546,4 +1747,14 @@ mod tests {
         assert_eq!(no, 2);
         assert_eq!(abstain, 1);
     }
+
+    #[test]
+    fn test_verify_no_vote() {
+        let _ctx = TestContext::new();
+
+        assert!(verify_no_vote(Enc::new(0u8))); // NO
+        assert!(!verify_no_vote(Enc::new(1u8))); // YES
+        assert!(!verify_no_vote(Enc::new(2u8))); // ABSTAIN
+        assert!(!verify_no_vote(Enc::new(255u8))); // invalid
+    }
 }
@@ -0,0 +1,118 @@
+//! # Private DAO Voting — Shared Types
+//!
+//! Single source of truth for the wire format shared by the Anchor program,
+//! the Arcis circuit and the client SDK (`frontend/lib/voting-types.ts`
+//! mirrors this file). Anything that changes here is a ballot layout change
+//! and must bump [`BALLOT_VERSION`].
+//!
+//! ## Vote Encoding
+//!
+//! - `0` = NO
+//! - `1` = YES
+//! - `2` = ABSTAIN
+//!
+//! ## Tally Ciphertext
+//!
+//! `Enc<Mxe, Tally>` is stored on-chain as four 32-byte ciphertexts, one per
+//! `u64` field, in declaration order (`yes`, `no`, `abstain`, `total`).
+
+#![no_std]
+
+/// Encrypted choice value for a NO vote
+pub const CHOICE_NO: u8 = 0;
+/// Encrypted choice value for a YES vote
+pub const CHOICE_YES: u8 = 1;
+/// Encrypted choice value for an ABSTAIN vote
+pub const CHOICE_ABSTAIN: u8 = 2;
+/// Number of valid choices; anything `>= CHOICE_COUNT` is counted as nothing
+pub const CHOICE_COUNT: u8 = 3;
+
+/// Ciphertext layout of ballots and tallies understood by the current circuits
+/// (v1: `Enc<Shared, u8>` choice, 4 × u64 `Enc<Mxe, Tally>`)
+pub const BALLOT_VERSION: u8 = 1;
+
+/// Size of one Rescue ciphertext field element
+pub const CIPHERTEXT_LEN: usize = 32;
+/// Number of `u64` fields in [`Tally`]
+pub const TALLY_FIELDS: usize = 4;
+/// Size of an encrypted [`Tally`] as stored in the on-chain tally account
+pub const TALLY_CIPHERTEXT_LEN: usize = CIPHERTEXT_LEN * TALLY_FIELDS;
+
+/// Weight-mode flag sent alongside an encrypted ballot weight
+pub const WEIGHT_MODE_LINEAR: u8 = 0;
+pub const WEIGHT_MODE_QUADRATIC: u8 = 1;
+
+/// Options on a ranked-choice ballot
+pub const RANKED_OPTIONS: usize = 4;
+/// Ballots shuffled together by `shuffle_ballots`
+pub const BALLOT_BATCH: usize = 8;
+/// Conviction-weighted ballots aggregated per `apply_conviction` call
+pub const CONVICTION_BATCH: usize = 16;
+/// Depth of the eligibility snapshot Merkle tree
+pub const SNAPSHOT_DEPTH: usize = 16;
+
+/// Plaintext shape of the MXE-owned tally.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub yes: u64,
+    pub no: u64,
+    pub abstain: u64,
+    pub total: u64,
+}
+
+impl Tally {
+    /// Integrity invariant enforced by the circuit: `yes + no + abstain == total`
+    pub fn is_consistent(&self) -> bool {
+        self.yes
+            .checked_add(self.no)
+            .and_then(|sum| sum.checked_add(self.abstain))
+            == Some(self.total)
+    }
+}
+
+/// Split an encrypted tally into its per-field ciphertexts.
+pub fn split_tally_ciphertext(
+    data: [u8; TALLY_CIPHERTEXT_LEN],
+) -> [[u8; CIPHERTEXT_LEN]; TALLY_FIELDS] {
+    let mut out = [[0u8; CIPHERTEXT_LEN]; TALLY_FIELDS];
+    for (i, field) in out.iter_mut().enumerate() {
+        field.copy_from_slice(&data[i * CIPHERTEXT_LEN..(i + 1) * CIPHERTEXT_LEN]);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_tally_ciphertext_preserves_field_order() {
+        let mut data = [0u8; TALLY_CIPHERTEXT_LEN];
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = (i / CIPHERTEXT_LEN) as u8;
+        }
+        let fields = split_tally_ciphertext(data);
+        for (i, field) in fields.iter().enumerate() {
+            assert!(field.iter().all(|&b| b == i as u8));
+        }
+    }
+
+    #[test]
+    fn test_tally_consistency() {
+        let tally = Tally {
+            yes: 3,
+            no: 2,
+            abstain: 1,
+            total: 6,
+        };
+        assert!(tally.is_consistent());
+        assert!(!Tally { total: 5, ..tally }.is_consistent());
+        assert!(!Tally {
+            yes: u64::MAX,
+            no: 1,
+            abstain: 0,
+            total: 0
+        }
+        .is_consistent());
+    }
+}
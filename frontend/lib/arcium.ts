@@ -7,6 +7,7 @@
 import { Connection, PublicKey } from "@solana/web3.js";
 import { AnchorProvider, BN } from "@coral-xyz/anchor";
 import { PROGRAM_ID } from "./contract";
+import { VoteChoice } from "./voting-types";
 import {
   RescueCipher,
   x25519,
@@ -218,7 +219,7 @@ export class ArciumClient {
   // ==================== ENCRYPT ====================
 
   async encryptVote(
-    vote: 0 | 1 | 2,
+    vote: VoteChoice,
     proposalPubkey: PublicKey,
     voterPubkey: PublicKey
   ): Promise<EncryptedVote> {
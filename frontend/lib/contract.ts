@@ -172,6 +172,46 @@ export async function getDelegation(
   }
 }
 
+/**
+ * Effective voting power of `voter` on a token-gated proposal, resolved
+ * on-chain by simulating `get_voting_power`. Pass incoming delegators as
+ * [delegation, delegator token account] pairs via `incoming`.
+ */
+export async function getVotingPower(
+  program: Program,
+  voter: PublicKey,
+  proposalPDA: PublicKey,
+  voterTokenAccount: PublicKey,
+  incoming: { delegation: PublicKey; tokenAccount: PublicKey }[] = []
+): Promise<{ ownWeight: number; delegatedWeight: number; total: number; delegatedAway: boolean }> {
+  const [delegationPDA] = findDelegationPDA(voter);
+  const outgoing = await getDelegation(program, voter);
+  const power: any = await (program.methods as any)
+    .getVotingPower()
+    .accountsPartial({
+      voter,
+      proposal: proposalPDA,
+      voterTokenAccount,
+      voterStakeAccount: null,
+      stakePool: null,
+      reputation: null,
+      delegation: outgoing ? delegationPDA : null,
+    })
+    .remainingAccounts(
+      incoming.flatMap(({ delegation, tokenAccount }) => [
+        { pubkey: delegation, isSigner: false, isWritable: false },
+        { pubkey: tokenAccount, isSigner: false, isWritable: false },
+      ])
+    )
+    .view();
+  return {
+    ownWeight: power.ownWeight.toNumber(),
+    delegatedWeight: power.delegatedWeight.toNumber(),
+    total: power.total.toNumber(),
+    delegatedAway: power.delegatedAway,
+  };
+}
+
 // Dev mode: Initialize tally for a proposal
 export async function devInitTally(
   program: Program,
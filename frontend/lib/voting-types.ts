@@ -0,0 +1,46 @@
+/**
+ * Ballot encodings and ciphertext layout shared with the program and circuit.
+ *
+ * Mirrors `crates/voting-types/src/lib.rs` — keep the two in lockstep and bump
+ * BALLOT_VERSION on any layout change.
+ */
+
+export const CHOICE_NO = 0;
+export const CHOICE_YES = 1;
+export const CHOICE_ABSTAIN = 2;
+
+/** Encrypted choice value understood by the `cast_vote` circuits */
+export type VoteChoice = typeof CHOICE_NO | typeof CHOICE_YES | typeof CHOICE_ABSTAIN;
+
+export type VoteLabel = "yes" | "no" | "abstain";
+
+/** Ciphertext layout version expected by the on-chain program */
+export const BALLOT_VERSION = 1;
+
+/** One Rescue ciphertext field element */
+export const CIPHERTEXT_LEN = 32;
+/** `Enc<Mxe, Tally>`: four u64 fields (yes, no, abstain, total) */
+export const TALLY_FIELDS = 4;
+export const TALLY_CIPHERTEXT_LEN = CIPHERTEXT_LEN * TALLY_FIELDS;
+
+export const WEIGHT_MODE_LINEAR = 0;
+export const WEIGHT_MODE_QUADRATIC = 1;
+
+/** Plaintext shape of the revealed tally */
+export interface Tally {
+  yes: bigint;
+  no: bigint;
+  abstain: bigint;
+  total: bigint;
+}
+
+export function encodeChoice(label: VoteLabel): VoteChoice {
+  switch (label) {
+    case "yes":
+      return CHOICE_YES;
+    case "abstain":
+      return CHOICE_ABSTAIN;
+    case "no":
+      return CHOICE_NO;
+  }
+}
@@ -28,6 +28,7 @@ import {
   deriveComputationOffset,
 } from "../lib/arcium";
 import { parseAnchorError, explorerTxUrl } from "../lib/errors";
+import { encodeChoice } from "../lib/voting-types";
 import { withRetry } from "../lib/retry";
 import { LockIcon, ShieldCheckIcon } from "../components/Icons";
 import { Toast, ToastData } from "../components/Toast";
@@ -386,7 +387,7 @@ export default function Home() {
         setArciumClient(client);
       }
 
-      const voteValue: 0 | 1 | 2 = choice === "yes" ? 1 : choice === "abstain" ? 2 : 0;
+      const voteValue = encodeChoice(choice);
       setIsEncrypting(true);
       const encryptedVote = await client.encryptVote(voteValue, proposal.publicKey, publicKey);
       const secretInput = client.toSecretInput(encryptedVote, publicKey);
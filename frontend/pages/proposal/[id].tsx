@@ -29,6 +29,7 @@ import { ProposalCard, Proposal } from "../../components/ProposalCard";
 import { Toast, ToastData } from "../../components/Toast";
 import { LockIcon, ShieldCheckIcon } from "../../components/Icons";
 import { parseAnchorError, explorerTxUrl } from "../../lib/errors";
+import { encodeChoice } from "../../lib/voting-types";
 
 import generatedIdl from "../../idl/private_dao_voting.json";
 
@@ -160,7 +161,7 @@ export default function ProposalDetail() {
         setArciumClient(client);
       }
 
-      const voteValue: 0 | 1 | 2 = choice === "yes" ? 1 : choice === "abstain" ? 2 : 0;
+      const voteValue = encodeChoice(choice);
       setIsEncrypting(true);
       const encryptedVote = await client.encryptVote(voteValue, p.publicKey, publicKey);
       const secretInput = client.toSecretInput(encryptedVote, publicKey);
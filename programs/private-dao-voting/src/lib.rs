@@ -43,6 +43,16 @@
 //! └─────────────────────────────────────────────────────────────────────┘
 //! ```
 //!
+//! ## Program-Initiated Proposals
+//!
+//! Other programs may open votes by CPI into `create_proposal`. The caller
+//! must be on `DaoConfig.proposer_programs` and sign as its own
+//! `[PROGRAM_PROPOSER_SEED]` PDA, passing itself as `caller_program`; that
+//! PDA is the proposal authority, pays the rent and posts the deposit. Any
+//! other signer reaching `create_proposal` through CPI is rejected, so an
+//! intermediary program cannot open proposals with a user's forwarded
+//! signature.
+//!
 //! ## Encryption Types
 //!
 //! - `Enc<Shared, u8>`: Individual vote — client-encrypted via x25519 ECDH.
@@ -58,11 +68,28 @@
 //! a modified circuit, the hash mismatch causes computation to abort.
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_lang::solana_program::instruction::{
+    get_stack_height, AccountMeta, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT,
+};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::system_program;
+use anchor_spl::token::{
+    self, Burn, CloseAccount, FreezeAccount, Mint, MintTo, ThawAccount, Token, TokenAccount,
+    Transfer,
+};
+use arcium_client::idl::arcium::accounts::MXEAccount;
+use arcium_client::idl::arcium::client::args::CallbackComputation;
 use arcium_client::idl::arcium::cpi::{accounts::QueueComputation, queue_computation};
 use arcium_client::idl::arcium::program::Arcium;
-use arcium_client::idl::arcium::types::{ArgumentList, ArgumentRef, CallbackInstruction};
-use arcium_client::pda::comp_def_offset;
+use arcium_client::idl::arcium::types::{
+    ArgumentList, ArgumentRef, CallbackInstruction, ExecutionStatus,
+};
+use arcium_client::pda::{comp_def_offset, mxe_acc};
+use solana_sdk_ids::ed25519_program;
+use solana_sha256_hasher::hashv;
+use solana_stake_interface::{self as stake, state::StakeStateV2};
+use voting_types::split_tally_ciphertext;
 
 declare_id!("71tbXM3A2j5pKHfjtu1LYgY8jfQWuoZtHecDu6F6EPJH");
 
@@ -78,11 +105,188 @@ pub const DELEGATION_SEED: &[u8] = b"delegation";
 pub const DAO_CONFIG_SEED: &[u8] = b"dao_config";
 pub const PROPOSAL_COUNTER_SEED: &[u8] = b"proposal_counter";
 pub const DEPOSIT_ESCROW_SEED: &[u8] = b"deposit_escrow";
-
-/// Maximum active proposals per wallet (anti-spam)
+pub const SLOT_STAKE_SEED: &[u8] = b"slot_stake";
+pub const SOL_BOND_SEED: &[u8] = b"sol_bond";
+pub const REVENUE_EPOCH_SEED: &[u8] = b"revenue_epoch";
+pub const REVENUE_CLAIM_SEED: &[u8] = b"revenue_claim";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const INVOKE_AUTHORITY_SEED: &[u8] = b"invoke_authority";
+pub const SOL_TREASURY_SEED: &[u8] = b"sol_treasury";
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority";
+pub const GOVERNANCE_MINT_SEED: &[u8] = b"governance_mint";
+pub const POWER_AUTHORITY_SEED: &[u8] = b"power_authority";
+pub const POWER_ASSET_SEED: &[u8] = b"power_asset";
+pub const POWER_VAULT_SEED: &[u8] = b"power_vault";
+pub const POWER_DEPOSIT_SEED: &[u8] = b"power_deposit";
+pub const PRICE_FEED_SEED: &[u8] = b"price_feed";
+pub const REPUTATION_SEED: &[u8] = b"reputation";
+pub const JURY_POOL_SEED: &[u8] = b"jury_pool";
+pub const JURY_ROSTER_SEED: &[u8] = b"jury_roster";
+pub const PROPOSAL_ARCHIVE_SEED: &[u8] = b"proposal_archive";
+pub const AUDIT_BUNDLE_SEED: &[u8] = b"audit_bundle";
+pub const TRANSLATION_SEED: &[u8] = b"translation";
+pub const DENYLIST_SEED: &[u8] = b"denylist";
+pub const MODERATION_SEED: &[u8] = b"moderation";
+pub const APPEAL_BOND_SEED: &[u8] = b"appeal_bond";
+pub const SPEND_TRACKER_SEED: &[u8] = b"spend_tracker";
+pub const STREAM_SEED: &[u8] = b"stream";
+pub const GRANT_SEED: &[u8] = b"grant";
+pub const VESTING_SEED: &[u8] = b"vesting";
+pub const VESTING_VAULT_SEED: &[u8] = b"vesting_vault";
+pub const VOTER_ESCROW_SEED: &[u8] = b"voter_escrow";
+pub const ESCROW_VAULT_SEED: &[u8] = b"escrow_vault";
+pub const PETITION_SEED: &[u8] = b"petition";
+pub const PETITION_SIGNATURE_SEED: &[u8] = b"petition_signature";
+pub const RECOUNT_SEED: &[u8] = b"recount";
+pub const RECOUNT_BOND_SEED: &[u8] = b"recount_bond";
+pub const ATTESTATION_SEED: &[u8] = b"attestation";
+pub const WALLET_PROFILE_SEED: &[u8] = b"wallet_profile";
+pub const BALLOT_SEQUENCE_SEED: &[u8] = b"ballot_sequence";
+pub const DELEGATION_NONCE_SEED: &[u8] = b"delegation_nonce";
+pub const RELAYER_SEED: &[u8] = b"relayer";
+pub const VOTER_PROFILE_SEED: &[u8] = b"voter_profile";
+/// Longest participation window a `VoterProfile` can answer for
+pub const MAX_PARTICIPATION_WINDOW: u8 = 64;
+pub const ENDORSEMENT_SEED: &[u8] = b"endorsement";
+pub const RELAYER_BOND_SEED: &[u8] = b"relayer_bond";
+/// Seconds an unbonding relayer stays slashable before withdrawing its bond
+pub const RELAYER_UNBONDING_PERIOD: i64 = 7 * 86_400;
+pub const NULLIFIER_SEED: &[u8] = b"nullifier";
+pub const PENDING_COMPUTATION_SEED: &[u8] = b"pending_computation";
+pub const COMMITTEE_SEED: &[u8] = b"committee";
+pub const COMMITTEE_VAULT_SEED: &[u8] = b"committee_vault";
+pub const EXECUTION_PAYLOAD_SEED: &[u8] = b"execution_payload";
+pub const OUTBOX_SEED: &[u8] = b"outbox";
+pub const OBLIGATION_SEED: &[u8] = b"obligation";
+
+/// Maximum active proposals per wallet (anti-spam), enforced through
+/// `ProposalCounter` when the holdings-based rate limit is enabled
 pub const MAX_ACTIVE_PROPOSALS: u8 = 3;
 /// Cooldown in seconds between proposals from the same wallet
 pub const PROPOSAL_COOLDOWN: i64 = 3600;
+/// SPL stake-pool program. Pool accounts are read by offset (see `stake_pool_lamports`).
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey =
+    pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+/// Bonfida token-vesting program. Schedule accounts are read by offset (see `vesting_power`).
+pub const BONFIDA_VESTING_PROGRAM_ID: Pubkey =
+    pubkey!("CChTq6PthWU82YZkbveA3WDf7s97BWhBK4Vx9bmsT743");
+/// Pyth pull-oracle receiver program, owner of `PriceUpdateV2` accounts.
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+/// Anchor discriminator of Pyth's `PriceUpdateV2` account.
+pub const PYTH_PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+/// Switchboard On-Demand program, owner of `RandomnessAccountData` accounts.
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: Pubkey =
+    pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+/// Anchor discriminator of Switchboard's `RandomnessAccountData` account.
+pub const SWITCHBOARD_RANDOMNESS_DISCRIMINATOR: [u8; 8] = [10, 66, 229, 135, 220, 239, 217, 114];
+/// USD amounts (gates, deposits) are expressed with 6 decimals.
+pub const USD_DECIMALS: u8 = 6;
+/// Maximum number of wallets in a committee's proposer set
+pub const MAX_COMMITTEE_PROPOSERS: usize = 10;
+/// Maximum number of actions in a proposal's execution payload
+pub const MAX_PROPOSAL_ACTIONS: usize = 8;
+/// Maximum typed links attached to a proposal
+pub const MAX_PROPOSAL_LINKS: usize = 4;
+/// Maximum length of a single proposal link URL
+pub const MAX_LINK_LEN: usize = 128;
+/// URL schemes accepted for proposal links
+pub const LINK_SCHEMES: [&str; 2] = ["https://", "ipfs://"];
+/// Maximum length of a translation's content CID
+pub const MAX_CID_LEN: usize = 64;
+/// Minimum threshold (basis points) for a follow-up vote that cancels a queued execution
+pub const SUPERMAJORITY_BPS: u16 = 6667;
+
+/// Maximum approved MXE program / cluster pairs in `DaoConfig`
+pub const MAX_APPROVED_MXES: usize = 8;
+/// Balance changes kept per `VoterEscrow`; older ones are pruned
+pub const MAX_ESCROW_CHECKPOINTS: usize = 16;
+/// Maximum tranches in a milestone grant
+pub const MAX_GRANT_TRANCHES: usize = 4;
+/// Maximum extra programs execution payloads may invoke
+pub const MAX_ALLOWED_PROGRAMS: usize = 8;
+/// Maximum registered result observers in `DaoConfig`
+pub const MAX_OBSERVERS: usize = 16;
+/// Maximum programs allowed to create proposals through CPI
+pub const MAX_PROPOSER_PROGRAMS: usize = 8;
+/// Seed of the PDA, derived under the calling program, that signs
+/// CPI-created proposals
+pub const PROGRAM_PROPOSER_SEED: &[u8] = b"program_proposer";
+/// Maximum accounts in an `Invoke` action
+pub const MAX_INVOKE_ACCOUNTS: usize = 8;
+/// Maximum instruction data bytes in an `Invoke` action
+pub const MAX_INVOKE_DATA: usize = 128;
+/// Seconds after the deadline during which `apply_delegate_fallback` may run
+/// and `reveal_results` waits
+pub const DELEGATE_FALLBACK_WINDOW: i64 = 3_600;
+/// Maximum addresses a proposal can exclude from voting
+pub const MAX_EXCLUDED_VOTERS: usize = 8;
+/// Maximum voter tiers in `DaoConfig`
+pub const MAX_VOTER_TIERS: usize = 4;
+/// `VoterTier.rights`: may create proposals
+pub const TIER_RIGHT_CREATE_PROPOSAL: u8 = 1 << 0;
+/// `VoterTier.rights`: may sign veto petitions
+pub const TIER_RIGHT_SIGN_PETITION: u8 = 1 << 1;
+/// `VoterTier.rights`: binding proposals post no deposit
+pub const TIER_RIGHT_FEE_EXEMPT: u8 = 1 << 2;
+/// Rights of every wallet while no tiers are configured
+pub const DEFAULT_TIER_RIGHTS: u8 = TIER_RIGHT_CREATE_PROPOSAL | TIER_RIGHT_SIGN_PETITION;
+/// Maximum payload bytes of a `PostObligation` action
+pub const MAX_OBLIGATION_DATA: usize = 128;
+/// Maximum priced mints a `UsdValue` gate accepts
+pub const MAX_GATE_MINTS: usize = 8;
+/// Maximum per-mint weight multipliers in `DaoConfig`
+pub const MAX_MINT_MULTIPLIERS: usize = 8;
+/// Largest per-mint weight multiplier, in basis points (5x)
+pub const MAX_MULTIPLIER_BPS: u16 = 50_000;
+/// Maximum delegators folded into one `cast_delegated_vote`
+pub const MAX_DELEGATORS_PER_VOTE: usize = 16;
+/// Maximum tally shards per proposal (shard 0 is the primary tally)
+pub const MAX_TALLY_SHARDS: u8 = 8;
+/// Maximum revealed proposals sharing one revenue epoch (width of the claim bitmap)
+pub const MAX_EPOCH_PROPOSALS: usize = 64;
+/// Maximum turnout-boost tiers per revenue epoch
+pub const MAX_TURNOUT_BOOSTS: usize = 4;
+/// Domain tag prefixed to off-chain signed delegation messages
+pub const DELEGATION_MESSAGE_DOMAIN: &[u8] = b"private-dao-voting:delegate:v2";
+/// Domain tag prefixed to off-chain signed ballot messages
+pub const BALLOT_MESSAGE_DOMAIN: &[u8] = b"private-dao-voting:ballot:v2";
+/// Optional weight accounts a signed ballot message commits to
+pub const BALLOT_WEIGHT_ACCOUNTS: usize = 5;
+/// Domain tag prefixed to a relayer's signed acknowledgement of a ballot
+pub const RELAY_RECEIPT_DOMAIN: &[u8] = b"private-dao-voting:relay-receipt:v1";
+/// Default share of the deposit slashed under the graded schedule when turnout
+/// reached at least half of quorum (`DaoConfig.graded_slash_partial_bps`)
+pub const DEFAULT_GRADED_SLASH_PARTIAL_BPS: u16 = 5_000;
+
+/// Seconds a recount's reveal computation may stay outstanding before
+/// `expire_recount` lifts the halt
+pub const RECOUNT_TIMEOUT: i64 = 86_400;
+
+/// Slots a `migrate_tally` computation may stay outstanding before
+/// `cancel_tally_migration` may abandon it (~1 hour)
+pub const MIGRATION_TIMEOUT_SLOTS: u64 = 9_000;
+
+/// Slots after which an unanswered `merge_tally_shard` may be queued again
+pub const MERGE_TIMEOUT_SLOTS: u64 = 9_000;
+
+/// Slots after which an unanswered `resolve_tie` may be queued again
+pub const TIE_BREAK_TIMEOUT_SLOTS: u64 = 9_000;
+
+/// Seconds after a moderation flag during which the creator may appeal
+pub const MODERATION_APPEAL_WINDOW: i64 = 3 * 24 * 60 * 60;
+
+/// Nominal slot time used to derive `voting_ends_slot` from the deadline
+pub const NOMINAL_MS_PER_SLOT: i64 = 400;
+
+/// Default seconds after `voting_ends_at` during which callbacks for ballots
+/// cast before the deadline are still accepted (`DaoConfig.callback_grace_period`)
+pub const DEFAULT_CALLBACK_GRACE_PERIOD: i64 = 600;
+/// Maximum vote records folded into the Merkle root per compaction call
+pub const MAX_COMPACTION_BATCH: usize = 16;
+/// Maximum registrants in a sortition pool
+pub const MAX_JURY_POOL: usize = 64;
+/// Maximum jurors drawn for a proposal
+pub const MAX_JURY_SIZE: usize = 32;
 
 /// Privacy levels
 pub const PRIVACY_FULL: u8 = 0;
@@ -92,10 +296,27 @@ pub const PRIVACY_TRANSPARENT: u8 = 2;
 /// Computation definition names (must match #[instruction] names in the Arcis circuit)
 pub const INIT_TALLY_COMP: &str = "initialize_voting";
 pub const VOTE_COMP: &str = "cast_vote";
+pub const WEIGHTED_VOTE_COMP: &str = "cast_weighted_vote";
+pub const ENCRYPTED_WEIGHT_VOTE_COMP: &str = "cast_vote_encrypted_weight";
+pub const DELEGATED_VOTE_COMP: &str = "vote_with_delegations";
 pub const REVEAL_RESULT_COMP: &str = "finalize_and_reveal";
 pub const REVEAL_WITH_THRESHOLD_COMP: &str = "finalize_with_threshold";
 pub const LIVE_TALLY_COMP: &str = "get_live_tally";
 pub const VOTE_COUNT_COMP: &str = "get_vote_count";
+pub const VERIFY_NO_VOTE_COMP: &str = "verify_no_vote";
+pub const COMBINE_TALLIES_COMP: &str = "combine_tallies";
+pub const MIGRATE_TALLY_COMP: &str = "migrate_tally";
+pub const TIE_BREAK_COMP: &str = "resolve_tie";
+pub const NULLIFIER_COMP: &str = "derive_nullifier";
+pub const ELIGIBLE_VOTE_COMP: &str = "cast_vote_with_eligibility";
+pub const SEQUENCED_VOTE_COMP: &str = "cast_sequenced_vote";
+pub const RECAST_VOTE_COMP: &str = "recast_vote";
+
+/// Ballot encodings and ciphertext layout, shared with the circuit and client
+pub use voting_types::{
+    BALLOT_VERSION, CHOICE_ABSTAIN, CHOICE_NO, CHOICE_YES, SNAPSHOT_DEPTH, WEIGHT_MODE_LINEAR,
+    WEIGHT_MODE_QUADRATIC,
+};
 
 /// SHA-256 hash of the compiled voting circuit bytecode, embedded at build time.
 /// Used to verify MPC logic integrity during computation definition initialization.
@@ -105,15 +326,66 @@ pub const VOTE_COUNT_COMP: &str = "get_vote_count";
 /// In dev/test: hardcoded placeholder (circuit isn't compiled during `anchor build`)
 pub const CIRCUIT_HASH: &str = "dev-mode-circuit-hash-placeholder";
 
-fn split_ciphertext_128(data: [u8; 128]) -> [[u8; 32]; 4] {
-    let mut out = [[0u8; 32]; 4];
-    for i in 0..4 {
-        out[i].copy_from_slice(&data[i * 32..(i + 1) * 32]);
+/// Arguments for `cast_vote`, or for `cast_vote_encrypted_weight` when
+/// `encrypted_weight` carries an `Enc<Shared, u64>` weight, its plaintext cap
+/// and weight-mode flag (`WEIGHT_MODE_*`).
+fn build_args_for_vote(
+    encrypted_choice: [u8; 32],
+    tally: [u8; 128],
+    encrypted_weight: Option<([u8; 32], u64, u8)>,
+) -> ArgumentList {
+    let mut args = ArgumentList {
+        args: Vec::new(),
+        byte_arrays: Vec::new(),
+        plaintext_numbers: Vec::new(),
+        values_128_bit: Vec::new(),
+        accounts: Vec::new(),
+    };
+
+    args.args
+        .push(ArgumentRef::EncryptedU8(args.byte_arrays.len() as u8));
+    args.byte_arrays.push(encrypted_choice);
+
+    for chunk in split_tally_ciphertext(tally) {
+        args.args
+            .push(ArgumentRef::EncryptedU64(args.byte_arrays.len() as u8));
+        args.byte_arrays.push(chunk);
+    }
+
+    if let Some((weight, max_weight, weight_mode)) = encrypted_weight {
+        args.args
+            .push(ArgumentRef::EncryptedU64(args.byte_arrays.len() as u8));
+        args.byte_arrays.push(weight);
+        args.args
+            .push(ArgumentRef::PlaintextU64(args.plaintext_numbers.len() as u8));
+        args.plaintext_numbers.push(max_weight);
+        args.args.push(ArgumentRef::PlaintextU8(weight_mode));
     }
-    out
+
+    args
+}
+
+fn build_args_for_weighted_vote(
+    encrypted_choice: [u8; 32],
+    tally: [u8; 128],
+    weight: u64,
+) -> ArgumentList {
+    let mut args = build_args_for_vote(encrypted_choice, tally, None);
+
+    args.args
+        .push(ArgumentRef::PlaintextU64(args.plaintext_numbers.len() as u8));
+    args.plaintext_numbers.push(weight);
+
+    args
 }
 
-fn build_args_for_vote(encrypted_choice: [u8; 32], tally: [u8; 128]) -> ArgumentList {
+/// Arguments for `vote_with_delegations(choice, own_weight, delegated_weight, tally)`
+fn build_args_for_delegated_vote(
+    encrypted_choice: [u8; 32],
+    own_weight: u64,
+    delegated_weight: u64,
+    tally: [u8; 128],
+) -> ArgumentList {
     let mut args = ArgumentList {
         args: Vec::new(),
         byte_arrays: Vec::new(),
@@ -126,15 +398,69 @@ fn build_args_for_vote(encrypted_choice: [u8; 32], tally: [u8; 128]) -> Argument
         .push(ArgumentRef::EncryptedU8(args.byte_arrays.len() as u8));
     args.byte_arrays.push(encrypted_choice);
 
-    for chunk in split_ciphertext_128(tally) {
+    for weight in [own_weight, delegated_weight] {
+        args.args
+            .push(ArgumentRef::PlaintextU64(args.plaintext_numbers.len() as u8));
+        args.plaintext_numbers.push(weight);
+    }
+
+    for chunk in split_tally_ciphertext(tally) {
         args.args
-            .push(ArgumentRef::EncryptedU32(args.byte_arrays.len() as u8));
+            .push(ArgumentRef::EncryptedU64(args.byte_arrays.len() as u8));
         args.byte_arrays.push(chunk);
     }
 
     args
 }
 
+fn build_args_for_nullifier(encrypted_secret: [u8; 32], proposal_id: u64) -> ArgumentList {
+    ArgumentList {
+        args: vec![ArgumentRef::EncryptedU128(0), ArgumentRef::PlaintextU64(0)],
+        byte_arrays: vec![encrypted_secret],
+        plaintext_numbers: vec![proposal_id],
+        values_128_bit: Vec::new(),
+        accounts: Vec::new(),
+    }
+}
+
+/// Arguments for `cast_vote_with_eligibility`. 32-byte public values (voter,
+/// root, proof siblings) travel as little-endian u128 pairs.
+fn build_args_for_eligible_vote(
+    encrypted_choice: [u8; 32],
+    encrypted_weight: [u8; 32],
+    tally: [u8; 128],
+    voter: &Pubkey,
+    leaf_index: u64,
+    proof: &[[u8; 32]],
+    root: [u8; 32],
+) -> ArgumentList {
+    let mut args = build_args_for_vote(encrypted_choice, tally, None);
+
+    args.args
+        .push(ArgumentRef::EncryptedU64(args.byte_arrays.len() as u8));
+    args.byte_arrays.push(encrypted_weight);
+
+    fn push_bytes32(args: &mut ArgumentList, bytes: &[u8; 32]) {
+        for half in bytes.chunks_exact(16) {
+            args.args
+                .push(ArgumentRef::PlaintextU128(args.values_128_bit.len() as u8));
+            args.values_128_bit
+                .push(u128::from_le_bytes(half.try_into().unwrap()));
+        }
+    }
+
+    push_bytes32(&mut args, &voter.to_bytes());
+    args.args
+        .push(ArgumentRef::PlaintextU64(args.plaintext_numbers.len() as u8));
+    args.plaintext_numbers.push(leaf_index);
+    for sibling in proof {
+        push_bytes32(&mut args, sibling);
+    }
+    push_bytes32(&mut args, &root);
+
+    args
+}
+
 fn build_args_for_tally(tally: [u8; 128]) -> ArgumentList {
     let mut args = ArgumentList {
         args: Vec::new(),
@@ -144,1251 +470,13935 @@ fn build_args_for_tally(tally: [u8; 128]) -> ArgumentList {
         accounts: Vec::new(),
     };
 
-    for chunk in split_ciphertext_128(tally) {
+    for chunk in split_tally_ciphertext(tally) {
         args.args
-            .push(ArgumentRef::EncryptedU32(args.byte_arrays.len() as u8));
+            .push(ArgumentRef::EncryptedU64(args.byte_arrays.len() as u8));
         args.byte_arrays.push(chunk);
     }
 
     args
 }
 
-// ==================== PROGRAM ====================
+/// Arguments for `recast_vote(tally, ballot, counted)`: the `SequencedBallot`
+/// is `[choice, sequence]` and the `CountedBallot` `[choice, sequence, weight]`.
+fn build_args_for_recast(
+    tally: [u8; 128],
+    ballot: [[u8; 32]; 2],
+    counted_ballot: [u8; 96],
+    sequence: u64,
+) -> ArgumentList {
+    let mut args = build_args_for_tally(tally);
 
-#[program]
-pub mod private_dao_voting {
-    use super::*;
+    args.args
+        .push(ArgumentRef::EncryptedU8(args.byte_arrays.len() as u8));
+    args.byte_arrays.push(ballot[0]);
+    args.args
+        .push(ArgumentRef::EncryptedU64(args.byte_arrays.len() as u8));
+    args.byte_arrays.push(ballot[1]);
 
-    /// Create a new proposal and initialize encrypted tally
-    pub fn create_proposal(
-        ctx: Context<CreateProposal>,
-        proposal_id: u64,
-        title: String,
-        description: String,
-        voting_ends_at: i64,
-        gate_mint: Pubkey,
-        min_balance: u64,
-        mxe_program_id: Pubkey,
-        threshold_bps: u16,
-        privacy_level: u8,
-        discussion_url: String,
-        execution_delay: i64,
-    ) -> Result<()> {
-        // Validate V2 fields
-        require!(
-            threshold_bps > 0 && threshold_bps <= 10_000,
-            VotingError::InvalidThreshold
-        );
-        require!(privacy_level <= 2, VotingError::InvalidPrivacyLevel);
-        require!(execution_delay >= 0, VotingError::InvalidExecutionDelay);
+    for (i, chunk) in counted_ballot.chunks_exact(32).enumerate() {
+        args.args.push(if i == 0 {
+            ArgumentRef::EncryptedU8(args.byte_arrays.len() as u8)
+        } else {
+            ArgumentRef::EncryptedU64(args.byte_arrays.len() as u8)
+        });
+        args.byte_arrays.push(chunk.try_into().unwrap());
+    }
 
-        // Initialize proposal state
-        let proposal = &mut ctx.accounts.proposal;
-        proposal.id = proposal_id;
-        proposal.authority = ctx.accounts.authority.key();
-        proposal.title = title;
-        proposal.description = description;
-        proposal.voting_ends_at = voting_ends_at;
-        proposal.is_active = true;
-        proposal.is_revealed = false;
-        proposal.total_votes = 0;
-        proposal.gate_mint = gate_mint;
-        proposal.min_balance = min_balance;
-        proposal.mxe_program_id = mxe_program_id;
-        proposal.threshold_bps = threshold_bps;
-        proposal.privacy_level = privacy_level;
-        proposal.passed = false;
-        proposal.discussion_url = discussion_url;
-        proposal.deposit_amount = 0;
-        proposal.deposit_returned = false;
-        proposal.execution_delay = execution_delay;
-        proposal.executed = false;
-        proposal.bump = ctx.bumps.proposal;
+    args.args
+        .push(ArgumentRef::PlaintextU64(args.plaintext_numbers.len() as u8));
+    args.plaintext_numbers.push(sequence);
 
-        // Queue computation to initialize encrypted tally
-        let cpi_accounts = QueueComputation {
-            signer: ctx.accounts.authority.to_account_info(),
-            sign_seed: ctx.accounts.sign_seed.to_account_info(),
-            comp: ctx.accounts.computation_account.to_account_info(),
-            mxe: ctx.accounts.mxe_account.to_account_info(),
-            mempool: ctx.accounts.mempool_account.to_account_info(),
-            executing_pool: ctx.accounts.executing_pool.to_account_info(),
-            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
-            cluster: ctx.accounts.cluster_account.to_account_info(),
-            pool_account: ctx.accounts.pool_account.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-            clock: ctx.accounts.clock_account.to_account_info(),
-        };
+    args
+}
 
-        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.arcium_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        );
+fn build_args_for_tally_pair(primary: [u8; 128], shard: [u8; 128]) -> ArgumentList {
+    let mut args = build_args_for_tally(primary);
 
-        let computation_offset = Clock::get()?.slot as u64;
-        let args = ArgumentList {
-            args: vec![],
-            byte_arrays: vec![],
-            plaintext_numbers: vec![],
-            values_128_bit: vec![],
-            accounts: vec![],
-        };
+    for chunk in split_tally_ciphertext(shard) {
+        args.args
+            .push(ArgumentRef::EncryptedU64(args.byte_arrays.len() as u8));
+        args.byte_arrays.push(chunk);
+    }
 
-        queue_computation(
-            cpi_ctx,
-            computation_offset,
-            comp_def_offset(INIT_TALLY_COMP),
-            None,
-            args,
-            proposal.mxe_program_id,
-            Vec::<CallbackInstruction>::new(),
-            0,
-            0,
-            0,
-        )?;
+    args
+}
 
-        emit!(ProposalCreated {
-            proposal_id,
-            authority: ctx.accounts.authority.key(),
-            voting_ends_at,
-        });
+fn build_args_for_tally_migration(tally: [u8; 128], new_mxe_pubkey: [u8; 32]) -> ArgumentList {
+    let mut args = build_args_for_tally(tally);
 
-        Ok(())
-    }
+    args.args
+        .push(ArgumentRef::X25519Pubkey(args.byte_arrays.len() as u8));
+    args.byte_arrays.push(new_mxe_pubkey);
 
-    /// Callback from Arcium after init_tally completes
-    pub fn init_tally_callback(
-        ctx: Context<InitTallyCallback>,
-        encrypted_tally: [u8; 128], // Encrypted VoteTally
-        nonce: [u8; 16],
-    ) -> Result<()> {
-        let tally = &mut ctx.accounts.tally;
-        tally.proposal = ctx.accounts.proposal.key();
-        tally.encrypted_data = encrypted_tally;
-        tally.nonce = nonce;
-        tally.bump = ctx.bumps.tally;
+    args
+}
 
-        Ok(())
+fn build_args_for_ballot(encrypted_choice: [u8; 32]) -> ArgumentList {
+    let mut args = ArgumentList {
+        args: Vec::new(),
+        byte_arrays: Vec::new(),
+        plaintext_numbers: Vec::new(),
+        values_128_bit: Vec::new(),
+        accounts: Vec::new(),
+    };
+
+    args.args
+        .push(ArgumentRef::EncryptedU8(args.byte_arrays.len() as u8));
+    args.byte_arrays.push(encrypted_choice);
+
+    args
+}
+
+/// Resolve the committee scope for a new proposal. Committee-scoped proposals
+/// may only be created by a wallet in that committee's proposer set.
+fn committee_scope(
+    committee: &Option<Account<Committee>>,
+    proposer: &Pubkey,
+) -> Result<Option<Pubkey>> {
+    match committee {
+        Some(committee) => {
+            require!(
+                committee.proposers.contains(proposer),
+                VotingError::NotCommitteeProposer
+            );
+            Ok(Some(committee.key()))
+        }
+        None => Ok(None),
     }
+}
 
-    /// Cast an encrypted vote
-    pub fn cast_vote(
-        ctx: Context<CastVote>,
-        encrypted_choice: [u8; 32],
-        nonce: [u8; 16],
-        voter_pubkey: [u8; 32],
-    ) -> Result<()> {
-        let proposal = &ctx.accounts.proposal;
+/// Move lamports out of the SOL treasury PDA, signing with its seeds.
+fn sol_treasury_transfer(
+    accounts: &ExecuteProposal,
+    program_id: &Pubkey,
+    recipient: Pubkey,
+    lamports: u64,
+) -> Result<()> {
+    let sol_treasury = accounts
+        .sol_treasury
+        .as_ref()
+        .ok_or(VotingError::MissingExecutionAccount)?;
+    let recipient_account = accounts
+        .recipient
+        .as_ref()
+        .ok_or(VotingError::MissingExecutionAccount)?;
+    require!(
+        recipient_account.key() == recipient,
+        VotingError::InvalidRecipient
+    );
+
+    let (expected, bump) = Pubkey::find_program_address(&[SOL_TREASURY_SEED], program_id);
+    require!(
+        sol_treasury.key() == expected,
+        VotingError::InvalidTreasuryVault
+    );
+    charge_spend_limit(accounts, program_id, Pubkey::default(), lamports)?;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[SOL_TREASURY_SEED, &[bump]]];
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: sol_treasury.to_account_info(),
+                to: recipient_account.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        lamports,
+    )
+}
 
-        // Validate voting is still active
-        require!(proposal.is_active, VotingError::VotingClosed);
+/// Unix timestamp at which a revealed proposal's payload becomes executable.
+fn executable_at(proposal: &Proposal) -> Result<i64> {
+    Ok(proposal
+        .revealed_at
+        .checked_add(proposal.execution_delay)
+        .ok_or(VotingError::ArithmeticOverflow)?)
+}
 
-        let clock = Clock::get()?;
-        require!(
-            clock.unix_timestamp < proposal.voting_ends_at,
-            VotingError::VotingEnded
-        );
+/// Cancel a passed proposal's queued execution. Only allowed while the
+/// `execution_delay` timelock is still running.
+fn cancel_queued_execution(proposal: &mut Account<Proposal>, cancelled_by: Pubkey) -> Result<()> {
+    require!(proposal.passed, VotingError::ProposalNotPassed);
+    require!(!proposal.executed, VotingError::AlreadyExecuted);
+    require!(!proposal.cancelled, VotingError::ExecutionCancelled);
+    require!(
+        Clock::get()?.unix_timestamp < executable_at(proposal)?,
+        VotingError::CancellationWindowClosed
+    );
+
+    proposal.cancelled = true;
+
+    emit!(ExecutionCancelled {
+        proposal: proposal.key(),
+        cancelled_by,
+    });
+
+    Ok(())
+}
 
-        // Check no active delegation — delegators must revoke before voting directly
-        let (delegation_pda, _) = Pubkey::find_program_address(
-            &[DELEGATION_SEED, ctx.accounts.voter.key().as_ref()],
-            ctx.program_id,
-        );
-        let delegation_info = ctx
-            .remaining_accounts
-            .iter()
-            .find(|a| a.key() == delegation_pda);
-        if let Some(acct) = delegation_info {
-            if acct.data_len() > 0 && acct.owner == ctx.program_id {
-                return Err(VotingError::ActiveDelegation.into());
-            }
-        }
+/// `amount` base units of a `from_decimals` asset expressed in base units of
+/// a `to_decimals` mint, so one whole asset token backs one whole power token.
+fn normalize_decimals(amount: u64, from_decimals: u8, to_decimals: u8) -> Result<u64> {
+    let scale = |exp: u8| 10u128.checked_pow(exp as u32);
+    let scaled = if to_decimals >= from_decimals {
+        scale(to_decimals - from_decimals).and_then(|factor| (amount as u128).checked_mul(factor))
+    } else {
+        scale(from_decimals - to_decimals).map(|factor| amount as u128 / factor)
+    };
+    scaled
+        .and_then(|power| u64::try_from(power).ok())
+        .ok_or(VotingError::ArithmeticOverflow.into())
+}
 
-        // Token gate: voter must hold the required SPL token
-        let token_account = &ctx.accounts.voter_token_account;
-        require!(
-            token_account.owner == ctx.accounts.voter.key(),
-            VotingError::InvalidTokenAccount
-        );
-        require!(
-            token_account.mint == proposal.gate_mint,
-            VotingError::InvalidTokenMint
-        );
+/// Thaw or freeze a holder's governance-power token account. Power accounts
+/// stay frozen (non-transferable) except inside a deposit or withdrawal.
+fn set_power_account_frozen<'info>(
+    token_program: &Program<'info, Token>,
+    account: &Account<'info, TokenAccount>,
+    mint: &Account<'info, Mint>,
+    power_authority: &AccountInfo<'info>,
+    bump: u8,
+    frozen: bool,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[POWER_AUTHORITY_SEED, &[bump]]];
+    if frozen {
+        token::freeze_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            FreezeAccount {
+                account: account.to_account_info(),
+                mint: mint.to_account_info(),
+                authority: power_authority.clone(),
+            },
+            signer_seeds,
+        ))
+    } else {
+        token::thaw_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            ThawAccount {
+                account: account.to_account_info(),
+                mint: mint.to_account_info(),
+                authority: power_authority.clone(),
+            },
+            signer_seeds,
+        ))
+    }
+}
+
+/// Delegated lamports of a fully active native stake account whose withdraw
+/// authority is `voter`. Only the withdrawer counts, so a stake account with
+/// a separate staker can't back two ballots. A non-default `validator` restricts the gate to stake delegated to that vote account.
+fn active_stake(stake_account: &AccountInfo, voter: &Pubkey, validator: &Pubkey) -> Result<u64> {
+    require!(
+        *stake_account.owner == stake::program::ID,
+        VotingError::InvalidStakeAccount
+    );
+    let data = stake_account.try_borrow_data()?;
+    let state = StakeStateV2::deserialize(&mut &data[..])
+        .map_err(|_| error!(VotingError::InvalidStakeAccount))?;
+    let StakeStateV2::Stake(meta, stake, _) = state else {
+        return err!(VotingError::InactiveStake);
+    };
+    require!(
+        meta.authorized.withdrawer == *voter,
+        VotingError::InvalidStakeAccount
+    );
+
+    let delegation = stake.delegation;
+    if *validator != Pubkey::default() {
         require!(
-            token_account.amount >= proposal.min_balance,
-            VotingError::InsufficientTokenBalance
+            delegation.voter_pubkey == *validator,
+            VotingError::WrongValidator
         );
+    }
 
-        // Record that this voter has voted (prevents double-voting)
-        let vote_record = &mut ctx.accounts.vote_record;
-        vote_record.proposal = proposal.key();
-        vote_record.voter = ctx.accounts.voter.key();
-        vote_record.voted_at = clock.unix_timestamp;
-        vote_record.encrypted_choice = encrypted_choice;
-        vote_record.nonce = nonce;
-        vote_record.voter_pubkey = voter_pubkey;
-        vote_record.bump = ctx.bumps.vote_record;
+    // Warming-up and deactivating stake does not count
+    let epoch = Clock::get()?.epoch;
+    require!(
+        (delegation.activation_epoch == u64::MAX || delegation.activation_epoch < epoch)
+            && delegation.deactivation_epoch == u64::MAX,
+        VotingError::InactiveStake
+    );
 
-        // Queue the vote computation
-        let cpi_accounts = QueueComputation {
-            signer: ctx.accounts.voter.to_account_info(),
-            sign_seed: ctx.accounts.sign_seed.to_account_info(),
-            comp: ctx.accounts.computation_account.to_account_info(),
-            mxe: ctx.accounts.mxe_account.to_account_info(),
-            mempool: ctx.accounts.mempool_account.to_account_info(),
-            executing_pool: ctx.accounts.executing_pool.to_account_info(),
-            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
-            cluster: ctx.accounts.cluster_account.to_account_info(),
-            pool_account: ctx.accounts.pool_account.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-            clock: ctx.accounts.clock_account.to_account_info(),
-        };
+    Ok(delegation.stake)
+}
 
-        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.arcium_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        );
+/// Lamport value of an SPL stake-pool LST balance at the pool's current exchange rate.
+fn stake_pool_lamports(stake_pool: &AccountInfo, token_account: &TokenAccount) -> Result<u64> {
+    require!(
+        *stake_pool.owner == SPL_STAKE_POOL_PROGRAM_ID,
+        VotingError::InvalidStakePool
+    );
+    let data = stake_pool.try_borrow_data()?;
+    // StakePool layout: account_type (1), manager, staker, deposit authority (32 each),
+    // withdraw bump (1), validator_list, reserve_stake (32 each), pool_mint @ 162,
+    // fee account, token program (32 each), total_lamports @ 258, pool_token_supply @ 266
+    require!(
+        data.len() >= 274 && data[0] == 1,
+        VotingError::InvalidStakePool
+    );
+    let pool_mint = Pubkey::try_from(&data[162..194]).unwrap();
+    let total_lamports = u64::from_le_bytes(data[258..266].try_into().unwrap());
+    let pool_token_supply = u64::from_le_bytes(data[266..274].try_into().unwrap());
+    require!(
+        token_account.mint == pool_mint,
+        VotingError::InvalidTokenMint
+    );
+
+    if pool_token_supply == 0 {
+        return Ok(0);
+    }
+    Ok((token_account.amount as u128 * total_lamports as u128 / pool_token_supply as u128) as u64)
+}
 
-        let computation_offset = Clock::get()?.slot as u64;
-        let args = build_args_for_vote(encrypted_choice, ctx.accounts.tally.encrypted_data);
+/// Price from a Pyth `PriceUpdateV2` account for `feed`, as (price, conf, exponent).
+/// Rejects partially verified updates, stale prices and wide confidence intervals.
+fn pyth_price(oracle: &AccountInfo, feed: &PriceFeed) -> Result<(i64, u64, i32)> {
+    require!(
+        *oracle.owner == PYTH_RECEIVER_PROGRAM_ID,
+        VotingError::InvalidOracle
+    );
+    let data = oracle.try_borrow_data()?;
+    // discriminator (8), write_authority (32), verification_level (1 = Full),
+    // then PriceFeedMessage: feed_id (32), price, conf, exponent, publish_time
+    require!(
+        data.len() >= 101 && data[..8] == PYTH_PRICE_UPDATE_DISCRIMINATOR && data[40] == 1,
+        VotingError::InvalidOracle
+    );
+    require!(data[41..73] == feed.feed_id, VotingError::InvalidOracle);
+    let price = i64::from_le_bytes(data[73..81].try_into().unwrap());
+    let conf = u64::from_le_bytes(data[81..89].try_into().unwrap());
+    let exponent = i32::from_le_bytes(data[89..93].try_into().unwrap());
+    let publish_time = i64::from_le_bytes(data[93..101].try_into().unwrap());
+
+    require!(price > 0, VotingError::InvalidOracle);
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now.saturating_sub(publish_time) <= feed.max_staleness,
+        VotingError::StalePrice
+    );
+    require!(
+        conf as u128 * 10_000 <= price as u128 * feed.max_confidence_bps as u128,
+        VotingError::PriceConfidenceTooWide
+    );
+
+    Ok((price, conf, exponent))
+}
 
-        queue_computation(
-            cpi_ctx,
-            computation_offset,
-            comp_def_offset(VOTE_COMP),
-            None,
-            args,
-            proposal.mxe_program_id,
-            Vec::<CallbackInstruction>::new(),
-            0,
-            0,
-            0,
-        )?;
+/// Conservative USD value (`USD_DECIMALS`) of `amount` base units, priced at the
+/// lower edge of the oracle confidence interval.
+fn usd_value(amount: u64, decimals: u8, price: i64, conf: u64, exponent: i32) -> Result<u64> {
+    let lower = (price as u128).saturating_sub(conf as u128);
+    let value = (amount as u128)
+        .checked_mul(lower)
+        .ok_or(VotingError::ArithmeticOverflow)?;
+    let scale = exponent + USD_DECIMALS as i32 - decimals as i32;
+    let factor = 10u128
+        .checked_pow(scale.unsigned_abs())
+        .ok_or(VotingError::ArithmeticOverflow)?;
+    let value = if scale >= 0 {
+        value
+            .checked_mul(factor)
+            .ok_or(VotingError::ArithmeticOverflow)?
+    } else {
+        value / factor
+    };
+    u64::try_from(value).map_err(|_| error!(VotingError::ArithmeticOverflow))
+}
 
-        emit!(VoteCast {
-            proposal: proposal.key(),
-            voter: ctx.accounts.voter.key(),
-        });
+/// Deposit-mint amount worth `usd` (`USD_DECIMALS`) at the oracle price, rounded up.
+fn usd_to_tokens(usd: u64, decimals: u8, price: i64, exponent: i32) -> Result<u64> {
+    let scale = decimals as i32 - USD_DECIMALS as i32 - exponent;
+    let factor = 10u128
+        .checked_pow(scale.unsigned_abs())
+        .ok_or(VotingError::ArithmeticOverflow)?;
+    let (numerator, denominator) = if scale >= 0 {
+        (
+            (usd as u128)
+                .checked_mul(factor)
+                .ok_or(VotingError::ArithmeticOverflow)?,
+            price as u128,
+        )
+    } else {
+        (
+            usd as u128,
+            (price as u128)
+                .checked_mul(factor)
+                .ok_or(VotingError::ArithmeticOverflow)?,
+        )
+    };
+    let tokens = numerator.div_ceil(denominator);
+    u64::try_from(tokens).map_err(|_| error!(VotingError::ArithmeticOverflow))
+}
 
-        Ok(())
+/// Slots covering `seconds` at the nominal slot rate, rounded up.
+fn slots_for(seconds: i64) -> u64 {
+    (seconds.max(0) as u64)
+        .saturating_mul(1_000)
+        .div_ceil(NOMINAL_MS_PER_SLOT as u64)
+}
+
+/// Slot matching a wall-clock deadline at the nominal slot rate, rounded up.
+fn deadline_slot(clock: &Clock, voting_ends_at: i64) -> u64 {
+    clock
+        .slot
+        .saturating_add(slots_for(voting_ends_at - clock.unix_timestamp))
+}
+
+/// Deadline timestamp and slot for a new proposal. A non-zero
+/// `voting_end_slot` selects slot mode, where the timestamp is only a nominal
+/// estimate for display and reminders.
+fn proposal_deadline(clock: &Clock, voting_ends_at: i64, voting_end_slot: u64) -> (i64, u64) {
+    if voting_end_slot == 0 {
+        return (voting_ends_at, deadline_slot(clock, voting_ends_at));
     }
+    let remaining_ms = voting_end_slot
+        .saturating_sub(clock.slot)
+        .saturating_mul(NOMINAL_MS_PER_SLOT as u64);
+    (
+        clock
+            .unix_timestamp
+            .saturating_add((remaining_ms / 1_000) as i64),
+        voting_end_slot,
+    )
+}
 
-    /// Callback from Arcium after vote computation completes
-    pub fn vote_callback(
-        ctx: Context<VoteCallback>,
-        new_encrypted_tally: [u8; 128],
-        nonce: [u8; 16],
-    ) -> Result<()> {
-        // Update the encrypted tally with new value
-        let tally = &mut ctx.accounts.tally;
-        tally.encrypted_data = new_encrypted_tally;
-        tally.nonce = nonce;
+/// Ballots are accepted only while neither the deadline timestamp nor the
+/// deadline slot has passed (`voting_ends_slot == 0` = time only). Slot-mode
+/// proposals follow the slot alone. A moderator-flagged proposal is neither
+/// open nor closed until its `ModerationCase` is resolved.
+fn voting_open(proposal: &Proposal, clock: &Clock) -> bool {
+    if proposal.flagged || proposal.awaiting_sponsorship {
+        return false;
+    }
+    if proposal.slot_deadline {
+        return clock.slot < proposal.voting_ends_slot;
+    }
+    clock.unix_timestamp < proposal.voting_ends_at
+        && (proposal.voting_ends_slot == 0 || clock.slot < proposal.voting_ends_slot)
+}
 
-        // Increment public vote counter
-        let proposal = &mut ctx.accounts.proposal;
-        proposal.total_votes += 1;
+/// Voting has ended only once both the deadline timestamp and slot passed, so
+/// a skewed validator clock alone can neither cut voting short nor reveal early.
+fn voting_closed(proposal: &Proposal, clock: &Clock) -> bool {
+    if proposal.flagged || proposal.awaiting_sponsorship {
+        return false;
+    }
+    if proposal.slot_deadline {
+        return clock.slot >= proposal.voting_ends_slot;
+    }
+    clock.unix_timestamp >= proposal.voting_ends_at && clock.slot >= proposal.voting_ends_slot
+}
 
-        Ok(())
+/// Recoverable ballot guard: on failure emits `VoteRejected` before reverting
+/// with `error`. Failed transactions keep their logs, so support can read the
+/// reason without reproducing the transaction.
+fn require_vote(
+    ok: bool,
+    reason: RejectReason,
+    error: VotingError,
+    proposal: Pubkey,
+    voter: Pubkey,
+) -> Result<()> {
+    if ok {
+        return Ok(());
     }
+    Err(vote_rejected(reason, proposal, voter)(error.into()))
+}
 
-    /// Reveal the final vote results
-    pub fn reveal_results(ctx: Context<RevealResults>) -> Result<()> {
-        let proposal = &ctx.accounts.proposal;
+/// `map_err` adapter emitting `VoteRejected` for a failed ballot check.
+fn vote_rejected(
+    reason: RejectReason,
+    proposal: Pubkey,
+    voter: Pubkey,
+) -> impl FnOnce(Error) -> Error {
+    move |error| {
+        let error_code = match &error {
+            Error::AnchorError(e) => e.error_code_number,
+            Error::ProgramError(_) => 0,
+        };
+        emit!(VoteRejected {
+            proposal,
+            voter,
+            reason,
+            error_code,
+        });
+        error
+    }
+}
 
-        // Only authority can reveal
-        require!(
-            ctx.accounts.authority.key() == proposal.authority,
-            VotingError::Unauthorized
-        );
+/// Why `voting_open` is false for an active proposal
+fn window_reason(proposal: &Proposal) -> RejectReason {
+    if proposal.flagged {
+        RejectReason::Paused
+    } else if proposal.awaiting_sponsorship {
+        RejectReason::AwaitingSponsorship
+    } else {
+        RejectReason::VotingEnded
+    }
+}
 
-        // Prevent re-reveal
-        require!(!proposal.is_revealed, VotingError::AlreadyRevealed);
+/// Accounts `cast_vote` and `cast_vote_signed` share for vetting a ballot
+struct BallotAccounts<'a, 'info> {
+    proposal: &'a Account<'info, Proposal>,
+    dao_config: &'a Account<'info, DaoConfig>,
+    denylist_entry: &'a UncheckedAccount<'info>,
+    tally: &'a Account<'info, Tally>,
+    cluster_account: &'a AccountInfo<'info>,
+    jury_roster: &'a Option<Account<'info, JuryRoster>>,
+    voter_token_account: &'a Option<Account<'info, TokenAccount>>,
+    voter_stake_account: &'a Option<UncheckedAccount<'info>>,
+    stake_pool: &'a Option<UncheckedAccount<'info>>,
+    vesting_position: &'a Option<UncheckedAccount<'info>>,
+    voter_escrow: &'a Option<Account<'info, VoterEscrow>>,
+    reputation: &'a Option<Account<'info, Reputation>>,
+}
 
-        // Validate voting has ended
-        let clock = Clock::get()?;
-        require!(
-            clock.unix_timestamp >= proposal.voting_ends_at,
-            VotingError::VotingNotEnded
-        );
+/// Checks every ballot path shares — voting window, screening, voter cap and
+/// jury — emitting `VoteRejected` for each failure. Denied voters are
+/// reported by `ScreeningBlocked` alone. A `recast` replaces a counted
+/// ballot, so it isn't held to the voter cap.
+fn vet_ballot_window(
+    accounts: &BallotAccounts,
+    voter: Pubkey,
+    clock: &Clock,
+    recast: bool,
+) -> Result<()> {
+    let proposal = accounts.proposal;
+
+    // Validate voting is still active
+    require_vote(
+        proposal.is_active,
+        RejectReason::VotingClosed,
+        VotingError::VotingClosed,
+        proposal.key(),
+        voter,
+    )?;
+    require_not_denied(
+        accounts.dao_config,
+        accounts.denylist_entry,
+        voter,
+        proposal.key(),
+        ScreenedAction::Vote,
+    )?;
+    require_vote(
+        !proposal.excluded.contains(&voter),
+        RejectReason::Excluded,
+        VotingError::VoterExcluded,
+        proposal.key(),
+        voter,
+    )?;
+    require_vote(
+        proposal.pending_mxe_program_id == Pubkey::default(),
+        RejectReason::Paused,
+        VotingError::MigrationInProgress,
+        proposal.key(),
+        voter,
+    )?;
+    require_proposal_mxe(proposal, accounts.cluster_account)?;
+
+    require_vote(
+        voting_open(proposal, clock),
+        window_reason(proposal),
+        VotingError::VotingEnded,
+        proposal.key(),
+        voter,
+    )?;
+    require_vote(
+        recast || proposal.max_voters == 0 || proposal.ballots_cast < proposal.max_voters,
+        RejectReason::CapReached,
+        VotingError::VoterCapReached,
+        proposal.key(),
+        voter,
+    )?;
+    if proposal.jury_size > 0 {
+        let on_jury = accounts
+            .jury_roster
+            .as_ref()
+            .is_some_and(|roster| roster.jurors.contains(&voter));
+        require_vote(
+            on_jury,
+            RejectReason::NotOnJury,
+            VotingError::NotOnJury,
+            proposal.key(),
+            voter,
+        )?;
+    }
+    Ok(())
+}
 
-        // Queue reveal computation
-        let cpi_accounts = QueueComputation {
-            signer: ctx.accounts.authority.to_account_info(),
-            sign_seed: ctx.accounts.sign_seed.to_account_info(),
-            comp: ctx.accounts.computation_account.to_account_info(),
-            mxe: ctx.accounts.mxe_account.to_account_info(),
-            mempool: ctx.accounts.mempool_account.to_account_info(),
-            executing_pool: ctx.accounts.executing_pool.to_account_info(),
-            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
-            cluster: ctx.accounts.cluster_account.to_account_info(),
-            pool_account: ctx.accounts.pool_account.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-            clock: ctx.accounts.clock_account.to_account_info(),
-        };
+/// Checks every direct ballot passes — `vet_ballot_window`, then snapshot
+/// electorate, delegation, gate balance and tally shard — emitting
+/// `VoteRejected` for the voter-facing ones. Returns the ballot's balance and
+/// weight.
+fn vet_ballot(
+    accounts: &BallotAccounts,
+    voter: Pubkey,
+    remaining_accounts: &[AccountInfo],
+    clock: &Clock,
+    recast: bool,
+) -> Result<(u64, u64)> {
+    let proposal = accounts.proposal;
+    vet_ballot_window(accounts, voter, clock, recast)?;
+    // Snapshot electorates vote only through `cast_eligible_vote` or
+    // `cast_vote_with_proof`
+    require_vote(
+        proposal.snapshot_root == [0u8; 32] && proposal.oracle_root == [0u8; 32],
+        RejectReason::SnapshotElectorate,
+        VotingError::SnapshotElectorate,
+        proposal.key(),
+        voter,
+    )?;
+
+    // Check no active delegation — delegators must revoke before voting directly
+    let (delegation_pda, _) =
+        Pubkey::find_program_address(&[DELEGATION_SEED, voter.as_ref()], &crate::ID);
+    let delegation_info = remaining_accounts
+        .iter()
+        .find(|a| a.key() == delegation_pda);
+    if let Some(acct) = delegation_info {
+        if acct.data_len() > 0 && acct.owner == &crate::ID {
+            // Delegations still awaiting `accept_delegation` carry no power
+            let delegation = Delegation::try_deserialize(&mut &acct.try_borrow_data()?[..])?;
+            require!(!delegation.accepted, VotingError::ActiveDelegation);
+        }
+    }
 
-        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.arcium_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        );
+    // Gate: voter must hold the required token, stake or LST balance
+    let power = gate_balance(
+        proposal,
+        &voter,
+        accounts.voter_token_account,
+        accounts.voter_stake_account,
+        accounts.stake_pool,
+        remaining_accounts,
+        &accounts.dao_config.mint_multipliers,
+    )
+    .map_err(vote_rejected(
+        RejectReason::GateMismatch,
+        proposal.key(),
+        voter,
+    ))?;
+    let power = settled_power(
+        power,
+        accounts.dao_config,
+        proposal,
+        &voter,
+        accounts.voter_escrow,
+    )?;
+    let power = at_snapshot(power, proposal, &voter, accounts.voter_escrow)?;
+    let (balance, weighted_balance) = with_vesting_power(
+        power,
+        proposal,
+        &voter,
+        accounts
+            .voter_token_account
+            .as_ref()
+            .map(|account| account.key()),
+        accounts.vesting_position.as_deref(),
+        accounts.dao_config.vesting_boost_bps,
+    )?;
+    require_vote(
+        balance >= proposal.min_balance,
+        RejectReason::InsufficientBalance,
+        VotingError::InsufficientTokenBalance,
+        proposal.key(),
+        voter,
+    )?;
+    let weight = vote_weight(proposal, weighted_balance, accounts.reputation)?;
+
+    // Ballots are hash-routed so voters spread across tally shards
+    let tally = accounts.tally;
+    require!(
+        tally.proposal == proposal.key()
+            && tally.shard == tally_shard_for(&voter, proposal.tally_shards),
+        VotingError::WrongTallyShard
+    );
+    require_ballot_version(tally.ballot_version)?;
+
+    Ok((balance, weight))
+}
 
-        let computation_offset = Clock::get()?.slot as u64;
-        let args = build_args_for_tally(ctx.accounts.tally.encrypted_data);
+/// Circuit and arguments tallying a direct ballot of `weight`. Equal
+/// weighting keeps the original one-ballot-one-vote circuit; re-voting
+/// proposals keep each voter's counted ballot in the MXE. Token-weighted and
+/// quadratic ballots count the voter's `encrypted_weight`, capped in MPC at
+/// the on-chain `weight`.
+fn ballot_circuit(
+    proposal: &Proposal,
+    encrypted_choice: [u8; 32],
+    encrypted_weight: [u8; 32],
+    tally: [u8; 128],
+    weight: u64,
+) -> (&'static str, ArgumentList) {
+    let weight_mode = match proposal.weighting_mode {
+        WeightingMode::Quadratic => WEIGHT_MODE_QUADRATIC,
+        _ => WEIGHT_MODE_LINEAR,
+    };
+    match proposal.weighting_mode {
+        _ if proposal.revoting => (
+            SEQUENCED_VOTE_COMP,
+            build_args_for_weighted_vote(encrypted_choice, tally, weight),
+        ),
+        WeightingMode::Equal => (
+            VOTE_COMP,
+            build_args_for_vote(encrypted_choice, tally, None),
+        ),
+        WeightingMode::TokenWeighted | WeightingMode::Quadratic => (
+            ENCRYPTED_WEIGHT_VOTE_COMP,
+            build_args_for_vote(
+                encrypted_choice,
+                tally,
+                Some((encrypted_weight, weight, weight_mode)),
+            ),
+        ),
+        WeightingMode::Reputation => (
+            WEIGHTED_VOTE_COMP,
+            build_args_for_weighted_vote(encrypted_choice, tally, weight),
+        ),
+    }
+}
 
-        queue_computation(
-            cpi_ctx,
-            computation_offset,
-            comp_def_offset(REVEAL_RESULT_COMP),
-            None,
-            args,
-            proposal.mxe_program_id,
-            Vec::<CallbackInstruction>::new(),
-            0,
-            0,
-            0,
-        )?;
+/// Whether `seconds` have elapsed since the deadline, counted in nominal slots
+/// for slot-mode proposals.
+fn past_deadline_by(proposal: &Proposal, clock: &Clock, seconds: i64) -> bool {
+    if proposal.slot_deadline {
+        return clock.slot >= proposal.voting_ends_slot.saturating_add(slots_for(seconds));
+    }
+    clock.unix_timestamp >= proposal.voting_ends_at + seconds
+}
 
-        Ok(())
+/// Seconds left before the deadline (nominal for slot-mode proposals).
+fn seconds_until_deadline(proposal: &Proposal, clock: &Clock) -> i64 {
+    if proposal.slot_deadline {
+        let remaining_ms = proposal
+            .voting_ends_slot
+            .saturating_sub(clock.slot)
+            .saturating_mul(NOMINAL_MS_PER_SLOT as u64);
+        return (remaining_ms / 1_000) as i64;
     }
+    proposal.voting_ends_at - clock.unix_timestamp
+}
 
-    /// Callback from Arcium with revealed results
-    /// Only callable by the Arcium program via CPI (validated by sign PDA signer constraint)
-    pub fn reveal_results_callback(
-        ctx: Context<RevealResultsCallback>,
-        yes_count: u64,
-        no_count: u64,
-        abstain_count: u64,
-        total_votes: u64,
-    ) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
+/// Gate-mint supply frozen on a token-gated proposal at creation (0 when the
+/// gate has no mint supply to snapshot).
+fn eligible_supply(
+    gate_kind: GateKind,
+    gate_mint: &Pubkey,
+    gate_mint_account: &Option<Account<Mint>>,
+) -> Result<u64> {
+    if gate_kind != GateKind::Token {
+        return Ok(0);
+    }
+    let mint = gate_mint_account
+        .as_ref()
+        .ok_or(VotingError::MissingGateAccount)?;
+    require!(mint.key() == *gate_mint, VotingError::InvalidTokenMint);
+    Ok(mint.supply)
+}
 
-        // Validate vote count consistency
-        let computed_total = yes_count
-            .checked_add(no_count)
-            .and_then(|x| x.checked_add(abstain_count))
-            .ok_or(VotingError::ArithmeticOverflow)?;
-        require!(
-            computed_total == total_votes,
-            VotingError::VoteTallyMismatch
-        );
+/// A posted snapshot's total weight becomes the proposal's `eligible_supply`,
+/// and the supply-relative quorum follows that electorate instead.
+fn adopt_snapshot_supply(proposal: &mut Proposal, config: &DaoConfig, total_weight: u64) {
+    proposal.eligible_supply = total_weight;
+    if config.quorum_bps > 0 {
+        let supply_quorum = (total_weight as u128 * config.quorum_bps as u128 / 10_000) as u64;
+        proposal.quorum = proposal.quorum.max(supply_quorum);
+    }
+}
 
-        // Enforce quorum if set
-        if proposal.quorum > 0 {
-            require!(
-                total_votes >= proposal.quorum,
-                VotingError::QuorumNotReached
-            );
-        }
+/// Revealed `total_weight` in basis points of the proposal's frozen
+/// `eligible_supply`. Only token-weighted tallies are comparable to supply;
+/// other proposals (and those without a snapshot) report 0.
+fn turnout_bps(proposal: &Proposal, total_weight: u64) -> u16 {
+    if proposal.eligible_supply == 0 || proposal.weighting_mode != WeightingMode::TokenWeighted {
+        return 0;
+    }
+    (total_weight as u128 * 10_000 / proposal.eligible_supply as u128).min(10_000) as u16
+}
 
-        // Check threshold for production path too
-        let non_abstain = yes_count
-            .checked_add(no_count)
-            .ok_or(VotingError::ArithmeticOverflow)?;
-        let threshold_met = if non_abstain > 0 {
-            yes_count
-                .checked_mul(10_000)
-                .ok_or(VotingError::ArithmeticOverflow)?
-                / non_abstain
-                >= proposal.threshold_bps as u64
-        } else {
-            false
-        };
+/// A turnout-boost schedule is sorted by strictly increasing turnout and
+/// multiplier, every tier boosting.
+fn require_valid_boosts(boosts: &[TurnoutBoost]) -> Result<()> {
+    require!(
+        boosts.len() <= MAX_TURNOUT_BOOSTS
+            && boosts
+                .iter()
+                .all(|boost| boost.min_turnout_bps <= 10_000 && boost.multiplier_bps > 10_000)
+            && boosts.windows(2).all(|pair| {
+                pair[0].min_turnout_bps < pair[1].min_turnout_bps
+                    && pair[0].multiplier_bps < pair[1].multiplier_bps
+            }),
+        VotingError::InvalidTurnoutBoost
+    );
+    Ok(())
+}
 
-        let quorum_met = proposal.quorum == 0 || total_votes >= proposal.quorum;
+/// Reward multiplier (basis points, 10_000 = unmatched) of the highest boost
+/// tier whose `min_turnout_bps` the turnout reaches.
+fn turnout_multiplier(boosts: &[TurnoutBoost], turnout: u16) -> u16 {
+    boosts
+        .iter()
+        .rev()
+        .find(|boost| turnout >= boost.min_turnout_bps)
+        .map_or(10_000, |boost| boost.multiplier_bps)
+}
 
-        proposal.is_active = false;
-        proposal.is_revealed = true;
-        proposal.yes_votes = yes_count;
-        proposal.no_votes = no_count;
-        proposal.abstain_votes = abstain_count;
-        proposal.passed = quorum_met && threshold_met;
+/// Quorum in force at `now`: falls linearly from `quorum` to its floor
+/// (`quorum_floor_bps` of it) over `quorum_decay_period` seconds of voting.
+/// Decay stops at the deadline, so settling late can't lower the bar.
+fn effective_quorum(proposal: &Proposal, now: i64) -> u64 {
+    if proposal.quorum_decay_period <= 0 {
+        return proposal.quorum;
+    }
+    let floor = (proposal.quorum as u128 * proposal.quorum_floor_bps as u128 / 10_000) as u64;
+    let now = now.min(proposal.voting_ends_at);
+    let elapsed = (now - proposal.voting_starts_at).clamp(0, proposal.quorum_decay_period);
+    let decay =
+        (proposal.quorum - floor) as u128 * elapsed as u128 / proposal.quorum_decay_period as u128;
+    proposal.quorum - decay as u64
+}
 
-        let winner: u8 = if yes_count > no_count {
-            1
-        } else if no_count > yes_count {
-            2
+/// Portion of a proposal deposit (basis points) forfeited to the treasury,
+/// based on the revealed `total_weight` against the stored quorum and the
+/// DAO's slash schedule.
+fn deposit_slash_bps(config: &DaoConfig, proposal: &Proposal) -> u64 {
+    if proposal.sponsorship_expired {
+        config.unsponsored_slash_bps as u64
+    } else if proposal.total_weight >= proposal.quorum {
+        0
+    } else if config.graded_slash {
+        if proposal.total_weight.saturating_mul(2) >= proposal.quorum {
+            config.graded_slash_partial_bps as u64
         } else {
-            0
-        };
+            10_000
+        }
+    } else if config.slash_if_no_quorum {
+        10_000
+    } else {
+        0
+    }
+}
 
-        emit!(ResultsRevealed {
-            proposal: proposal.key(),
-            yes_votes: yes_count,
-            no_votes: no_count,
-            abstain_votes: abstain_count,
-            total_votes,
-            winner,
-        });
+/// Whether YES clears `threshold_bps` of the proposal's `threshold_base`.
+/// Supply is only comparable to token-weighted tallies; other proposals
+/// measure against total turnout instead.
+fn threshold_met(proposal: &Proposal, yes: u64, no: u64, abstain: u64) -> Result<bool> {
+    let supply_base =
+        proposal.eligible_supply > 0 && proposal.weighting_mode == WeightingMode::TokenWeighted;
+    let base = match proposal.threshold_base {
+        ThresholdBase::NonAbstain => yes.checked_add(no),
+        ThresholdBase::Supply if supply_base => Some(proposal.eligible_supply),
+        ThresholdBase::Turnout | ThresholdBase::Supply => {
+            yes.checked_add(no).and_then(|x| x.checked_add(abstain))
+        }
+    }
+    .ok_or(VotingError::ArithmeticOverflow)?;
+    if base == 0 {
+        return Ok(false);
+    }
+    Ok(yes
+        .checked_mul(10_000)
+        .ok_or(VotingError::ArithmeticOverflow)?
+        / base
+        >= proposal.threshold_bps as u64)
+}
 
-        Ok(())
+/// Revenue epoch containing `timestamp` (`revenue_epoch_duration` buckets).
+fn revenue_epoch_of(config: &DaoConfig, timestamp: i64) -> Result<u64> {
+    require!(
+        config.revenue_epoch_duration > 0,
+        VotingError::RevenueSharingDisabled
+    );
+    Ok((timestamp / config.revenue_epoch_duration) as u64)
+}
+
+/// Proposals revealed in `epoch` may be registered until one full epoch after
+/// it ends; claims open at that point so every voter's share is final.
+fn revenue_claims_open_at(config: &DaoConfig, epoch: u64) -> Result<i64> {
+    (epoch as i64)
+        .checked_add(2)
+        .and_then(|e| e.checked_mul(config.revenue_epoch_duration))
+        .ok_or(error!(VotingError::ArithmeticOverflow))
+}
+
+/// Lock the DAO's proposal deposit in the proposal escrow. USD-denominated
+/// deposits are converted to deposit-mint units at the current oracle price.
+fn collect_proposal_deposit(accounts: &CreateProposal) -> Result<u64> {
+    let config = &accounts.dao_config;
+    if config.proposal_deposit == 0 {
+        return Ok(0);
+    }
+    if config.deposit_in_sol {
+        return collect_sol_bond(accounts);
     }
 
-    /// Initialize computation definitions (called once at deployment).
-    ///
-    /// Registers the Arcis circuit bytecode on-chain and stores the circuit hash
-    /// for integrity verification. The `circuit_hash` parameter must match
-    /// `CIRCUIT_HASH` (compiled from `circuit_hash!("voting-circuit")`).
-    ///
-    /// After initialization, the MXE cluster can execute the following instructions:
-    /// - `initialize_voting` → Creates `Enc<Mxe, Tally>` with zero counters
-    /// - `cast_vote` → Accumulates `Enc<Shared, u8>` into `Enc<Mxe, Tally>`
-    /// - `finalize_and_reveal` → Threshold-decrypts aggregate totals
-    /// - `finalize_with_threshold` → Reveals + checks quorum/threshold
-    /// - `get_live_tally` → Real-time tally for Transparent mode
-    /// - `get_vote_count` → Total participation without breakdown
-    pub fn init_comp_def(
-        ctx: Context<InitCompDef>,
-        circuit_hash: String,
-        comp_def_data: Vec<u8>,
-    ) -> Result<()> {
-        // Verify circuit integrity: the provided hash must match the compile-time hash.
-        // This prevents deployment of tampered circuits — if any byte of the Arcis
-        // bytecode has been modified, the SHA-256 hash will differ.
+    let amount = if config.deposit_in_usd {
+        let feed = accounts
+            .deposit_price_feed
+            .as_ref()
+            .ok_or(VotingError::MissingDepositAccount)?;
+        let oracle = accounts
+            .deposit_oracle
+            .as_ref()
+            .ok_or(VotingError::MissingDepositAccount)?;
+        let (price, _conf, exponent) = pyth_price(oracle, feed)?;
+        usd_to_tokens(config.proposal_deposit, feed.decimals, price, exponent)?
+    } else {
+        config.proposal_deposit
+    };
+
+    let from = accounts
+        .creator_deposit_account
+        .as_ref()
+        .ok_or(VotingError::MissingDepositAccount)?;
+    let escrow = accounts
+        .deposit_escrow
+        .as_ref()
+        .ok_or(VotingError::MissingDepositAccount)?;
+    require!(
+        from.owner == accounts.authority.key(),
+        VotingError::InvalidTokenAccount
+    );
+    require!(
+        from.mint == config.deposit_mint,
+        VotingError::InvalidTokenMint
+    );
+
+    token::transfer(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: from.to_account_info(),
+                to: escrow.to_account_info(),
+                authority: accounts.authority.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    Ok(amount)
+}
+
+/// Lock `proposal_deposit` lamports in the proposal's SOL bond PDA.
+fn collect_sol_bond(accounts: &CreateProposal) -> Result<u64> {
+    let amount = accounts.dao_config.proposal_deposit;
+    let bond = accounts
+        .sol_bond
+        .as_ref()
+        .ok_or(VotingError::MissingDepositAccount)?;
+    // The bond PDA holds no data, so it must stay rent-exempt on its own
+    require!(
+        amount >= Rent::get()?.minimum_balance(0),
+        VotingError::BondBelowRentExemption
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: accounts.authority.to_account_info(),
+                to: bond.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    Ok(amount)
+}
+
+/// Mints priced by a gate: a `UsdValue` gate lists 1..=`MAX_GATE_MINTS`
+/// distinct mints, every other gate none.
+fn require_valid_gate_mints(gate_kind: GateKind, gate_mints: &[Pubkey]) -> Result<()> {
+    let distinct = gate_mints
+        .iter()
+        .enumerate()
+        .all(|(i, mint)| !gate_mints[..i].contains(mint));
+    require!(
+        distinct
+            && if gate_kind == GateKind::UsdValue {
+                !gate_mints.is_empty() && gate_mints.len() <= MAX_GATE_MINTS
+            } else {
+                gate_mints.is_empty()
+            },
+        VotingError::InvalidGateMints
+    );
+    Ok(())
+}
+
+/// Combined USD value of the voter's holdings across the price feeds of the
+/// proposal's `gate_mints`. `remaining` must start with
+/// `[price_feed, oracle, token_account]` triplets.
+fn usd_gate_value(
+    remaining: &[AccountInfo],
+    voter: &Pubkey,
+    gate_mints: &[Pubkey],
+    multipliers: &[MintMultiplier],
+) -> Result<(u64, u64)> {
+    let mut counted: Vec<Pubkey> = Vec::new();
+    let mut total: u64 = 0;
+    let mut weighted: u64 = 0;
+    for accounts in remaining.chunks_exact(3) {
+        if *accounts[0].owner != crate::ID {
+            break;
+        }
+        let Ok(feed) = PriceFeed::try_deserialize(&mut &accounts[0].try_borrow_data()?[..]) else {
+            break;
+        };
         require!(
-            circuit_hash == CIRCUIT_HASH,
-            VotingError::CircuitHashMismatch
+            gate_mints.contains(&feed.mint),
+            VotingError::InvalidTokenMint
         );
-
-        msg!(
-            "Initializing computation definitions with circuit hash: {}",
-            circuit_hash
+        require!(
+            *accounts[2].owner == token::ID,
+            VotingError::InvalidTokenAccount
         );
-        msg!(
-            "Bytecode size: {} bytes ({} computation definitions)",
-            comp_def_data.len(),
-            6 // initialize_voting, cast_vote, finalize_and_reveal, finalize_with_threshold, get_live_tally, get_vote_count
+        let token_account =
+            TokenAccount::try_deserialize(&mut &accounts[2].try_borrow_data()?[..])?;
+        require!(
+            token_account.owner == *voter,
+            VotingError::InvalidTokenAccount
         );
+        require!(
+            token_account.mint == feed.mint,
+            VotingError::InvalidTokenMint
+        );
+        require!(
+            !counted.contains(accounts[2].key),
+            VotingError::DuplicateGateAccount
+        );
+        counted.push(*accounts[2].key);
 
-        // Store circuit hash in the comp def state for on-chain verification
-        let comp_def_state = &mut ctx.accounts.comp_def_state;
-        comp_def_state.circuit_hash = circuit_hash;
-        comp_def_state.authority = ctx.accounts.authority.key();
-        comp_def_state.initialized = true;
-        comp_def_state.bump = ctx.bumps.comp_def_state;
-
-        Ok(())
+        let (price, conf, exponent) = pyth_price(&accounts[1], &feed)?;
+        let value = usd_value(token_account.amount, feed.decimals, price, conf, exponent)?;
+        total = total
+            .checked_add(value)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+        let multiplier = mint_multiplier_bps(multipliers, &feed.mint);
+        let value = u64::try_from(value as u128 * multiplier as u128 / 10_000)
+            .map_err(|_| error!(VotingError::ArithmeticOverflow))?;
+        weighted = weighted
+            .checked_add(value)
+            .ok_or(VotingError::ArithmeticOverflow)?;
     }
+    Ok((total, weighted))
+}
 
-    /// Initialize the computation offset PDA (one-time setup)
-    pub fn init_computation_offset(ctx: Context<InitComputationOffset>) -> Result<()> {
-        let state = &mut ctx.accounts.computation_offset_account;
-        state.bump = ctx.bumps.computation_offset_account;
-        Ok(())
-    }
-
-    // ==================== DEV MODE INSTRUCTIONS ====================
-    // These bypass Arcium MXE CPI for devnet testing.
-    // All other logic (token gating, PDA validation, double-vote
-    // prevention) remains identical to production instructions.
-    // Remove before mainnet deployment.
-
-    /// Dev mode: Create a proposal without Arcium CPI
-    pub fn dev_create_proposal(
-        ctx: Context<DevCreateProposal>,
-        proposal_id: u64,
-        title: String,
-        description: String,
-        voting_ends_at: i64,
-        gate_mint: Pubkey,
-        min_balance: u64,
-        quorum: u64,
-        threshold_bps: u16,
-        privacy_level: u8,
-        discussion_url: String,
-        execution_delay: i64,
-    ) -> Result<()> {
-        // Validate V2 fields
-        require!(
-            threshold_bps > 0 && threshold_bps <= 10_000,
-            VotingError::InvalidThreshold
-        );
-        require!(privacy_level <= 2, VotingError::InvalidPrivacyLevel);
-        require!(execution_delay >= 0, VotingError::InvalidExecutionDelay);
-
-        let proposal = &mut ctx.accounts.proposal;
-        proposal.id = proposal_id;
-        proposal.authority = ctx.accounts.authority.key();
-        proposal.title = title;
-        proposal.description = description;
-        proposal.voting_ends_at = voting_ends_at;
-        proposal.is_active = true;
-        proposal.is_revealed = false;
-        proposal.total_votes = 0;
-        proposal.gate_mint = gate_mint;
-        proposal.min_balance = min_balance;
-        proposal.mxe_program_id = Pubkey::default();
-        proposal.quorum = quorum;
-        proposal.threshold_bps = threshold_bps;
-        proposal.privacy_level = privacy_level;
-        proposal.passed = false;
-        proposal.discussion_url = discussion_url;
-        proposal.deposit_amount = 0;
-        proposal.deposit_returned = false;
-        proposal.execution_delay = execution_delay;
-        proposal.executed = false;
-        proposal.bump = ctx.bumps.proposal;
+/// Weight multiplier for `mint` in basis points (10_000 = 1x when unlisted)
+fn mint_multiplier_bps(multipliers: &[MintMultiplier], mint: &Pubkey) -> u16 {
+    multipliers
+        .iter()
+        .find(|m| m.mint == *mint)
+        .map(|m| m.multiplier_bps)
+        .unwrap_or(10_000)
+}
 
-        emit!(ProposalCreated {
-            proposal_id,
-            authority: ctx.accounts.authority.key(),
-            voting_ends_at,
-        });
+/// Balance the voter holds under the proposal's gate, compared against
+/// `min_balance`, and the same balance after per-mint multipliers, used for
+/// weighting. Multipliers only apply to multi-mint `UsdValue` gates.
+fn gate_balance(
+    proposal: &Proposal,
+    voter: &Pubkey,
+    token_account: &Option<Account<TokenAccount>>,
+    stake_account: &Option<UncheckedAccount>,
+    stake_pool: &Option<UncheckedAccount>,
+    remaining: &[AccountInfo],
+    multipliers: &[MintMultiplier],
+) -> Result<(u64, u64)> {
+    let balance = match proposal.gate_kind {
+        GateKind::Token => {
+            let token_account = token_account
+                .as_ref()
+                .ok_or(VotingError::MissingGateAccount)?;
+            require!(
+                token_account.owner == *voter,
+                VotingError::InvalidTokenAccount
+            );
+            require!(
+                token_account.mint == proposal.gate_mint,
+                VotingError::InvalidTokenMint
+            );
+            token_account.amount
+        }
+        GateKind::StakeAccount => {
+            let stake_account = stake_account
+                .as_ref()
+                .ok_or(VotingError::MissingGateAccount)?;
+            active_stake(stake_account, voter, &proposal.gate_mint)?
+        }
+        GateKind::StakePool => {
+            let token_account = token_account
+                .as_ref()
+                .ok_or(VotingError::MissingGateAccount)?;
+            let stake_pool = stake_pool.as_ref().ok_or(VotingError::MissingGateAccount)?;
+            require!(
+                token_account.owner == *voter,
+                VotingError::InvalidTokenAccount
+            );
+            require!(
+                stake_pool.key() == proposal.gate_mint,
+                VotingError::InvalidStakePool
+            );
+            stake_pool_lamports(stake_pool, token_account)?
+        }
+        GateKind::UsdValue => {
+            return usd_gate_value(remaining, voter, &proposal.gate_mints, multipliers)
+        }
+    };
+    Ok((balance, balance))
+}
 
-        Ok(())
+/// Escrowed balance at the start of `slot`: every change from earlier slots
+/// applied, none from `slot` itself. Fails if that point has been pruned from
+/// the escrow's checkpoints.
+fn escrow_balance_at(escrow: &VoterEscrow, slot: u64) -> Result<u64> {
+    if slot <= escrow.created_slot {
+        return Ok(0);
     }
+    escrow
+        .checkpoints
+        .iter()
+        .rev()
+        .find(|checkpoint| checkpoint.slot < slot)
+        .map(|checkpoint| checkpoint.amount)
+        .ok_or_else(|| error!(VotingError::InvalidCheckpoint))
+}
 
-    /// Delegate voting power to another address
-    /// The delegator's token-gated vote weight is transferred to the delegate.
-    /// Delegators cannot vote directly while their delegation is active.
-    pub fn delegate_vote(ctx: Context<DelegateVote>) -> Result<()> {
-        let delegation = &mut ctx.accounts.delegation;
-        delegation.delegator = ctx.accounts.delegator.key();
-        delegation.delegate = ctx.accounts.delegate.key();
-        delegation.created_at = Clock::get()?.unix_timestamp;
-        delegation.bump = ctx.bumps.delegation;
-
-        emit!(VoteDelegated {
-            delegator: ctx.accounts.delegator.key(),
-            delegate: ctx.accounts.delegate.key(),
-        });
+/// Record the escrow's current amount as its checkpoint for `slot`.
+fn checkpoint_escrow(escrow: &mut VoterEscrow, slot: u64) {
+    let amount = escrow.amount;
+    match escrow.checkpoints.last_mut() {
+        Some(last) if last.slot == slot => last.amount = amount,
+        _ => {
+            if escrow.checkpoints.len() == MAX_ESCROW_CHECKPOINTS {
+                escrow.checkpoints.remove(0);
+            }
+            escrow.checkpoints.push(Checkpoint { slot, amount });
+        }
+    }
+}
 
-        Ok(())
+/// On snapshot proposals a Token-gate balance is the owner's `VoterEscrow`
+/// balance at the start of `snapshot_slot`. Every escrow change goes through
+/// the program, so at that point each token sits in at most one escrow, and
+/// tokens moved afterwards count only where they were. No-op for
+/// live-balance proposals.
+fn at_snapshot(
+    power: (u64, u64),
+    proposal: &Proposal,
+    owner: &Pubkey,
+    escrow: &Option<Account<VoterEscrow>>,
+) -> Result<(u64, u64)> {
+    if proposal.snapshot_slot == 0 {
+        return Ok(power);
     }
+    let escrow = escrow.as_ref().ok_or(VotingError::InvalidCheckpoint)?;
+    require!(
+        escrow.owner == *owner && escrow.mint == proposal.gate_mint,
+        VotingError::InvalidCheckpoint
+    );
+    let balance = escrow_balance_at(escrow, proposal.snapshot_slot)?;
+    Ok((balance, balance))
+}
 
-    /// Revoke a previously created delegation
-    pub fn revoke_delegation(_ctx: Context<RevokeDelegation>) -> Result<()> {
-        // Account is closed by the close constraint
-        emit!(DelegationRevoked {
-            delegator: _ctx.accounts.delegation.delegator,
-            delegate: _ctx.accounts.delegation.delegate,
-        });
+/// Petition and endorsement weight of `owner`: its `VoterEscrow` balance at the
+/// start of the proposal's snapshot slot, or of its creation slot when it has
+/// none. The point is fixed before anyone can weigh in, so tokens moved to
+/// another wallet afterwards count only once.
+fn escrowed_weight(proposal: &Proposal, escrow: &VoterEscrow) -> Result<u64> {
+    let slot = if proposal.snapshot_slot != 0 {
+        proposal.snapshot_slot
+    } else {
+        proposal.created_slot
+    };
+    escrow_balance_at(escrow, slot)
+}
 
-        Ok(())
+/// With `same_slot_guard` on, a live Token-gate balance is the owner's
+/// `VoterEscrow` balance at the start of the current slot rather than the
+/// wallet balance. Tokens withdrawn in a slot stop counting from that slot, so
+/// borrowed tokens (flash loans) can't be escrowed and repaid around a ballot.
+/// Snapshot proposals are exempt.
+fn settled_power(
+    power: (u64, u64),
+    config: &DaoConfig,
+    proposal: &Proposal,
+    owner: &Pubkey,
+    escrow: &Option<Account<VoterEscrow>>,
+) -> Result<(u64, u64)> {
+    if !config.same_slot_guard
+        || proposal.gate_kind != GateKind::Token
+        || proposal.snapshot_slot != 0
+    {
+        return Ok(power);
     }
+    let escrow = escrow.as_ref().ok_or(VotingError::UnsettledBalance)?;
+    require!(
+        escrow.owner == *owner && escrow.mint == proposal.gate_mint,
+        VotingError::UnsettledBalance
+    );
+    let balance = escrow_balance_at(escrow, Clock::get()?.slot)?;
+    Ok((balance, balance))
+}
 
-    /// Dev mode: Initialize tally without Arcium callback
-    pub fn dev_init_tally(ctx: Context<DevInitTally>) -> Result<()> {
-        let tally = &mut ctx.accounts.tally;
-        tally.proposal = ctx.accounts.proposal.key();
-        tally.encrypted_data = [0u8; 128];
-        tally.nonce = [0u8; 16];
-        tally.bump = ctx.bumps.tally;
-        Ok(())
+/// Emit `SybilSignals` for a ballot from the voter's `WalletProfile`. A
+/// wallet's first ballot creates the profile, recording `payer` as its funder
+/// and the current time as its first appearance. Analytics only.
+fn emit_sybil_signals(
+    proposal: Pubkey,
+    voter: Pubkey,
+    profile: &mut Account<WalletProfile>,
+    payer: Pubkey,
+    bump: u8,
+    clock: &Clock,
+) {
+    const DAY: i64 = 86_400;
+    if profile.wallet == Pubkey::default() {
+        profile.wallet = voter;
+        profile.funded_by = payer;
+        profile.created_at = clock.unix_timestamp;
+        profile.created_slot = clock.slot;
+        profile.bump = bump;
     }
+    let age = clock.unix_timestamp - profile.created_at;
+    let age_bucket = if age < DAY {
+        1
+    } else if age < 7 * DAY {
+        2
+    } else if age < 30 * DAY {
+        3
+    } else {
+        4
+    };
+    emit!(SybilSignals {
+        proposal,
+        voter,
+        funding_parent: profile.funded_by,
+        age_bucket,
+    });
+}
 
-    /// Dev mode: Cast vote without Arcium CPI (token gating still enforced)
-    pub fn dev_cast_vote(
-        ctx: Context<DevCastVote>,
-        encrypted_choice: [u8; 32],
-        nonce: [u8; 16],
-        voter_pubkey: [u8; 32],
-    ) -> Result<()> {
-        require!(ctx.accounts.proposal.is_active, VotingError::VotingClosed);
-
-        let clock = Clock::get()?;
-        require!(
-            clock.unix_timestamp < ctx.accounts.proposal.voting_ends_at,
-            VotingError::VotingEnded
-        );
-
-        // Check no active delegation — delegators must revoke before voting directly
-        let (delegation_pda, _) = Pubkey::find_program_address(
-            &[DELEGATION_SEED, ctx.accounts.voter.key().as_ref()],
-            ctx.program_id,
-        );
-        let delegation_info = ctx
-            .remaining_accounts
-            .iter()
-            .find(|a| a.key() == delegation_pda);
-        if let Some(acct) = delegation_info {
-            if acct.data_len() > 0 && acct.owner == ctx.program_id {
-                return Err(VotingError::ActiveDelegation.into());
-            }
-        }
+/// Amount of a native vesting position unlocked at `now` (linear release).
+fn vested_amount(position: &VestingPosition, now: i64) -> u64 {
+    if now >= position.end_at {
+        return position.total;
+    }
+    if now <= position.start_at {
+        return 0;
+    }
+    (position.total as u128 * (now - position.start_at) as u128
+        / (position.end_at - position.start_at) as u128) as u64
+}
 
-        // Token gate: voter must hold the required SPL token
-        let token_account = &ctx.accounts.voter_token_account;
+/// Add a Token-gate voter's locked or vesting position of the gate mint to
+/// their (raw, weighted) power. `position` is either an in-crate
+/// `VestingPosition` or a Bonfida token-vesting account paying out to the
+/// voter's gate token account. Tokens still locked are boosted by `boost_bps`.
+fn with_vesting_power(
+    power: (u64, u64),
+    proposal: &Proposal,
+    voter: &Pubkey,
+    token_account: Option<Pubkey>,
+    position: Option<&AccountInfo>,
+    boost_bps: u16,
+) -> Result<(u64, u64)> {
+    let Some(position) = position else {
+        return Ok(power);
+    };
+    require!(
+        proposal.gate_kind == GateKind::Token,
+        VotingError::InvalidVestingPosition
+    );
+    let now = Clock::get()?.unix_timestamp;
+
+    let (held, locked) = if *position.owner == crate::ID {
+        let position = VestingPosition::try_deserialize(&mut &position.try_borrow_data()?[..])?;
         require!(
-            token_account.owner == ctx.accounts.voter.key(),
-            VotingError::InvalidTokenAccount
+            position.owner == *voter && position.mint == proposal.gate_mint,
+            VotingError::InvalidVestingPosition
         );
+        (
+            position.total - position.withdrawn,
+            position.total - vested_amount(&position, now),
+        )
+    } else if *position.owner == BONFIDA_VESTING_PROGRAM_ID {
+        let token_account = token_account.ok_or(VotingError::MissingGateAccount)?;
+        let data = position.try_borrow_data()?;
+        // Header: destination token account, mint (32 each), is_initialized (1),
+        // then (release_time, amount) u64 pairs; released schedules are zeroed
         require!(
-            token_account.mint == ctx.accounts.proposal.gate_mint,
-            VotingError::InvalidTokenMint
+            data.len() >= 65 && data[64] == 1,
+            VotingError::InvalidVestingPosition
         );
+        let destination = Pubkey::try_from(&data[0..32]).unwrap();
+        let mint = Pubkey::try_from(&data[32..64]).unwrap();
         require!(
-            token_account.amount >= ctx.accounts.proposal.min_balance,
-            VotingError::InsufficientTokenBalance
+            destination == token_account && mint == proposal.gate_mint,
+            VotingError::InvalidVestingPosition
         );
+        let (mut held, mut locked) = (0u64, 0u64);
+        for schedule in data[65..].chunks_exact(16) {
+            let release_time = u64::from_le_bytes(schedule[..8].try_into().unwrap());
+            let amount = u64::from_le_bytes(schedule[8..].try_into().unwrap());
+            held = held
+                .checked_add(amount)
+                .ok_or(VotingError::ArithmeticOverflow)?;
+            if release_time as i64 > now {
+                locked = locked
+                    .checked_add(amount)
+                    .ok_or(VotingError::ArithmeticOverflow)?;
+            }
+        }
+        (held, locked)
+    } else {
+        return err!(VotingError::InvalidVestingPosition);
+    };
 
-        // Record that this voter has voted
-        let vote_record = &mut ctx.accounts.vote_record;
-        vote_record.proposal = ctx.accounts.proposal.key();
-        vote_record.voter = ctx.accounts.voter.key();
-        vote_record.voted_at = clock.unix_timestamp;
-        vote_record.encrypted_choice = encrypted_choice;
-        vote_record.nonce = nonce;
-        vote_record.voter_pubkey = voter_pubkey;
-        vote_record.bump = ctx.bumps.vote_record;
+    let boost = u64::try_from(locked as u128 * (boost_bps as u128 - 10_000) / 10_000)
+        .map_err(|_| error!(VotingError::ArithmeticOverflow))?;
+    Ok((
+        power
+            .0
+            .checked_add(held)
+            .ok_or(VotingError::ArithmeticOverflow)?,
+        power
+            .1
+            .checked_add(held)
+            .and_then(|weighted| weighted.checked_add(boost))
+            .ok_or(VotingError::ArithmeticOverflow)?,
+    ))
+}
 
-        // Dev mode: directly update tally nonce and vote counter
-        ctx.accounts.tally.nonce = nonce;
-        ctx.accounts.proposal.total_votes += 1;
+/// Public weight of a ballot under the proposal's weighting mode.
+fn vote_weight(
+    proposal: &Proposal,
+    gate_balance: u64,
+    reputation: &Option<Account<Reputation>>,
+) -> Result<u64> {
+    let weight = match proposal.weighting_mode {
+        WeightingMode::Equal => 1,
+        WeightingMode::TokenWeighted => gate_balance,
+        WeightingMode::Quadratic => gate_balance.isqrt(),
+        WeightingMode::Reputation => reputation
+            .as_ref()
+            .map(|reputation| reputation.score)
+            .unwrap_or(0),
+    };
+    require!(weight > 0, VotingError::ZeroVoteWeight);
+    Ok(weight)
+}
 
-        emit!(VoteCast {
-            proposal: ctx.accounts.proposal.key(),
-            voter: ctx.accounts.voter.key(),
-        });
+/// Read a Switchboard randomness account as (seed_slot, reveal_slot, value).
+fn switchboard_randomness(randomness: &AccountInfo) -> Result<(u64, u64, [u8; 32])> {
+    require!(
+        *randomness.owner == SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+        VotingError::InvalidRandomness
+    );
+    let data = randomness.try_borrow_data()?;
+    // discriminator (8), authority, queue, seed_slothash (32 each), seed_slot @ 104,
+    // oracle (32), reveal_slot @ 144, value @ 152
+    require!(
+        data.len() >= 184 && data[..8] == SWITCHBOARD_RANDOMNESS_DISCRIMINATOR,
+        VotingError::InvalidRandomness
+    );
+    let seed_slot = u64::from_le_bytes(data[104..112].try_into().unwrap());
+    let reveal_slot = u64::from_le_bytes(data[144..152].try_into().unwrap());
+    let value: [u8; 32] = data[152..184].try_into().unwrap();
+    Ok((seed_slot, reveal_slot, value))
+}
 
-        Ok(())
+/// Draw `size` distinct candidates with a partial Fisher-Yates shuffle driven
+/// by an xorshift64* stream seeded from `seed`.
+fn draw_jury(candidates: &[Pubkey], size: usize, seed: [u8; 32]) -> Vec<Pubkey> {
+    let mut pool = candidates.to_vec();
+    let mut state = seed.chunks_exact(8).fold(0u64, |acc, word| {
+        acc ^ u64::from_le_bytes(word.try_into().unwrap())
+    }) | 1;
+    let count = size.min(pool.len());
+    for i in 0..count {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        let r = state.wrapping_mul(0x2545_F491_4F6C_DD1D);
+        let j = i + (r % (pool.len() - i) as u64) as usize;
+        pool.swap(i, j);
     }
+    pool.truncate(count);
+    pool
+}
 
-    /// Dev mode: Reveal results with provided counts (simulates MXE callback)
-    pub fn dev_reveal_results(
-        ctx: Context<DevRevealResults>,
-        yes_count: u64,
-        no_count: u64,
-        abstain_count: u64,
-    ) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
+/// A proposal may be archived and closed once results are in (or its
+/// sponsorship lapsed), any passed payload has run (or was cancelled) and the
+/// deposit has been settled.
+fn require_closable(proposal: &Proposal) -> Result<()> {
+    require!(
+        proposal.is_revealed || proposal.sponsorship_expired,
+        VotingError::NotYetRevealed
+    );
+    require!(
+        !proposal.passed || proposal.executed || proposal.cancelled,
+        VotingError::ExecutionPending
+    );
+    require!(!proposal.tie_pending, VotingError::TiePending);
+    require!(
+        proposal.deposit_amount == 0 || proposal.deposit_returned,
+        VotingError::DepositNotSettled
+    );
+    require!(!proposal.holds_rate_slot, VotingError::RateSlotHeld);
+    Ok(())
+}
 
-        require!(
-            ctx.accounts.authority.key() == proposal.authority,
-            VotingError::Unauthorized
-        );
+/// An MXE program / cluster pair must be on the DAO allowlist (an empty
+/// allowlist leaves MXE selection unrestricted).
+fn require_approved_mxe(
+    config: &DaoConfig,
+    mxe_program_id: &Pubkey,
+    cluster: &Pubkey,
+) -> Result<()> {
+    require!(
+        config.approved_mxes.is_empty()
+            || config
+                .approved_mxes
+                .iter()
+                .any(|m| m.program_id == *mxe_program_id && m.cluster == *cluster),
+        VotingError::MxeNotApproved
+    );
+    Ok(())
+}
 
-        // Prevent re-reveal
-        require!(!proposal.is_revealed, VotingError::AlreadyRevealed);
+/// Queueing against a proposal must use its recorded cluster. The pair was
+/// approved when it was recorded; a later revocation only keeps new proposals
+/// and migrations off it, so bound proposals still finish or migrate away.
+fn require_proposal_mxe(proposal: &Proposal, cluster_account: &AccountInfo) -> Result<()> {
+    require_keys_eq!(
+        cluster_account.key(),
+        proposal.mxe_cluster,
+        VotingError::WrongMxeCluster
+    );
+    Ok(())
+}
 
-        let clock = Clock::get()?;
+/// Refuse to hand a ciphertext of an unknown layout to the circuits.
+fn require_ballot_version(version: u8) -> Result<()> {
+    require!(
+        version == BALLOT_VERSION,
+        VotingError::UnsupportedBallotVersion
+    );
+    Ok(())
+}
+
+/// Bound the link list and require each URL to be non-empty, short enough,
+/// and use an accepted scheme.
+fn validate_links(links: &[ProposalLink]) -> Result<()> {
+    require!(links.len() <= MAX_PROPOSAL_LINKS, VotingError::TooManyLinks);
+    for link in links {
         require!(
-            clock.unix_timestamp >= proposal.voting_ends_at,
-            VotingError::VotingNotEnded
+            link.url.len() <= MAX_LINK_LEN
+                && LINK_SCHEMES
+                    .iter()
+                    .any(|scheme| link.url.len() > scheme.len() && link.url.starts_with(scheme)),
+            VotingError::InvalidLink
         );
+    }
+    Ok(())
+}
 
-        // Checked arithmetic to prevent overflow
-        let total_votes = yes_count
-            .checked_add(no_count)
-            .and_then(|x| x.checked_add(abstain_count))
-            .ok_or(VotingError::ArithmeticOverflow)?;
+/// Holdings-based rate limit: every active proposal locks
+/// `tokens_per_active_proposal` deposit-mint tokens in its slot-stake escrow
+/// until `release_proposal_slot`, capped at `MAX_ACTIVE_PROPOSALS`. Locking
+/// (rather than reading a balance) keeps the same tokens from backing
+/// proposals from several wallets.
+fn claim_proposal_slot(accounts: &mut CreateProposal, bump: Option<u8>) -> Result<()> {
+    let per_proposal = accounts.dao_config.tokens_per_active_proposal;
+    let authority = accounts.authority.key();
+    let holdings = accounts
+        .creator_deposit_account
+        .as_ref()
+        .ok_or(VotingError::MissingDepositAccount)?;
+    require!(
+        holdings.owner == authority && holdings.mint == accounts.dao_config.deposit_mint,
+        VotingError::InvalidTokenAccount
+    );
+    let slot_stake = accounts
+        .slot_stake
+        .as_ref()
+        .ok_or(VotingError::MissingProposalCounter)?;
+    require!(
+        holdings.amount >= per_proposal,
+        VotingError::TooManyActiveProposals
+    );
+    token::transfer(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: holdings.to_account_info(),
+                to: slot_stake.to_account_info(),
+                authority: accounts.authority.to_account_info(),
+            },
+        ),
+        per_proposal,
+    )?;
+
+    let counter = accounts
+        .proposal_counter
+        .as_mut()
+        .ok_or(VotingError::MissingProposalCounter)?;
+    require!(
+        counter.active_count < MAX_ACTIVE_PROPOSALS,
+        VotingError::TooManyActiveProposals
+    );
+    counter.authority = authority;
+    counter.active_count += 1;
+    counter.last_created_at = Clock::get()?.unix_timestamp;
+    counter.bump = bump.ok_or(VotingError::MissingProposalCounter)?;
+    Ok(())
+}
 
-        // Check quorum if set
-        if proposal.quorum > 0 {
-            require!(
-                total_votes >= proposal.quorum,
-                VotingError::QuorumNotReached
-            );
-        }
+/// Revealed proposals among the last `window` (by `reveal_number`) the
+/// profile's voter took part in. Bit `i` of `participation` is reveal
+/// number `latest - i`.
+fn recent_participation(profile: &VoterProfile, revealed: u64, window: u8) -> u32 {
+    let oldest = revealed.saturating_sub(window as u64);
+    let span = profile.latest.saturating_sub(oldest);
+    let mask = if span >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << span) - 1
+    };
+    (profile.participation & mask).count_ones()
+}
 
-        // Check threshold: yes_votes must be >= threshold_bps of non-abstain votes
-        let non_abstain = yes_count
-            .checked_add(no_count)
-            .ok_or(VotingError::ArithmeticOverflow)?;
-        let threshold_met = if non_abstain > 0 {
-            yes_count
-                .checked_mul(10_000)
-                .ok_or(VotingError::ArithmeticOverflow)?
-                / non_abstain
-                >= proposal.threshold_bps as u64
-        } else {
-            false
-        };
+/// With `proposer_min_votes`, the creator must have voted in that many of
+/// the last `proposer_vote_window` revealed proposals (fewer while the DAO
+/// has revealed fewer).
+fn require_proposer_participation(
+    config: &DaoConfig,
+    voter_profile: &Option<Account<VoterProfile>>,
+) -> Result<()> {
+    if config.proposer_min_votes == 0 {
+        return Ok(());
+    }
+    let required = (config.proposer_min_votes as u64).min(config.revealed_proposals) as u32;
+    let votes = voter_profile.as_ref().map_or(0, |profile| {
+        recent_participation(
+            profile,
+            config.revealed_proposals,
+            config.proposer_vote_window,
+        )
+    });
+    require!(votes >= required, VotingError::InsufficientParticipation);
+    Ok(())
+}
 
-        let quorum_met = proposal.quorum == 0 || total_votes >= proposal.quorum;
+/// Reject a wallet with a `DenylistEntry` while screening is enabled. The
+/// `ScreeningBlocked` event stays in the failed transaction's logs.
+fn require_not_denied(
+    config: &DaoConfig,
+    denylist_entry: &AccountInfo,
+    actor: Pubkey,
+    proposal: Pubkey,
+    action: ScreenedAction,
+) -> Result<()> {
+    if config.compliance_authority != Pubkey::default()
+        && denylist_entry.data_len() > 0
+        && *denylist_entry.owner == crate::ID
+    {
+        emit!(ScreeningBlocked {
+            actor,
+            proposal,
+            action,
+        });
+        return err!(VotingError::ActorDenied);
+    }
+    Ok(())
+}
 
-        proposal.is_active = false;
-        proposal.is_revealed = true;
-        proposal.yes_votes = yes_count;
-        proposal.no_votes = no_count;
-        proposal.abstain_votes = abstain_count;
-        proposal.passed = quorum_met && threshold_met;
+/// `require_not_denied` for a delegator whose denylist entry arrives in
+/// `remaining_accounts` instead of through account constraints.
+fn require_delegator_not_denied(
+    config: &DaoConfig,
+    denylist_entry: &AccountInfo,
+    delegator: Pubkey,
+    proposal: Pubkey,
+) -> Result<()> {
+    let (expected, _) =
+        Pubkey::find_program_address(&[DENYLIST_SEED, delegator.as_ref()], &crate::ID);
+    require!(
+        denylist_entry.key() == expected,
+        VotingError::InvalidDelegation
+    );
+    require_not_denied(
+        config,
+        denylist_entry,
+        delegator,
+        proposal,
+        ScreenedAction::Vote,
+    )
+}
 
-        let winner = if yes_count > no_count {
-            1u8
-        } else if no_count > yes_count {
-            2u8
-        } else {
-            0u8
-        };
+/// A locale is a zero-padded BCP 47 tag such as `en`, `pt-BR` or `zh-Hant`.
+fn validate_locale(locale: &[u8; 8]) -> Result<()> {
+    let len = locale.iter().position(|b| *b == 0).unwrap_or(locale.len());
+    require!(
+        len >= 2
+            && locale[0].is_ascii_alphabetic()
+            && locale[..len]
+                .iter()
+                .all(|b| b.is_ascii_alphanumeric() || *b == b'-')
+            && locale[len..].iter().all(|b| *b == 0),
+        VotingError::InvalidLocale
+    );
+    Ok(())
+}
 
-        emit!(ResultsRevealed {
-            proposal: proposal.key(),
-            yes_votes: yes_count,
-            no_votes: no_count,
-            abstain_votes: abstain_count,
-            total_votes,
-            winner,
-        });
+/// Bind a vote callback to the ballot it tallies: the computation must be the
+/// one last queued for `vote_record`, on the voter's tally shard. The binding
+/// is cleared, so each queued ballot settles at most once.
+fn settle_ballot(
+    vote_record: &mut VoteRecord,
+    proposal: &Proposal,
+    tally: &Tally,
+    computation_offset: u64,
+) -> Result<()> {
+    require!(
+        vote_record.computation_offset != 0 && vote_record.computation_offset == computation_offset,
+        VotingError::ComputationMismatch
+    );
+    require!(
+        tally.shard == tally_shard_for(&vote_record.voter, proposal.tally_shards),
+        VotingError::WrongTallyShard
+    );
+    vote_record.computation_offset = 0;
+    Ok(())
+}
 
-        Ok(())
+/// Chain a tally update into `proposal.tally_lineage`:
+/// `lineage = sha256(lineage || shard || ciphertext)`.
+fn extend_tally_lineage(proposal: &mut Proposal, tally: &Tally) {
+    proposal.tally_lineage = hashv(&[
+        &proposal.tally_lineage,
+        &[tally.shard],
+        &tally.encrypted_data,
+    ])
+    .to_bytes();
+}
+
+/// Chain an accepted ballot's computation offset into
+/// `proposal.ballot_offsets_hash`: `hash = sha256(hash || offset)`.
+fn extend_ballot_offsets(proposal: &mut Proposal, computation_offset: u64) {
+    proposal.ballot_offsets_hash = hashv(&[
+        &proposal.ballot_offsets_hash,
+        &computation_offset.to_le_bytes(),
+    ])
+    .to_bytes();
+}
+
+/// Require the instruction immediately before the current one to be an
+/// Ed25519 program check of `signer`'s signature over exactly `message`.
+/// The native program aborts the transaction on a bad signature, so only the
+/// offsets need validating here.
+fn verify_ed25519_ix(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let ix = solana_instructions_sysvar::get_instruction_relative(-1, instructions)
+        .map_err(|_| error!(VotingError::MissingSignatureVerification))?;
+    require!(
+        ix.program_id == ed25519_program::ID,
+        VotingError::MissingSignatureVerification
+    );
+
+    // [num_signatures: u8, padding: u8, 7 × u16 offsets, payload...]
+    let data = &ix.data;
+    require!(
+        data.len() >= 16 && data[0] == 1,
+        VotingError::InvalidSignatureVerification
+    );
+    let offset = |i: usize| u16::from_le_bytes([data[2 + i * 2], data[3 + i * 2]]);
+    let (signature_ix, pubkey_offset, pubkey_ix) = (offset(1), offset(2) as usize, offset(3));
+    let (message_offset, message_len, message_ix) =
+        (offset(4) as usize, offset(5) as usize, offset(6));
+    // Signature, key and message must all live in the Ed25519 instruction itself
+    require!(
+        signature_ix == u16::MAX && pubkey_ix == u16::MAX && message_ix == u16::MAX,
+        VotingError::InvalidSignatureVerification
+    );
+
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + 32)
+        .ok_or(VotingError::InvalidSignatureVerification)?;
+    let signed = data
+        .get(message_offset..message_offset + message_len)
+        .ok_or(VotingError::InvalidSignatureVerification)?;
+    require!(
+        pubkey == signer.as_ref() && signed == message,
+        VotingError::InvalidSignatureVerification
+    );
+    Ok(())
+}
+
+/// Require the current top-level instruction to be Arcium's
+/// `callback_computation` settling `pending` with a successful execution.
+/// Arcium only accepts that instruction signed by a node of the computation's
+/// cluster, so the callback's outputs are that cluster's result for the queued
+/// computation rather than caller-chosen arguments.
+fn verify_computation_output(
+    instructions: &AccountInfo,
+    pending: &PendingComputation,
+    mxe_program_id: &Pubkey,
+) -> Result<()> {
+    let ix = solana_instructions_sysvar::get_instruction_relative(0, instructions)
+        .map_err(|_| error!(VotingError::UnverifiedComputationOutput))?;
+    // Accounts: signer, node, mxe, cluster, computation, ...
+    require!(
+        ix.program_id == Arcium::id()
+            && ix.data.starts_with(CallbackComputation::DISCRIMINATOR)
+            && ix.accounts.get(4).map(|meta| meta.pubkey) == Some(pending.computation_account),
+        VotingError::UnverifiedComputationOutput
+    );
+    let output =
+        CallbackComputation::deserialize(&mut &ix.data[CallbackComputation::DISCRIMINATOR.len()..])
+            .map_err(|_| error!(VotingError::UnverifiedComputationOutput))?;
+    require!(
+        output.comp_offset == pending.computation_offset
+            && output.comp_def_offset == pending.comp_def_offset
+            && output.mxe_program == *mxe_program_id
+            && matches!(output.execution_status, ExecutionStatus::Success),
+        VotingError::UnverifiedComputationOutput
+    );
+    Ok(())
+}
+
+/// With `require_delegate_eligibility`, the delegate must hold at least
+/// `delegate_min_balance` of `delegate_gate_mint` when the delegation is made.
+fn require_delegate_eligible(
+    config: &DaoConfig,
+    delegate: &Pubkey,
+    delegate_token_account: &Option<Account<TokenAccount>>,
+) -> Result<()> {
+    if !config.require_delegate_eligibility {
+        return Ok(());
     }
+    let token_account = delegate_token_account
+        .as_ref()
+        .ok_or(VotingError::DelegateNotEligible)?;
+    require!(
+        token_account.owner == *delegate
+            && token_account.mint == config.delegate_gate_mint
+            && token_account.amount >= config.delegate_min_balance,
+        VotingError::DelegateNotEligible
+    );
+    Ok(())
+}
 
-    /// Initialize DAO configuration (one-time setup)
-    pub fn init_dao_config(
-        ctx: Context<InitDaoConfig>,
-        deposit_mint: Pubkey,
-        proposal_deposit: u64,
-        treasury: Pubkey,
-        slash_if_no_quorum: bool,
-    ) -> Result<()> {
-        let config = &mut ctx.accounts.dao_config;
-        config.authority = ctx.accounts.authority.key();
-        config.deposit_mint = deposit_mint;
-        config.proposal_deposit = proposal_deposit;
-        config.treasury = treasury;
-        config.slash_if_no_quorum = slash_if_no_quorum;
-        config.bump = ctx.bumps.dao_config;
-        Ok(())
+/// Message a delegator signs off-chain for `delegate_vote_signed`:
+/// `domain || program_id || delegator || delegate || valid_until || nonce`.
+fn delegation_message(
+    delegator: &Pubkey,
+    delegate: &Pubkey,
+    valid_until: i64,
+    nonce: u64,
+) -> Vec<u8> {
+    [
+        DELEGATION_MESSAGE_DOMAIN,
+        crate::ID.as_ref(),
+        delegator.as_ref(),
+        delegate.as_ref(),
+        &valid_until.to_le_bytes(),
+        &nonce.to_le_bytes(),
+    ]
+    .concat()
+}
+
+/// Message a voter signs off-chain to authorize a relayed `cast_vote_signed`.
+/// `weight_accounts` binds the optional accounts the ballot is weighed from
+/// (see `ballot_weight_accounts`), so a relayer can't drop or swap them.
+#[allow(clippy::too_many_arguments)]
+fn ballot_message(
+    proposal: &Pubkey,
+    voter: &Pubkey,
+    encrypted_choice: &[u8; 32],
+    encrypted_weight: &[u8; 32],
+    nonce: &[u8; 16],
+    voter_pubkey: &[u8; 32],
+    valid_until: i64,
+    sequence: u64,
+    weight_accounts: &[Pubkey; BALLOT_WEIGHT_ACCOUNTS],
+) -> Vec<u8> {
+    let mut message = [
+        BALLOT_MESSAGE_DOMAIN,
+        crate::ID.as_ref(),
+        proposal.as_ref(),
+        voter.as_ref(),
+        encrypted_choice,
+        encrypted_weight,
+        nonce,
+        voter_pubkey,
+        &valid_until.to_le_bytes(),
+        &sequence.to_le_bytes(),
+    ]
+    .concat();
+    for account in weight_accounts {
+        message.extend_from_slice(account.as_ref());
     }
+    message
 }
 
-// ==================== ACCOUNT STRUCTURES ====================
+/// Optional weight accounts a signed ballot commits to, in order: gate token
+/// account, stake account, vesting position, voter escrow and reputation
+/// (`Pubkey::default()` for each one not supplied).
+fn ballot_weight_accounts(accounts: &BallotAccounts) -> [Pubkey; BALLOT_WEIGHT_ACCOUNTS] {
+    [
+        accounts.voter_token_account.as_ref().map(|a| a.key()),
+        accounts.voter_stake_account.as_ref().map(|a| a.key()),
+        accounts.vesting_position.as_ref().map(|a| a.key()),
+        accounts.voter_escrow.as_ref().map(|a| a.key()),
+        accounts.reputation.as_ref().map(|a| a.key()),
+    ]
+    .map(Option::unwrap_or_default)
+}
 
-#[derive(Accounts)]
-#[instruction(proposal_id: u64)]
-pub struct CreateProposal<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+/// Message a relayer signs when it accepts a ballot for submission, binding
+/// it to `hash(ballot_message)`; the voter holds it as evidence for
+/// `challenge_relayer`.
+fn relay_receipt_message(ballot_hash: &[u8; 32]) -> Vec<u8> {
+    [RELAY_RECEIPT_DOMAIN, crate::ID.as_ref(), ballot_hash].concat()
+}
 
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Proposal::INIT_SPACE,
-        seeds = [PROPOSAL_SEED, proposal_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub proposal: Account<'info, Proposal>,
+/// Tally shard a voter's ballots are routed to (hash of the voter key).
+fn tally_shard_for(voter: &Pubkey, tally_shards: u8) -> u8 {
+    if tally_shards <= 1 {
+        return 0;
+    }
+    hashv(&[voter.as_ref()]).to_bytes()[0] % tally_shards
+}
 
-    /// CHECK: Sign PDA for Arcium CPI
-    #[account(
-        seeds = [SIGN_SEED],
-        bump
-    )]
-    pub sign_seed: AccountInfo<'info>,
+/// Whether in-flight vote computations have settled: every callback landed,
+/// or the grace period has closed and the stragglers are abandoned.
+fn votes_settled(proposal: &Proposal, config: &DaoConfig, clock: &Clock) -> bool {
+    proposal.pending_computations == 0
+        || past_deadline_by(proposal, clock, config.callback_grace_period)
+}
 
-    // Arcium accounts
-    pub arcium_program: Program<'info, Arcium>,
-    /// CHECK: MXE account
-    pub mxe_account: AccountInfo<'info>,
-    /// CHECK: Cluster account
-    pub cluster_account: AccountInfo<'info>,
-    /// CHECK: Fee pool
-    pub pool_account: AccountInfo<'info>,
-    /// CHECK: Clock account
-    pub clock_account: AccountInfo<'info>,
-    /// CHECK: Mempool
-    pub mempool_account: AccountInfo<'info>,
-    /// CHECK: Executing pool
-    pub executing_pool: AccountInfo<'info>,
-    /// CHECK: Computation account
-    #[account(mut)]
-    pub computation_account: AccountInfo<'info>,
-    /// CHECK: Comp def account
-    pub comp_def_account: AccountInfo<'info>,
-    /// CHECK: Computation offset account
-    #[account(
-        mut,
-        seeds = [COMPUTATION_OFFSET_SEED],
-        bump = computation_offset_account.bump
-    )]
-    pub computation_offset_account: Account<'info, ComputationOffsetState>,
+/// Whether every secondary shard has been folded into the primary tally.
+fn all_shards_merged(proposal: &Proposal) -> bool {
+    let expected = ((1u16 << proposal.tally_shards) - 2) as u8;
+    proposal.shards_merged == expected
+}
 
-    pub system_program: Program<'info, System>,
+/// Merkle root over `leaves` (odd nodes are paired with themselves).
+fn merkle_root(mut leaves: Vec<[u8; 32]>) -> [u8; 32] {
+    while leaves.len() > 1 {
+        leaves = leaves
+            .chunks(2)
+            .map(|pair| {
+                let right = pair.get(1).unwrap_or(&pair[0]);
+                hashv(&[&pair[0], right]).to_bytes()
+            })
+            .collect();
+    }
+    leaves.first().copied().unwrap_or_default()
 }
 
-#[derive(Accounts)]
-pub struct InitTallyCallback<'info> {
-    #[account(
-        mut,
-        constraint = proposal.authority == authority.key() @ VotingError::Unauthorized
-    )]
-    pub proposal: Account<'info, Proposal>,
+/// Check a positional Merkle proof built like `merkle_root`: bit `i` of
+/// `index` says whether the node is the right child at level `i`.
+fn verify_merkle_proof(root: &[u8; 32], leaf: [u8; 32], index: u64, proof: &[[u8; 32]]) -> bool {
+    let node = proof
+        .iter()
+        .enumerate()
+        .fold(leaf, |node, (level, sibling)| {
+            if (index >> level) & 1 == 0 {
+                hashv(&[&node, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &node]).to_bytes()
+            }
+        });
+    proof.len() < 64 && index >> proof.len() == 0 && node == *root
+}
 
-    /// CHECK: Proposal authority — validated by constraint above
-    pub authority: AccountInfo<'info>,
+/// Close a program-owned account, sending its lamports to `destination`.
+fn close_program_account(info: &AccountInfo, destination: &AccountInfo) -> Result<()> {
+    let lamports = info.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(VotingError::ArithmeticOverflow)?;
+    **info.try_borrow_mut_lamports()? = 0;
+    info.assign(&system_program::ID);
+    info.resize(0).map_err(Into::into)
+}
 
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + Tally::INIT_SPACE,
-        seeds = [TALLY_SEED, proposal.key().as_ref()],
-        bump
-    )]
-    pub tally: Account<'info, Tally>,
+/// Create the `VoteRecord` PDA for `record.voter` from an unchecked account, as
+/// `init` would. Fails if the voter already has a record for this proposal.
+fn create_vote_record<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    record: &VoteRecord,
+) -> Result<()> {
+    let (expected, bump) = Pubkey::find_program_address(
+        &[
+            VOTE_RECORD_SEED,
+            record.proposal.as_ref(),
+            record.voter.as_ref(),
+        ],
+        &crate::ID,
+    );
+    require_keys_eq!(info.key(), expected, VotingError::InvalidVoteRecord);
+    require!(
+        info.data_is_empty() && *info.owner == system_program::ID,
+        VotingError::AlreadyVoted
+    );
+
+    let seeds: &[&[u8]] = &[
+        VOTE_RECORD_SEED,
+        record.proposal.as_ref(),
+        record.voter.as_ref(),
+        &[bump],
+    ];
+    let space = 8 + VoteRecord::INIT_SPACE;
+    let rent = Rent::get()?.minimum_balance(space);
+    let top_up = rent.saturating_sub(info.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program_info.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program_info.clone(),
+            system_program::Allocate {
+                account_to_allocate: info.clone(),
+            },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program_info.clone(),
+            system_program::Assign {
+                account_to_assign: info.clone(),
+            },
+            &[seeds],
+        ),
+        &crate::ID,
+    )?;
+
+    let mut record = record.clone();
+    record.bump = bump;
+    record.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+}
 
-    /// CHECK: Sign PDA ensures this callback was invoked via Arcium CPI
-    #[account(
-        seeds = [SIGN_SEED],
-        bump,
-        signer
-    )]
-    pub sign_seed: AccountInfo<'info>,
+/// Whether an action moves value out of the DAO treasury.
+fn spends_treasury(action: &ProposalAction) -> bool {
+    matches!(
+        action,
+        ProposalAction::TokenTransfer { .. }
+            | ProposalAction::SolTransfer { .. }
+            | ProposalAction::FundRentExempt { .. }
+            | ProposalAction::MintTokens { .. }
+            | ProposalAction::Invoke { .. }
+            | ProposalAction::StreamPayment { .. }
+            | ProposalAction::MilestoneGrant { .. }
+    )
+}
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    pub system_program: Program<'info, System>,
+/// Add `amount` to the running total spent from `vault` and return the total.
+fn accumulate_spend(spends: &mut Vec<(Pubkey, u64)>, vault: Pubkey, amount: u64) -> Result<u64> {
+    match spends.iter_mut().find(|(key, _)| *key == vault) {
+        Some((_, total)) => {
+            *total = total
+                .checked_add(amount)
+                .ok_or(VotingError::ArithmeticOverflow)?;
+            Ok(*total)
+        }
+        None => {
+            spends.push((vault, amount));
+            Ok(amount)
+        }
+    }
 }
 
-#[derive(Accounts)]
-pub struct CastVote<'info> {
-    #[account(mut)]
-    pub voter: Signer<'info>,
+/// Last moment tranche `index` of a grant can be released.
+fn tranche_deadline(grant: &Grant, index: u8) -> i64 {
+    grant.opened_at + (index as i64 + 1) * grant.milestone_period
+}
+
+/// Release a grant's next tranche from its escrow to the recipient. The grant
+/// PDA signs for the escrow.
+fn release_next_tranche<'info>(
+    grant: &mut Account<'info, Grant>,
+    escrow: &Account<'info, TokenAccount>,
+    recipient: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    require!(
+        !grant.clawed_back && (grant.released as usize) < grant.tranches.len(),
+        VotingError::GrantClosed
+    );
+    require!(
+        Clock::get()?.unix_timestamp <= tranche_deadline(grant, grant.released),
+        VotingError::MilestoneLapsed
+    );
+    require!(
+        escrow.key() == grant.escrow,
+        VotingError::InvalidTreasuryVault
+    );
+    require!(
+        recipient.key() == grant.recipient,
+        VotingError::InvalidRecipient
+    );
+
+    let index = grant.released;
+    let amount = grant.tranches[index as usize];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        GRANT_SEED,
+        grant.proposal.as_ref(),
+        &[grant.index],
+        &[grant.bump],
+    ]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: escrow.to_account_info(),
+                to: recipient.to_account_info(),
+                authority: grant.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    grant.released += 1;
+
+    emit!(TrancheReleased {
+        grant: grant.key(),
+        index,
+        amount,
+    });
+    Ok(())
+}
+
+/// Charge a treasury spend of `amount` of `mint` (default key = lamports)
+/// against its `SpendTracker`, if one is configured. The allowance refills
+/// linearly over the tracker's window. Supermajority proposals are exempt.
+fn charge_spend_limit(
+    accounts: &ExecuteProposal,
+    program_id: &Pubkey,
+    mint: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    if accounts.proposal.threshold_bps >= SUPERMAJORITY_BPS {
+        return Ok(());
+    }
+    // Always required, so an executor cannot skip a configured limit
+    let info = accounts
+        .spend_tracker
+        .as_ref()
+        .ok_or(VotingError::MissingExecutionAccount)?;
+    let (expected, _) =
+        Pubkey::find_program_address(&[SPEND_TRACKER_SEED, mint.as_ref()], program_id);
+    require!(info.key() == expected, VotingError::InvalidSpendTracker);
+    if info.data_len() == 0 || *info.owner != crate::ID {
+        return Ok(());
+    }
+
+    let mut tracker = SpendTracker::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    if tracker.window == 0 {
+        return Ok(());
+    }
+    refill_spend_tracker(&mut tracker, Clock::get()?.unix_timestamp);
+    let spent = tracker
+        .spent
+        .checked_add(amount)
+        .ok_or(VotingError::ArithmeticOverflow)?;
+    require!(spent <= tracker.limit, VotingError::SpendLimitExceeded);
+    tracker.spent = spent;
+    tracker.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+}
+
+/// Credit the allowance refilled since `updated_at` (nothing for a tracker
+/// with no limit configured).
+fn refill_spend_tracker(tracker: &mut SpendTracker, now: i64) {
+    if tracker.window > 0 {
+        let elapsed = (now - tracker.updated_at).max(0) as u128;
+        let refilled =
+            (tracker.limit as u128 * elapsed / tracker.window as u128).min(u64::MAX as u128);
+        tracker.spent = tracker.spent.saturating_sub(refilled as u64);
+    }
+    tracker.updated_at = now;
+}
+
+/// Execution payloads may only CPI into the token and system programs or a
+/// program on `DaoConfig.allowed_programs`.
+fn require_allowed_program(config: &DaoConfig, program_id: &Pubkey) -> Result<()> {
+    require!(
+        *program_id == token::ID
+            || *program_id == system_program::ID
+            || config.allowed_programs.contains(program_id),
+        VotingError::ProgramNotAllowed
+    );
+    Ok(())
+}
+
+/// Under CPI, `authority` must be the `[PROGRAM_PROPOSER_SEED]` PDA of an
+/// allowlisted `caller_program`. Only that program can sign for its PDA, so a
+/// caller cannot impersonate a user whose signature it forwards.
+fn require_cpi_proposer(
+    config: &DaoConfig,
+    authority: &Pubkey,
+    caller_program: &Option<UncheckedAccount>,
+) -> Result<()> {
+    if get_stack_height() <= TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(());
+    }
+    let program = caller_program
+        .as_ref()
+        .ok_or(VotingError::ProposerProgramNotAllowed)?
+        .key();
+    require!(
+        config.proposer_programs.contains(&program),
+        VotingError::ProposerProgramNotAllowed
+    );
+    let (expected, _) = Pubkey::find_program_address(&[PROGRAM_PROPOSER_SEED], &program);
+    require!(*authority == expected, VotingError::InvalidProgramProposer);
+    Ok(())
+}
+
+/// Rights of `wallet` under `DaoConfig.voter_tiers`: those of the highest band
+/// its `tier_mint` escrow reaches at the start of the current slot (none below
+/// the lowest band), or `DEFAULT_TIER_RIGHTS` while no tiers are configured.
+/// Escrowed balances can't be borrowed into a wallet for one instruction and
+/// repaid, so rights such as the deposit exemption need tokens actually held.
+fn resolve_tier(
+    config: &DaoConfig,
+    wallet: &Pubkey,
+    escrow: &Option<Account<VoterEscrow>>,
+) -> Result<u8> {
+    if config.voter_tiers.is_empty() {
+        return Ok(DEFAULT_TIER_RIGHTS);
+    }
+    let balance = match escrow {
+        Some(escrow) => {
+            require!(
+                escrow.owner == *wallet && escrow.mint == config.tier_mint,
+                VotingError::InvalidCheckpoint
+            );
+            escrow_balance_at(escrow, Clock::get()?.slot)?
+        }
+        None => 0,
+    };
+    Ok(config
+        .voter_tiers
+        .iter()
+        .rev()
+        .find(|tier| balance >= tier.min_balance)
+        .map_or(0, |tier| tier.rights))
+}
+
+/// Deserialize an SPL token account, checking it is owned by the token program.
+fn token_account_at(info: &AccountInfo) -> Result<TokenAccount> {
+    require!(*info.owner == token::ID, VotingError::InvalidTokenAccount);
+    TokenAccount::try_deserialize(&mut &info.try_borrow_data()?[..])
+}
+
+/// Ragequit is open between reveal and execution of a passed parent-DAO
+/// proposal that spends from the treasury.
+fn require_ragequit_window(proposal: &Proposal, payload: &ExecutionPayload) -> Result<()> {
+    require!(
+        proposal.is_revealed && proposal.passed,
+        VotingError::ProposalNotPassed
+    );
+    require!(
+        proposal.committee.is_none() && payload.actions.iter().any(spends_treasury),
+        VotingError::NotTreasurySpending
+    );
+    require!(
+        !proposal.cancelled
+            && payload.actions_executed == 0
+            && Clock::get()?.unix_timestamp < executable_at(proposal)?,
+        VotingError::RagequitWindowClosed
+    );
+    Ok(())
+}
+
+/// Execute a single payload action. Each action runs in its own instruction
+/// so a failure reverts only that action and execution can be resumed.
+fn execute_action<'info>(
+    accounts: &mut ExecuteProposal<'info>,
+    program_id: &Pubkey,
+    remaining: &[AccountInfo<'info>],
+    action: ProposalAction,
+) -> Result<()> {
+    let scope = accounts.proposal.committee;
+    match action {
+        ProposalAction::TokenTransfer {
+            mint,
+            recipient,
+            amount,
+        } => {
+            let source = accounts
+                .source_vault
+                .as_ref()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            let destination = accounts
+                .recipient_token_account
+                .as_ref()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            require!(source.mint == mint, VotingError::InvalidTokenMint);
+            require!(
+                destination.key() == recipient,
+                VotingError::InvalidRecipient
+            );
+
+            // Committee proposals spend from the committee sub-vault within
+            // the remaining budget; parent proposals spend from the treasury.
+            let (vault_authority, bump) = match scope {
+                Some(committee_key) => {
+                    let committee = accounts
+                        .committee
+                        .as_mut()
+                        .ok_or(VotingError::MissingExecutionAccount)?;
+                    require!(
+                        committee.key() == committee_key,
+                        VotingError::CommitteeScopeViolation
+                    );
+                    require!(committee.budget_mint == mint, VotingError::InvalidTokenMint);
+                    let spent = committee
+                        .spent
+                        .checked_add(amount)
+                        .ok_or(VotingError::ArithmeticOverflow)?;
+                    require!(
+                        spent <= committee.spending_cap,
+                        VotingError::CommitteeBudgetExceeded
+                    );
+                    committee.spent = spent;
+                    Pubkey::find_program_address(
+                        &[COMMITTEE_VAULT_SEED, committee_key.as_ref()],
+                        program_id,
+                    )
+                }
+                None => Pubkey::find_program_address(&[TREASURY_SEED], program_id),
+            };
+            require!(
+                accounts.vault_authority.key() == vault_authority
+                    && source.owner == vault_authority,
+                VotingError::InvalidTreasuryVault
+            );
+            // Committee sub-vaults are bounded by their own budget instead
+            if scope.is_none() {
+                charge_spend_limit(accounts, program_id, mint, amount)?;
+            }
+
+            let bump_seed = [bump];
+            let signer_seeds: &[&[&[u8]]] = match scope {
+                Some(ref committee_key) => {
+                    &[&[COMMITTEE_VAULT_SEED, committee_key.as_ref(), &bump_seed]]
+                }
+                None => &[&[TREASURY_SEED, &bump_seed]],
+            };
+            token::transfer(
+                CpiContext::new_with_signer(
+                    accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: source.to_account_info(),
+                        to: destination.to_account_info(),
+                        authority: accounts.vault_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+        ProposalAction::SetCommitteeBudget {
+            committee: committee_key,
+            spending_cap,
+        } => {
+            let committee = accounts
+                .committee
+                .as_mut()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            require!(
+                committee.key() == committee_key,
+                VotingError::CommitteeScopeViolation
+            );
+            committee.spending_cap = spending_cap;
+
+            emit!(CommitteeBudgetUpdated {
+                committee: committee_key,
+                spending_cap,
+                spent: committee.spent,
+            });
+        }
+        ProposalAction::SolTransfer {
+            recipient,
+            lamports,
+        } => {
+            sol_treasury_transfer(accounts, program_id, recipient, lamports)?;
+        }
+        ProposalAction::FundRentExempt { recipient, space } => {
+            let lamports = Rent::get()?.minimum_balance(space as usize);
+            sol_treasury_transfer(accounts, program_id, recipient, lamports)?;
+        }
+        ProposalAction::UpdateConfig { change } => {
+            let config = accounts
+                .dao_config
+                .as_mut()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            if let Some(default_quorum) = change.default_quorum {
+                config.default_quorum = default_quorum;
+            }
+            if let Some(proposal_deposit) = change.proposal_deposit {
+                config.proposal_deposit = proposal_deposit;
+            }
+            if let Some(treasury) = change.treasury {
+                config.treasury = treasury;
+            }
+            if let Some(council) = change.council {
+                config.council = council;
+            }
+            if let Some(compliance_authority) = change.compliance_authority {
+                config.compliance_authority = compliance_authority;
+            }
+            if let Some(moderator) = change.moderator {
+                config.moderator = moderator;
+            }
+            if let Some(snapshot_oracle) = change.snapshot_oracle {
+                config.snapshot_oracle = snapshot_oracle;
+            }
+            if let Some(outbox_executor) = change.outbox_executor {
+                config.outbox_executor = outbox_executor;
+            }
+            if let Some(appeal_bond) = change.appeal_bond {
+                require!(
+                    appeal_bond >= Rent::get()?.minimum_balance(0),
+                    VotingError::BondBelowRentExemption
+                );
+                config.appeal_bond = appeal_bond;
+            }
+            if let Some(recount_bond) = change.recount_bond {
+                config.recount_bond = recount_bond;
+            }
+            if let Some(dispute_window) = change.dispute_window {
+                require!(dispute_window >= 0, VotingError::InvalidDisputeWindow);
+                config.dispute_window = dispute_window;
+            }
+            if let Some(max_ballot_validity) = change.max_ballot_validity {
+                require!(max_ballot_validity >= 0, VotingError::InvalidBallotValidity);
+                config.max_ballot_validity = max_ballot_validity;
+            }
+            if let Some(relayer_bond) = change.relayer_bond {
+                config.relayer_bond = relayer_bond;
+            }
+            if let Some(tokens_per_active_proposal) = change.tokens_per_active_proposal {
+                config.tokens_per_active_proposal = tokens_per_active_proposal;
+            }
+            if change.proposer_min_votes.is_some() || change.proposer_vote_window.is_some() {
+                config.proposer_min_votes = change
+                    .proposer_min_votes
+                    .unwrap_or(config.proposer_min_votes);
+                config.proposer_vote_window = change
+                    .proposer_vote_window
+                    .unwrap_or(config.proposer_vote_window);
+                require!(
+                    config.proposer_min_votes <= config.proposer_vote_window
+                        && config.proposer_vote_window <= MAX_PARTICIPATION_WINDOW,
+                    VotingError::InvalidParticipationRule
+                );
+            }
+            if let Some(sponsorship_bps) = change.sponsorship_bps {
+                require!(
+                    sponsorship_bps <= 10_000,
+                    VotingError::InvalidSponsorshipRule
+                );
+                config.sponsorship_bps = sponsorship_bps;
+            }
+            if let Some(sponsorship_period) = change.sponsorship_period {
+                require!(sponsorship_period > 0, VotingError::InvalidSponsorshipRule);
+                config.sponsorship_period = sponsorship_period;
+            }
+            if let Some(unsponsored_slash_bps) = change.unsponsored_slash_bps {
+                require!(
+                    unsponsored_slash_bps <= 10_000,
+                    VotingError::InvalidSponsorshipRule
+                );
+                config.unsponsored_slash_bps = unsponsored_slash_bps;
+            }
+            if let Some(same_slot_guard) = change.same_slot_guard {
+                config.same_slot_guard = same_slot_guard;
+            }
+            if let Some(vesting_boost_bps) = change.vesting_boost_bps {
+                require!(
+                    (10_000..=MAX_MULTIPLIER_BPS).contains(&vesting_boost_bps),
+                    VotingError::InvalidMultiplier
+                );
+                config.vesting_boost_bps = vesting_boost_bps;
+            }
+            if let Some(deposit_in_usd) = change.deposit_in_usd {
+                config.deposit_in_usd = deposit_in_usd;
+            }
+            if let Some(deposit_in_sol) = change.deposit_in_sol {
+                config.deposit_in_sol = deposit_in_sol;
+            }
+            if let Some(reminder_window) = change.reminder_window {
+                config.reminder_window = reminder_window;
+            }
+            if let Some(reminder_interval) = change.reminder_interval {
+                config.reminder_interval = reminder_interval;
+            }
+            if let Some(crank_reward) = change.crank_reward {
+                config.crank_reward = crank_reward;
+            }
+            require!(
+                config.crank_reward == 0 || config.reminder_interval > 0,
+                VotingError::InvalidReminderInterval
+            );
+            if let Some(random_tie_break) = change.random_tie_break {
+                config.random_tie_break = random_tie_break;
+            }
+            if let Some(graded_slash) = change.graded_slash {
+                config.graded_slash = graded_slash;
+            }
+            if let Some(graded_slash_partial_bps) = change.graded_slash_partial_bps {
+                require!(
+                    graded_slash_partial_bps <= 10_000,
+                    VotingError::InvalidSlashSchedule
+                );
+                config.graded_slash_partial_bps = graded_slash_partial_bps;
+            }
+            if let Some(revenue_share_bps) = change.revenue_share_bps {
+                require!(
+                    revenue_share_bps <= 10_000,
+                    VotingError::InvalidRevenueShare
+                );
+                config.revenue_share_bps = revenue_share_bps;
+            }
+            if let Some(delegator_reward_share_bps) = change.delegator_reward_share_bps {
+                require!(
+                    delegator_reward_share_bps <= 10_000,
+                    VotingError::InvalidRevenueShare
+                );
+                config.delegator_reward_share_bps = delegator_reward_share_bps;
+            }
+            if let Some(require_delegate_acceptance) = change.require_delegate_acceptance {
+                config.require_delegate_acceptance = require_delegate_acceptance;
+            }
+            if let Some(require_delegate_eligibility) = change.require_delegate_eligibility {
+                config.require_delegate_eligibility = require_delegate_eligibility;
+            }
+            if let Some(delegate_gate_mint) = change.delegate_gate_mint {
+                config.delegate_gate_mint = delegate_gate_mint;
+            }
+            if let Some(delegate_min_balance) = change.delegate_min_balance {
+                config.delegate_min_balance = delegate_min_balance;
+            }
+            if let Some(quorum_bps) = change.quorum_bps {
+                require!(quorum_bps <= 10_000, VotingError::InvalidThreshold);
+                config.quorum_bps = quorum_bps;
+            }
+            if let Some(threshold_base) = change.threshold_base {
+                config.threshold_base = threshold_base;
+            }
+            if let Some(tier_mint) = change.tier_mint {
+                config.tier_mint = tier_mint;
+            }
+            if let Some(voter_tiers) = change.voter_tiers {
+                require!(
+                    voter_tiers.len() <= MAX_VOTER_TIERS
+                        && voter_tiers
+                            .windows(2)
+                            .all(|pair| pair[0].min_balance < pair[1].min_balance),
+                    VotingError::InvalidVoterTiers
+                );
+                config.voter_tiers = voter_tiers;
+            }
+            if let Some(multiplier) = change.mint_multiplier {
+                require!(
+                    multiplier.multiplier_bps > 0
+                        && multiplier.multiplier_bps <= MAX_MULTIPLIER_BPS,
+                    VotingError::InvalidMultiplier
+                );
+                config
+                    .mint_multipliers
+                    .retain(|m| m.mint != multiplier.mint);
+                if multiplier.multiplier_bps != 10_000 {
+                    require!(
+                        config.mint_multipliers.len() < MAX_MINT_MULTIPLIERS,
+                        VotingError::MultiplierTableFull
+                    );
+                    config.mint_multipliers.push(multiplier);
+                }
+            }
+            if let Some(quorum_decay_period) = change.quorum_decay_period {
+                require!(quorum_decay_period >= 0, VotingError::InvalidThreshold);
+                config.quorum_decay_period = quorum_decay_period;
+            }
+            if let Some(quorum_floor_bps) = change.quorum_floor_bps {
+                require!(quorum_floor_bps <= 10_000, VotingError::InvalidThreshold);
+                config.quorum_floor_bps = quorum_floor_bps;
+            }
+            if let Some(revenue_epoch_duration) = change.revenue_epoch_duration {
+                require!(
+                    revenue_epoch_duration >= 0,
+                    VotingError::InvalidRevenueShare
+                );
+                config.revenue_epoch_duration = revenue_epoch_duration;
+            }
+            if let Some(callback_grace_period) = change.callback_grace_period {
+                require!(callback_grace_period >= 0, VotingError::InvalidGracePeriod);
+                config.callback_grace_period = callback_grace_period;
+            }
+            // Queued payloads invoking a disallowed program fail at execution
+            if let Some(program) = change.allow_program {
+                require!(
+                    !config.allowed_programs.contains(&program),
+                    VotingError::AlreadyRegistered
+                );
+                require!(
+                    config.allowed_programs.len() < MAX_ALLOWED_PROGRAMS,
+                    VotingError::ProgramAllowlistFull
+                );
+                config.allowed_programs.push(program);
+            }
+            if let Some(program) = change.disallow_program {
+                let before = config.allowed_programs.len();
+                config.allowed_programs.retain(|p| *p != program);
+                require!(
+                    config.allowed_programs.len() < before,
+                    VotingError::ProgramNotAllowed
+                );
+            }
+            if let Some(observer) = change.register_observer {
+                require!(
+                    !config.observers.contains(&observer),
+                    VotingError::AlreadyRegistered
+                );
+                require!(
+                    config.observers.len() < MAX_OBSERVERS,
+                    VotingError::ObserverRegistryFull
+                );
+                config.observers.push(observer);
+            }
+            if let Some(observer) = change.remove_observer {
+                let before = config.observers.len();
+                config.observers.retain(|o| *o != observer);
+                require!(
+                    config.observers.len() < before,
+                    VotingError::ObserverNotRegistered
+                );
+            }
+            // Proposals already bound to a revoked pair keep using it
+            if let Some(entry) = change.approve_mxe {
+                require!(
+                    !config.approved_mxes.contains(&entry),
+                    VotingError::AlreadyRegistered
+                );
+                require!(
+                    config.approved_mxes.len() < MAX_APPROVED_MXES,
+                    VotingError::MxeAllowlistFull
+                );
+                config.approved_mxes.push(entry);
+            }
+            if let Some(entry) = change.revoke_mxe {
+                let before = config.approved_mxes.len();
+                config.approved_mxes.retain(|m| *m != entry);
+                require!(
+                    config.approved_mxes.len() < before,
+                    VotingError::MxeNotApproved
+                );
+            }
+            if let Some(SpendLimit {
+                mint,
+                limit,
+                window,
+            }) = change.spend_limit
+            {
+                require!(window > 0, VotingError::InvalidSpendLimit);
+                let info = accounts
+                    .spend_tracker
+                    .as_ref()
+                    .ok_or(VotingError::MissingExecutionAccount)?;
+                let (expected, _) =
+                    Pubkey::find_program_address(&[SPEND_TRACKER_SEED, mint.as_ref()], program_id);
+                require!(
+                    info.key() == expected && *info.owner == crate::ID,
+                    VotingError::InvalidSpendTracker
+                );
+                let mut tracker = SpendTracker::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+                // Settle under the old terms so the change isn't retroactive
+                refill_spend_tracker(&mut tracker, Clock::get()?.unix_timestamp);
+                tracker.limit = limit;
+                tracker.window = window;
+                tracker.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+            }
+            if change.turnout_boosts.is_some() || change.turnout_match_budget.is_some() {
+                if let Some(turnout_boosts) = change.turnout_boosts {
+                    require_valid_boosts(&turnout_boosts)?;
+                    config.turnout_boosts = turnout_boosts;
+                }
+                if let Some(turnout_match_budget) = change.turnout_match_budget {
+                    config.turnout_match_budget = turnout_match_budget;
+                }
+                emit!(TurnoutBoostSet {
+                    proposal: accounts.proposal.key(),
+                    boosts: config.turnout_boosts.clone(),
+                    budget: config.turnout_match_budget,
+                });
+            }
+
+            emit!(DaoConfigUpdated {
+                proposal: accounts.proposal.key(),
+                default_quorum: config.default_quorum,
+                proposal_deposit: config.proposal_deposit,
+                treasury: config.treasury,
+            });
+        }
+        ProposalAction::CancelExecution { proposal } => {
+            // Overriding an already-passed vote requires a supermajority follow-up
+            require!(
+                accounts.proposal.threshold_bps >= SUPERMAJORITY_BPS,
+                VotingError::SupermajorityRequired
+            );
+            let canceller = accounts.proposal.key();
+            let target = accounts
+                .target_proposal
+                .as_mut()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            require!(
+                target.key() == proposal && proposal != canceller,
+                VotingError::InvalidTargetProposal
+            );
+            cancel_queued_execution(target, canceller)?;
+        }
+        ProposalAction::MintTokens {
+            mint,
+            recipient,
+            amount,
+        } => {
+            let mint_account = accounts
+                .mint
+                .as_ref()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            let destination = accounts
+                .recipient_token_account
+                .as_ref()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            require!(mint_account.key() == mint, VotingError::InvalidTokenMint);
+            require!(
+                destination.key() == recipient,
+                VotingError::InvalidRecipient
+            );
+
+            // The DAO can only mint tokens whose mint authority is the program PDA
+            let (mint_authority, bump) =
+                Pubkey::find_program_address(&[MINT_AUTHORITY_SEED], program_id);
+            require!(
+                accounts.vault_authority.key() == mint_authority
+                    && mint_account.mint_authority == COption::Some(mint_authority),
+                VotingError::InvalidMintAuthority
+            );
+
+            let signer_seeds: &[&[&[u8]]] = &[&[MINT_AUTHORITY_SEED, &[bump]]];
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: mint_account.to_account_info(),
+                        to: destination.to_account_info(),
+                        authority: accounts.vault_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+        ProposalAction::StreamPayment {
+            mint,
+            recipient,
+            amount,
+            duration,
+        } => {
+            // The full stream is committed against the spend limit and moved
+            // into the stream's escrow up front, so later treasury spends
+            // cannot leave it unfunded
+            charge_spend_limit(accounts, program_id, mint, amount)?;
+            let proposal_key = accounts.proposal.key();
+            let index = accounts.execution_payload.actions_executed;
+            let (stream_key, bump) = Pubkey::find_program_address(
+                &[STREAM_SEED, proposal_key.as_ref(), &[index]],
+                program_id,
+            );
+            let (treasury_authority, treasury_bump) =
+                Pubkey::find_program_address(&[TREASURY_SEED], program_id);
+            let source = accounts
+                .source_vault
+                .as_ref()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            let escrow = accounts
+                .stream_escrow
+                .as_ref()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            require!(
+                source.mint == mint && escrow.mint == mint,
+                VotingError::InvalidTokenMint
+            );
+            require!(
+                accounts.vault_authority.key() == treasury_authority
+                    && source.owner == treasury_authority
+                    && escrow.owner == stream_key,
+                VotingError::InvalidTreasuryVault
+            );
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: source.to_account_info(),
+                        to: escrow.to_account_info(),
+                        authority: accounts.vault_authority.to_account_info(),
+                    },
+                    &[&[TREASURY_SEED, &[treasury_bump]]],
+                ),
+                amount,
+            )?;
+
+            let escrow_key = escrow.key();
+            let stream = accounts
+                .stream
+                .as_mut()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            let now = Clock::get()?.unix_timestamp;
+            stream.proposal = proposal_key;
+            stream.index = index;
+            stream.mint = mint;
+            stream.recipient = recipient;
+            stream.escrow = escrow_key;
+            stream.total = amount;
+            stream.claimed = 0;
+            stream.start_at = now;
+            stream.end_at = now
+                .checked_add(duration)
+                .ok_or(VotingError::ArithmeticOverflow)?;
+            stream.bump = bump;
+
+            emit!(StreamOpened {
+                stream: stream_key,
+                recipient,
+                amount,
+                end_at: stream.end_at,
+            });
+        }
+        ProposalAction::MilestoneGrant {
+            mint,
+            recipient,
+            tranches,
+            milestone_period,
+        } => {
+            let total = tranches
+                .iter()
+                .try_fold(0u64, |sum, tranche| sum.checked_add(*tranche))
+                .ok_or(VotingError::ArithmeticOverflow)?;
+            charge_spend_limit(accounts, program_id, mint, total)?;
+
+            let proposal_key = accounts.proposal.key();
+            let index = accounts.execution_payload.actions_executed;
+            let (grant_key, bump) = Pubkey::find_program_address(
+                &[GRANT_SEED, proposal_key.as_ref(), &[index]],
+                program_id,
+            );
+            let (treasury_authority, treasury_bump) =
+                Pubkey::find_program_address(&[TREASURY_SEED], program_id);
+            let source = accounts
+                .source_vault
+                .as_ref()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            let escrow = accounts
+                .grant_escrow
+                .as_ref()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            require!(
+                source.mint == mint && escrow.mint == mint,
+                VotingError::InvalidTokenMint
+            );
+            require!(
+                accounts.vault_authority.key() == treasury_authority
+                    && source.owner == treasury_authority
+                    && escrow.owner == grant_key,
+                VotingError::InvalidTreasuryVault
+            );
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: source.to_account_info(),
+                        to: escrow.to_account_info(),
+                        authority: accounts.vault_authority.to_account_info(),
+                    },
+                    &[&[TREASURY_SEED, &[treasury_bump]]],
+                ),
+                total,
+            )?;
+
+            let escrow_key = escrow.key();
+            let grant = accounts
+                .grant
+                .as_mut()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            grant.proposal = proposal_key;
+            grant.index = index;
+            grant.mint = mint;
+            grant.recipient = recipient;
+            grant.escrow = escrow_key;
+            grant.tranches = tranches;
+            grant.released = 0;
+            grant.opened_at = Clock::get()?.unix_timestamp;
+            grant.milestone_period = milestone_period;
+            grant.clawed_back = false;
+            grant.bump = bump;
+
+            emit!(GrantOpened {
+                grant: grant_key,
+                recipient,
+                total,
+                tranches: grant.tranches.len() as u8,
+            });
+        }
+        ProposalAction::ReleaseTranche { grant } => {
+            let target = accounts
+                .target_grant
+                .as_mut()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            require!(target.key() == grant, VotingError::InvalidGrant);
+            let escrow = accounts
+                .grant_escrow
+                .as_ref()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            let recipient = accounts
+                .recipient_token_account
+                .as_ref()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            release_next_tranche(target, escrow, recipient, &accounts.token_program)?;
+        }
+        ProposalAction::PostObligation { kind, data } => {
+            let proposal_key = accounts.proposal.key();
+            let index = accounts.execution_payload.actions_executed;
+            let (_, bump) = Pubkey::find_program_address(
+                &[OBLIGATION_SEED, proposal_key.as_ref(), &[index]],
+                program_id,
+            );
+            let outbox = accounts
+                .outbox
+                .as_mut()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            let seq = outbox.next_seq;
+            outbox.next_seq = seq.checked_add(1).ok_or(VotingError::ArithmeticOverflow)?;
+            // Each obligation gets its own account, so a backlog of
+            // unacknowledged ones never blocks execution
+            let obligation = accounts
+                .obligation
+                .as_mut()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            obligation.seq = seq;
+            obligation.proposal = proposal_key;
+            obligation.action_index = index;
+            obligation.kind = kind;
+            obligation.data = data;
+            obligation.posted_at = Clock::get()?.unix_timestamp;
+            obligation.acknowledged_at = 0;
+            obligation.bump = bump;
+
+            emit!(ObligationPosted {
+                seq,
+                proposal: proposal_key,
+                kind,
+            });
+        }
+        ProposalAction::Invoke {
+            program_id: target,
+            accounts: invoke_accounts,
+            data,
+        } => {
+            // Re-checked at execution: the allowlist may have shrunk since creation
+            let config = accounts
+                .dao_config
+                .as_ref()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            require_allowed_program(config, &target)?;
+
+            // The invoke authority is the only signer the program lends to a
+            // CPI. It never signs for the treasury, so an invoked program can
+            // only move what earlier actions paid it within the spend limits.
+            let (invoke_authority, bump) =
+                Pubkey::find_program_address(&[INVOKE_AUTHORITY_SEED], program_id);
+            require!(
+                accounts.vault_authority.key() == invoke_authority,
+                VotingError::InvalidTreasuryVault
+            );
+            let mut metas = Vec::with_capacity(invoke_accounts.len());
+            for account in &invoke_accounts {
+                require!(
+                    !account.is_signer || account.pubkey == invoke_authority,
+                    VotingError::InvalidInvokeAccounts
+                );
+                metas.push(if account.is_writable {
+                    AccountMeta::new(account.pubkey, account.is_signer)
+                } else {
+                    AccountMeta::new_readonly(account.pubkey, account.is_signer)
+                });
+            }
+
+            let mut infos = remaining.to_vec();
+            infos.push(accounts.vault_authority.to_account_info());
+            invoke_signed(
+                &Instruction {
+                    program_id: target,
+                    accounts: metas,
+                    data,
+                },
+                &infos,
+                &[&[INVOKE_AUTHORITY_SEED, &[bump]]],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+// ==================== PROGRAM ====================
+
+#[program]
+pub mod private_dao_voting {
+    use super::*;
+
+    /// Create a new proposal and initialize encrypted tally.
+    ///
+    /// Callable through CPI by programs on `DaoConfig.proposer_programs`,
+    /// signing as their `[PROGRAM_PROPOSER_SEED]` PDA (see the crate docs).
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        proposal_id: u64,
+        title: String,
+        description: String,
+        voting_ends_at: i64,
+        gate_mint: Pubkey,
+        gate_kind: GateKind,
+        min_balance: u64,
+        weighting_mode: WeightingMode,
+        max_voters: u64,
+        mxe_program_id: Pubkey,
+        threshold_bps: u16,
+        privacy_level: u8,
+        links: Vec<ProposalLink>,
+        execution_delay: i64,
+        voting_end_slot: u64,
+        kind: ProposalKind,
+        excluded: Vec<Pubkey>,
+        gate_mints: Vec<Pubkey>,
+    ) -> Result<()> {
+        // Validate V2 fields
+        require!(
+            threshold_bps > 0 && threshold_bps <= 10_000,
+            VotingError::InvalidThreshold
+        );
+        require!(
+            excluded.len() <= MAX_EXCLUDED_VOTERS,
+            VotingError::TooManyExclusions
+        );
+        require_valid_gate_mints(gate_kind, &gate_mints)?;
+        require!(privacy_level <= 2, VotingError::InvalidPrivacyLevel);
+        require!(execution_delay >= 0, VotingError::InvalidExecutionDelay);
+        require!(
+            kind == ProposalKind::Binding || execution_delay == 0,
+            VotingError::InvalidExecutionDelay
+        );
+        require_not_denied(
+            &ctx.accounts.dao_config,
+            &ctx.accounts.denylist_entry,
+            ctx.accounts.authority.key(),
+            ctx.accounts.proposal.key(),
+            ScreenedAction::CreateProposal,
+        )?;
+        require_cpi_proposer(
+            &ctx.accounts.dao_config,
+            &ctx.accounts.authority.key(),
+            &ctx.accounts.caller_program,
+        )?;
+        require_proposer_participation(&ctx.accounts.dao_config, &ctx.accounts.voter_profile)?;
+        let rights = resolve_tier(
+            &ctx.accounts.dao_config,
+            &ctx.accounts.authority.key(),
+            &ctx.accounts.tier_escrow,
+        )?;
+        require!(
+            rights & TIER_RIGHT_CREATE_PROPOSAL != 0,
+            VotingError::MissingTierRight
+        );
+        let rate_limited = ctx.accounts.dao_config.tokens_per_active_proposal > 0;
+        if rate_limited {
+            claim_proposal_slot(ctx.accounts, ctx.bumps.proposal_counter)?;
+        }
+        let committee = committee_scope(&ctx.accounts.committee, &ctx.accounts.authority.key())?;
+        // Signaling proposals and fee-exempt tiers post no deposit
+        let deposit_amount = match kind {
+            ProposalKind::Binding if rights & TIER_RIGHT_FEE_EXEMPT == 0 => {
+                collect_proposal_deposit(ctx.accounts)?
+            }
+            _ => 0,
+        };
+        let clock = Clock::get()?;
+        require!(
+            voting_end_slot == 0 || voting_end_slot > clock.slot,
+            VotingError::InvalidEndSlot
+        );
+        let (voting_ends_at, voting_ends_slot) =
+            proposal_deadline(&clock, voting_ends_at, voting_end_slot);
+
+        // Initialize proposal state
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.id = proposal_id;
+        proposal.authority = ctx.accounts.authority.key();
+        proposal.title = title;
+        proposal.description = description;
+        proposal.voting_ends_at = voting_ends_at;
+        proposal.voting_ends_slot = voting_ends_slot;
+        proposal.slot_deadline = voting_end_slot > 0;
+        proposal.is_active = true;
+        proposal.is_revealed = false;
+        proposal.total_votes = 0;
+        proposal.gate_mint = gate_mint;
+        proposal.gate_kind = gate_kind;
+        proposal.min_balance = min_balance;
+        proposal.weighting_mode = weighting_mode;
+        proposal.total_weight = 0;
+        proposal.max_voters = max_voters;
+        proposal.ballots_cast = 0;
+        proposal.jury_size = 0;
+        proposal.ragequit_burned = 0;
+        proposal.tie_pending = false;
+        proposal.tie_break_offset = 0;
+        proposal.snapshot_root = [0u8; 32];
+        proposal.snapshot_total_weight = 0;
+        proposal.snapshot_slot = 0;
+        proposal.created_slot = Clock::get()?.slot;
+        proposal.oracle_root = [0u8; 32];
+        proposal.oracle_total_weight = 0;
+        proposal.revoting = false;
+        proposal.excluded = excluded.clone();
+        proposal.gate_mints = gate_mints;
+        proposal.delegate_fallback = false;
+        proposal.fallback_delegators = 0;
+        proposal.fallback_weight = 0;
+        proposal.reveal_number = 0;
+        proposal.awaiting_sponsorship = false;
+        proposal.voting_starts_at = Clock::get()?.unix_timestamp;
+        proposal.sponsorship_deadline = 0;
+        proposal.endorsement_weight = 0;
+        proposal.sponsorship_expired = false;
+        proposal.quorum_decay_period = 0;
+        proposal.quorum_floor_bps = 0;
+        proposal.threshold_base = ThresholdBase::NonAbstain;
+        proposal.randomness_account = Pubkey::default();
+        proposal.randomness_seed_slot = 0;
+        proposal.randomness = None;
+        proposal.last_ping_at = 0;
+        proposal.vote_records_root = [0u8; 32];
+        proposal.tally_lineage = [0u8; 32];
+        proposal.ballot_offsets_hash = [0u8; 32];
+        proposal.reveal_computation_offset = 0;
+        proposal.compacted_records = 0;
+        proposal.tally_shards = 1;
+        proposal.shards_merged = 0;
+        proposal.merging_shard = 0;
+        proposal.merge_offset = 0;
+        proposal.pending_computations = 0;
+        proposal.dropped_computations = 0;
+        require_approved_mxe(
+            &ctx.accounts.dao_config,
+            &mxe_program_id,
+            &ctx.accounts.cluster_account.key(),
+        )?;
+        proposal.mxe_program_id = mxe_program_id;
+        proposal.mxe_cluster = ctx.accounts.cluster_account.key();
+        // Signaling ties simply don't pass; only binding ones need a coin flip
+        proposal.random_tie_break =
+            kind == ProposalKind::Binding && ctx.accounts.dao_config.random_tie_break;
+        proposal.pending_mxe_program_id = Pubkey::default();
+        proposal.pending_mxe_cluster = Pubkey::default();
+        proposal.pending_mxe_offset = 0;
+        proposal.eligible_supply =
+            eligible_supply(gate_kind, &gate_mint, &ctx.accounts.gate_mint_account)?;
+        proposal.quorum = ctx.accounts.dao_config.default_quorum;
+        // Supply-relative quorum is fixed now, against the frozen supply
+        let quorum_bps = ctx.accounts.dao_config.quorum_bps;
+        if quorum_bps > 0
+            && proposal.eligible_supply > 0
+            && weighting_mode == WeightingMode::TokenWeighted
+        {
+            let supply_quorum =
+                (proposal.eligible_supply as u128 * quorum_bps as u128 / 10_000) as u64;
+            proposal.quorum = proposal.quorum.max(supply_quorum);
+        }
+        proposal.quorum_decay_period = ctx.accounts.dao_config.quorum_decay_period;
+        proposal.quorum_floor_bps = ctx.accounts.dao_config.quorum_floor_bps;
+        proposal.threshold_base = ctx.accounts.dao_config.threshold_base;
+        // Sponsorship is weighed against the same frozen supply
+        if ctx.accounts.dao_config.sponsorship_bps > 0
+            && gate_kind == GateKind::Token
+            && proposal.eligible_supply > 0
+        {
+            proposal.awaiting_sponsorship = true;
+            proposal.sponsorship_deadline =
+                proposal.voting_starts_at + ctx.accounts.dao_config.sponsorship_period;
+        }
+        proposal.threshold_bps = threshold_bps;
+        proposal.privacy_level = privacy_level;
+        proposal.passed = false;
+        validate_links(&links)?;
+        proposal.links = links;
+        proposal.deposit_amount = deposit_amount;
+        proposal.deposit_returned = false;
+        proposal.bond_in_sol = deposit_amount > 0 && ctx.accounts.dao_config.deposit_in_sol;
+        proposal.holds_rate_slot = rate_limited;
+        proposal.execution_delay = execution_delay;
+        proposal.kind = kind;
+        proposal.executed = false;
+        proposal.cancelled = false;
+        proposal.flagged = false;
+        proposal.executed_by = Pubkey::default();
+        proposal.executed_slot = 0;
+        proposal.executed_at = 0;
+        proposal.committee = committee;
+        proposal.revealed_at = 0;
+        proposal.attestations = 0;
+        proposal.attestation_disputes = 0;
+        proposal.moderation_cases = 0;
+        proposal.finalized_at = 0;
+        proposal.bump = ctx.bumps.proposal;
+
+        // Queue computation to initialize encrypted tally
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.authority.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = Clock::get()?.slot as u64;
+        let args = ArgumentList {
+            args: vec![],
+            byte_arrays: vec![],
+            plaintext_numbers: vec![],
+            values_128_bit: vec![],
+            accounts: vec![],
+        };
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(INIT_TALLY_COMP),
+            None,
+            args,
+            proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        emit!(ProposalCreated {
+            proposal_id,
+            authority: ctx.accounts.authority.key(),
+            voting_ends_at,
+            voting_end_slot,
+            kind,
+            excluded,
+        });
+
+        Ok(())
+    }
+
+    /// Callback from Arcium after init_tally completes
+    pub fn init_tally_callback(
+        ctx: Context<InitTallyCallback>,
+        encrypted_tally: [u8; 128], // Encrypted VoteTally
+        nonce: [u8; 16],
+    ) -> Result<()> {
+        let tally = &mut ctx.accounts.tally;
+        tally.proposal = ctx.accounts.proposal.key();
+        tally.shard = 0;
+        tally.ballot_version = BALLOT_VERSION;
+        tally.encrypted_data = encrypted_tally;
+        tally.nonce = nonce;
+        tally.bump = ctx.bumps.tally;
+        extend_tally_lineage(&mut ctx.accounts.proposal, tally);
+
+        Ok(())
+    }
+
+    /// Cast an encrypted vote. On token-weighted and quadratic proposals
+    /// `encrypted_weight` is the `Enc<Shared, u64>` amount of the gate balance
+    /// the voter commits (capped at the full balance in MPC); other proposals
+    /// ignore it.
+    pub fn cast_vote(
+        ctx: Context<CastVote>,
+        encrypted_choice: [u8; 32],
+        encrypted_weight: [u8; 32],
+        nonce: [u8; 16],
+        voter_pubkey: [u8; 32],
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let voter = ctx.accounts.voter.key();
+
+        let clock = Clock::get()?;
+        let (balance, weight) = vet_ballot(
+            &BallotAccounts {
+                proposal,
+                dao_config: &ctx.accounts.dao_config,
+                denylist_entry: &ctx.accounts.denylist_entry,
+                tally: &ctx.accounts.tally,
+                cluster_account: &ctx.accounts.cluster_account,
+                jury_roster: &ctx.accounts.jury_roster,
+                voter_token_account: &ctx.accounts.voter_token_account,
+                voter_stake_account: &ctx.accounts.voter_stake_account,
+                stake_pool: &ctx.accounts.stake_pool,
+                vesting_position: &ctx.accounts.vesting_position,
+                voter_escrow: &ctx.accounts.voter_escrow,
+                reputation: &ctx.accounts.reputation,
+            },
+            voter,
+            ctx.remaining_accounts,
+            &clock,
+            false,
+        )?;
+
+        // Record that this voter has voted (prevents double-voting)
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.voted_at = clock.unix_timestamp;
+        vote_record.encrypted_choice = encrypted_choice;
+        vote_record.nonce = nonce;
+        vote_record.voter_pubkey = voter_pubkey;
+        vote_record.no_vote_proven = false;
+        vote_record.ballot_version = ctx.accounts.tally.ballot_version;
+        vote_record.gate_balance = balance;
+        vote_record.bump = ctx.bumps.vote_record;
+
+        // Queue the vote computation
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.voter.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = Clock::get()?.slot;
+        ctx.accounts.vote_record.computation_offset = computation_offset;
+        ctx.accounts.vote_record.ballot_in_flight = proposal.revoting;
+        let (circuit, args) = ballot_circuit(
+            proposal,
+            encrypted_choice,
+            encrypted_weight,
+            ctx.accounts.tally.encrypted_data,
+            weight,
+        );
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(circuit),
+            None,
+            args,
+            proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        ctx.accounts.proposal.ballots_cast += 1;
+        ctx.accounts.proposal.pending_computations += 1;
+
+        emit!(VoteCast {
+            proposal: ctx.accounts.proposal.key(),
+            voter: ctx.accounts.voter.key(),
+        });
+        emit_sybil_signals(
+            ctx.accounts.proposal.key(),
+            ctx.accounts.voter.key(),
+            &mut ctx.accounts.wallet_profile,
+            ctx.accounts.voter.key(),
+            ctx.bumps.wallet_profile,
+            &clock,
+        );
+
+        Ok(())
+    }
+
+    /// Gasless `cast_vote`: a relayer submits the voter's signed
+    /// `ballot_message` (verified by a preceding Ed25519 instruction) and pays
+    /// the vote record's rent. The ballot expires at `valid_until`, capped by
+    /// `DaoConfig.max_ballot_validity`, and must carry the voter's next
+    /// `BallotSequence` number, so a relayer can neither hold a ballot back
+    /// past its expiry nor submit a voter's ballots out of order. Only
+    /// relayers bonded through `register_relayer` may submit.
+    pub fn cast_vote_signed(
+        ctx: Context<CastVoteSigned>,
+        encrypted_choice: [u8; 32],
+        encrypted_weight: [u8; 32],
+        nonce: [u8; 16],
+        voter_pubkey: [u8; 32],
+        valid_until: i64,
+        sequence: u64,
+    ) -> Result<()> {
+        let voter = ctx.accounts.voter.key();
+        let proposal = &ctx.accounts.proposal;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now <= valid_until, VotingError::SignatureExpired);
+        let max_validity = ctx.accounts.dao_config.max_ballot_validity;
+        require!(
+            max_validity == 0 || valid_until - now <= max_validity,
+            VotingError::BallotValidityTooLong
+        );
+        require!(
+            sequence == ctx.accounts.ballot_sequence.next,
+            VotingError::BallotOutOfSequence
+        );
+        require!(
+            ctx.accounts.relayer_registration.unbonding_at == 0,
+            VotingError::RelayerNotRegistered
+        );
+        let ballot_accounts = BallotAccounts {
+            proposal,
+            dao_config: &ctx.accounts.dao_config,
+            denylist_entry: &ctx.accounts.denylist_entry,
+            tally: &ctx.accounts.tally,
+            cluster_account: &ctx.accounts.cluster_account,
+            jury_roster: &ctx.accounts.jury_roster,
+            voter_token_account: &ctx.accounts.voter_token_account,
+            voter_stake_account: &ctx.accounts.voter_stake_account,
+            stake_pool: &ctx.accounts.stake_pool,
+            vesting_position: &ctx.accounts.vesting_position,
+            voter_escrow: &ctx.accounts.voter_escrow,
+            reputation: &ctx.accounts.reputation,
+        };
+        let ballot = ballot_message(
+            &proposal.key(),
+            &voter,
+            &encrypted_choice,
+            &encrypted_weight,
+            &nonce,
+            &voter_pubkey,
+            valid_until,
+            sequence,
+            &ballot_weight_accounts(&ballot_accounts),
+        );
+        verify_ed25519_ix(&ctx.accounts.instructions, &voter, &ballot)?;
+
+        let clock = Clock::get()?;
+        let (balance, weight) = vet_ballot(
+            &ballot_accounts,
+            voter,
+            ctx.remaining_accounts,
+            &clock,
+            false,
+        )?;
+
+        // Record that this voter has voted (prevents double-voting)
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.voter = voter;
+        vote_record.voted_at = clock.unix_timestamp;
+        vote_record.encrypted_choice = encrypted_choice;
+        vote_record.nonce = nonce;
+        vote_record.voter_pubkey = voter_pubkey;
+        vote_record.no_vote_proven = false;
+        vote_record.ballot_version = ctx.accounts.tally.ballot_version;
+        vote_record.relayer = ctx.accounts.relayer.key();
+        vote_record.ballot_hash = hashv(&[&ballot]).to_bytes();
+        vote_record.gate_balance = balance;
+        vote_record.bump = ctx.bumps.vote_record;
+
+        // Queue the vote computation
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.relayer.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = Clock::get()?.slot;
+        ctx.accounts.vote_record.computation_offset = computation_offset;
+        ctx.accounts.vote_record.ballot_in_flight = proposal.revoting;
+        let (circuit, args) = ballot_circuit(
+            proposal,
+            encrypted_choice,
+            encrypted_weight,
+            ctx.accounts.tally.encrypted_data,
+            weight,
+        );
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(circuit),
+            None,
+            args,
+            proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        ctx.accounts.proposal.ballots_cast += 1;
+        ctx.accounts.proposal.pending_computations += 1;
+        let ballot_sequence = &mut ctx.accounts.ballot_sequence;
+        ballot_sequence.voter = voter;
+        ballot_sequence.next = sequence + 1;
+        ballot_sequence.bump = ctx.bumps.ballot_sequence;
+        ctx.accounts.relayer_registration.ballots_relayed += 1;
+
+        emit!(VoteCast {
+            proposal: ctx.accounts.proposal.key(),
+            voter,
+        });
+        emit_sybil_signals(
+            ctx.accounts.proposal.key(),
+            voter,
+            &mut ctx.accounts.wallet_profile,
+            ctx.accounts.relayer.key(),
+            ctx.bumps.wallet_profile,
+            &clock,
+        );
+
+        Ok(())
+    }
+
+    /// Bond `DaoConfig.relayer_bond` lamports and register as a relayer
+    /// allowed to submit `cast_vote_signed` ballots.
+    pub fn register_relayer(ctx: Context<RegisterRelayer>) -> Result<()> {
+        let amount = ctx.accounts.dao_config.relayer_bond;
+        // The bond PDA holds no data, so it must stay rent-exempt on its own
+        require!(
+            amount >= Rent::get()?.minimum_balance(0),
+            VotingError::BondBelowRentExemption
+        );
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.relayer_bond.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let relayer = &mut ctx.accounts.relayer_registration;
+        relayer.authority = ctx.accounts.authority.key();
+        relayer.bond = amount;
+        relayer.registered_at = Clock::get()?.unix_timestamp;
+        relayer.ballots_relayed = 0;
+        relayer.unbonding_at = 0;
+        relayer.bump = ctx.bumps.relayer_registration;
+
+        emit!(RelayerRegistered {
+            relayer: relayer.authority,
+            bond: amount,
+        });
+        Ok(())
+    }
+
+    /// Stop relaying and start the `RELAYER_UNBONDING_PERIOD`, during which
+    /// the bond can still be slashed for ballots already acknowledged.
+    pub fn begin_relayer_unbonding(ctx: Context<RelayerUnbonding>) -> Result<()> {
+        let relayer = &mut ctx.accounts.relayer_registration;
+        require!(relayer.unbonding_at == 0, VotingError::RelayerNotRegistered);
+        relayer.unbonding_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Return the bond and close the registration once unbonding completes.
+    pub fn withdraw_relayer_bond(ctx: Context<WithdrawRelayerBond>) -> Result<()> {
+        let relayer = &ctx.accounts.relayer_registration;
+        require!(
+            relayer.unbonding_at > 0
+                && Clock::get()?.unix_timestamp >= relayer.unbonding_at + RELAYER_UNBONDING_PERIOD,
+            VotingError::RelayerUnbonding
+        );
+        let authority = relayer.authority;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            RELAYER_BOND_SEED,
+            authority.as_ref(),
+            &[ctx.bumps.relayer_bond],
+        ]];
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.relayer_bond.to_account_info(),
+                    to: ctx.accounts.authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            relayer.bond,
+        )?;
+        Ok(())
+    }
+
+    /// Slash a relayer's bond to the challenger using the relayer's signed
+    /// `relay_receipt_message` for a ballot (verified by a preceding Ed25519
+    /// instruction). The relayer is at fault if the voter's `VoteRecord` was
+    /// submitted by it with a different ballot than the one it acknowledged
+    /// (altered or substituted): only the relayer chooses what it submits.
+    ///
+    /// An acknowledged ballot that was never submitted is not slashable: the
+    /// voter alone can make it unsubmittable (moving the gate tokens, reusing
+    /// the sequence), so withholding can't be attributed to the relayer.
+    /// `weight_accounts` are the ones the voter signed (`ballot_weight_accounts`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn challenge_relayer(
+        ctx: Context<ChallengeRelayer>,
+        encrypted_choice: [u8; 32],
+        encrypted_weight: [u8; 32],
+        nonce: [u8; 16],
+        voter_pubkey: [u8; 32],
+        valid_until: i64,
+        sequence: u64,
+        weight_accounts: [Pubkey; BALLOT_WEIGHT_ACCOUNTS],
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let voter = ctx.accounts.voter.key();
+        let authority = ctx.accounts.relayer_registration.authority;
+        let ballot_hash = hashv(&[&ballot_message(
+            &proposal.key(),
+            &voter,
+            &encrypted_choice,
+            &encrypted_weight,
+            &nonce,
+            &voter_pubkey,
+            valid_until,
+            sequence,
+            &weight_accounts,
+        )])
+        .to_bytes();
+        verify_ed25519_ix(
+            &ctx.accounts.instructions,
+            &authority,
+            &relay_receipt_message(&ballot_hash),
+        )?;
+
+        let vote_record_info = ctx.accounts.vote_record.to_account_info();
+        require!(
+            !vote_record_info.data_is_empty(),
+            VotingError::RelayerNotAtFault
+        );
+        let data = vote_record_info.try_borrow_data()?;
+        let record = VoteRecord::try_deserialize(&mut &data[..])?;
+        require!(
+            record.relayer == authority && record.ballot_hash != ballot_hash,
+            VotingError::RelayerNotAtFault
+        );
+        drop(data);
+
+        let bond = ctx.accounts.relayer_registration.bond;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            RELAYER_BOND_SEED,
+            authority.as_ref(),
+            &[ctx.bumps.relayer_bond],
+        ]];
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.relayer_bond.to_account_info(),
+                    to: ctx.accounts.challenger.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            bond,
+        )?;
+
+        emit!(RelayerSlashed {
+            relayer: authority,
+            challenger: ctx.accounts.challenger.key(),
+            proposal: proposal.key(),
+            voter,
+            bond,
+        });
+        Ok(())
+    }
+
+    /// Cast a ballot carrying the weight of every delegator pointing at the
+    /// voter, accumulated by `vote_with_delegations` in a single MPC round.
+    ///
+    /// Token gates only. `remaining_accounts` are `[delegation,
+    /// delegator_token_account, delegator_vote_record,
+    /// delegator_denylist_entry, delegator_vesting_position]` groups, with
+    /// this program's id standing in for a delegator without a vesting
+    /// position (weighed as in `with_vesting_power`); denied delegators fail
+    /// the ballot;
+    /// a `VoteRecord` holding this ballot is created for each delegator, so a
+    /// delegator can neither vote again directly nor be counted twice, and can
+    /// still ragequit off the shared ciphertext. The delegate's own gate
+    /// balance is re-checked at vote time (`min_balance`, and
+    /// `delegate_min_balance` when the gate is the delegate mint), so power
+    /// can't be parked on a wallet that emptied itself after delegation.
+    pub fn cast_delegated_vote<'info>(
+        ctx: Context<'_, '_, '_, 'info, CastVote<'info>>,
+        encrypted_choice: [u8; 32],
+        nonce: [u8; 16],
+        voter_pubkey: [u8; 32],
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let voter = ctx.accounts.voter.key();
+
+        let clock = Clock::get()?;
+        vet_ballot_window(
+            &BallotAccounts {
+                proposal,
+                dao_config: &ctx.accounts.dao_config,
+                denylist_entry: &ctx.accounts.denylist_entry,
+                tally: &ctx.accounts.tally,
+                cluster_account: &ctx.accounts.cluster_account,
+                jury_roster: &ctx.accounts.jury_roster,
+                voter_token_account: &ctx.accounts.voter_token_account,
+                voter_stake_account: &ctx.accounts.voter_stake_account,
+                stake_pool: &ctx.accounts.stake_pool,
+                vesting_position: &ctx.accounts.vesting_position,
+                voter_escrow: &ctx.accounts.voter_escrow,
+                reputation: &ctx.accounts.reputation,
+            },
+            voter,
+            &clock,
+            false,
+        )?;
+        require!(!proposal.revoting, VotingError::RevotingUnsupported);
+        // Juries vote in person
+        require_vote(
+            proposal.jury_size == 0,
+            RejectReason::NotOnJury,
+            VotingError::NotOnJury,
+            proposal.key(),
+            voter,
+        )?;
+        require_vote(
+            proposal.gate_kind == GateKind::Token,
+            RejectReason::GateMismatch,
+            VotingError::DelegationRequiresToken,
+            proposal.key(),
+            voter,
+        )?;
+        // Delegators' balances can't be checkpointed in the same batch
+        require!(
+            proposal.snapshot_slot == 0,
+            VotingError::SnapshotDelegationUnsupported
+        );
+        require_vote(
+            proposal.snapshot_root == [0u8; 32] && proposal.oracle_root == [0u8; 32],
+            RejectReason::SnapshotElectorate,
+            VotingError::SnapshotElectorate,
+            proposal.key(),
+            voter,
+        )?;
+
+        let own_power = gate_balance(
+            proposal,
+            &voter,
+            &ctx.accounts.voter_token_account,
+            &ctx.accounts.voter_stake_account,
+            &ctx.accounts.stake_pool,
+            &[],
+            &ctx.accounts.dao_config.mint_multipliers,
+        )
+        .map_err(vote_rejected(
+            RejectReason::GateMismatch,
+            proposal.key(),
+            voter,
+        ))?;
+        let own_power = settled_power(
+            own_power,
+            &ctx.accounts.dao_config,
+            proposal,
+            &voter,
+            &ctx.accounts.voter_escrow,
+        )?;
+        let (own_balance, own_weighted) = with_vesting_power(
+            own_power,
+            proposal,
+            &voter,
+            ctx.accounts
+                .voter_token_account
+                .as_ref()
+                .map(|account| account.key()),
+            ctx.accounts.vesting_position.as_deref(),
+            ctx.accounts.dao_config.vesting_boost_bps,
+        )?;
+        require_vote(
+            own_balance >= proposal.min_balance,
+            RejectReason::InsufficientBalance,
+            VotingError::InsufficientTokenBalance,
+            proposal.key(),
+            voter,
+        )?;
+        let own_weight = vote_weight(proposal, own_weighted, &ctx.accounts.reputation)?;
+
+        let tally = &ctx.accounts.tally;
+        require!(
+            tally.proposal == proposal.key()
+                && tally.shard == tally_shard_for(&voter, proposal.tally_shards),
+            VotingError::WrongTallyShard
+        );
+        require_ballot_version(tally.ballot_version)?;
+
+        let ballot = VoteRecord {
+            proposal: proposal.key(),
+            voter,
+            voted_at: clock.unix_timestamp,
+            encrypted_choice,
+            nonce,
+            voter_pubkey,
+            no_vote_proven: false,
+            ballot_version: tally.ballot_version,
+            delegators: 0,
+            relayer: Pubkey::default(),
+            ballot_hash: [0u8; 32],
+            receipt: [0u8; 64],
+            receipt_nonce: [0u8; 16],
+            counted_ballot: [0u8; 96],
+            counted_nonce: [0u8; 16],
+            ballot_in_flight: false,
+            revotes: 0,
+            computation_offset: 0,
+            folded_into: Pubkey::default(),
+            gate_balance: own_balance,
+            ragequit_burned: 0,
+            bump: 0,
+        };
+
+        let groups = ctx.remaining_accounts.chunks_exact(5);
+        require!(
+            groups.remainder().is_empty() && groups.len() <= MAX_DELEGATORS_PER_VOTE,
+            VotingError::InvalidDelegationBatch
+        );
+        let groups_len = groups.len();
+        let config = &ctx.accounts.dao_config;
+        if config.require_delegate_eligibility
+            && groups_len > 0
+            && proposal.gate_mint == config.delegate_gate_mint
+        {
+            require!(
+                own_balance >= config.delegate_min_balance,
+                VotingError::DelegateNotEligible
+            );
+        }
+        let payer = ctx.accounts.voter.to_account_info();
+        let system_program_info = ctx.accounts.system_program.to_account_info();
+        let mut delegated_weight: u64 = 0;
+        for accounts in groups {
+            require!(
+                *accounts[0].owner == crate::ID,
+                VotingError::InvalidDelegation
+            );
+            let delegation = Delegation::try_deserialize(&mut &accounts[0].try_borrow_data()?[..])?;
+            let (expected, _) = Pubkey::find_program_address(
+                &[DELEGATION_SEED, delegation.delegator.as_ref()],
+                ctx.program_id,
+            );
+            require!(
+                accounts[0].key() == expected && delegation.delegate == voter,
+                VotingError::InvalidDelegation
+            );
+            require!(delegation.accepted, VotingError::DelegationNotAccepted);
+            require!(
+                !proposal.excluded.contains(&delegation.delegator),
+                VotingError::VoterExcluded
+            );
+            require_delegator_not_denied(
+                &ctx.accounts.dao_config,
+                &accounts[3],
+                delegation.delegator,
+                proposal.key(),
+            )?;
+
+            require!(
+                *accounts[1].owner == token::ID,
+                VotingError::InvalidTokenAccount
+            );
+            let token_account =
+                TokenAccount::try_deserialize(&mut &accounts[1].try_borrow_data()?[..])?;
+            require!(
+                token_account.owner == delegation.delegator,
+                VotingError::InvalidTokenAccount
+            );
+            require!(
+                token_account.mint == proposal.gate_mint,
+                VotingError::InvalidTokenMint
+            );
+            let (balance, weighted) = with_vesting_power(
+                (token_account.amount, token_account.amount),
+                proposal,
+                &delegation.delegator,
+                Some(accounts[1].key()),
+                (accounts[4].key() != crate::ID).then_some(&accounts[4]),
+                config.vesting_boost_bps,
+            )?;
+            require!(
+                balance >= proposal.min_balance,
+                VotingError::InsufficientTokenBalance
+            );
+            let weight = vote_weight(proposal, weighted, &None)?;
+
+            create_vote_record(
+                &accounts[2],
+                &payer,
+                &system_program_info,
+                &VoteRecord {
+                    voter: delegation.delegator,
+                    folded_into: voter,
+                    gate_balance: balance,
+                    ..ballot.clone()
+                },
+            )?;
+
+            delegated_weight = delegated_weight
+                .checked_add(weight)
+                .ok_or(VotingError::ArithmeticOverflow)?;
+        }
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.set_inner(VoteRecord {
+            delegators: groups_len as u8,
+            bump: ctx.bumps.vote_record,
+            ..ballot
+        });
+
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.voter.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = Clock::get()?.slot;
+        ctx.accounts.vote_record.computation_offset = computation_offset;
+        let args = build_args_for_delegated_vote(
+            encrypted_choice,
+            own_weight,
+            delegated_weight,
+            ctx.accounts.tally.encrypted_data,
+        );
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(DELEGATED_VOTE_COMP),
+            None,
+            args,
+            ctx.accounts.proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        ctx.accounts.proposal.ballots_cast += 1;
+        ctx.accounts.proposal.pending_computations += 1;
+
+        emit!(DelegatedVoteCast {
+            proposal: ctx.accounts.proposal.key(),
+            voter,
+            delegators: groups_len as u8,
+            delegated_weight,
+        });
+        emit_sybil_signals(
+            ctx.accounts.proposal.key(),
+            voter,
+            &mut ctx.accounts.wallet_profile,
+            ctx.accounts.voter.key(),
+            ctx.bumps.wallet_profile,
+            &clock,
+        );
+
+        Ok(())
+    }
+
+    /// Pre-reveal crank for proposals with `delegate_fallback`: add the weight
+    /// of accepted delegators who never voted to their delegate's ballot,
+    /// re-tallying the delegate's stored ciphertext with
+    /// `vote_with_delegations`. Runs within `DELEGATE_FALLBACK_WINDOW` after
+    /// the deadline. Permissionless; the cranker funds the delegators'
+    /// `VoteRecord`s, which stop them being counted twice.
+    ///
+    /// Delegators are weighed at the proposal's `snapshot_slot` from their
+    /// `VoterEscrow`, never at a live balance: tokens that voted elsewhere
+    /// can't be moved to a silent delegator and counted again. Only
+    /// delegations made before the deadline follow the delegate.
+    ///
+    /// `remaining_accounts` are `[delegation, delegator_escrow,
+    /// delegator_vote_record, delegator_denylist_entry]` groups.
+    pub fn apply_delegate_fallback<'info>(
+        ctx: Context<'_, '_, '_, 'info, ApplyDelegateFallback<'info>>,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(
+            proposal.delegate_fallback,
+            VotingError::DelegateFallbackUnsupported
+        );
+        require!(
+            proposal.gate_kind == GateKind::Token
+                && proposal.snapshot_slot != 0
+                && !proposal.revoting,
+            VotingError::DelegateFallbackUnsupported
+        );
+        require!(
+            proposal.snapshot_root == [0u8; 32] && proposal.oracle_root == [0u8; 32],
+            VotingError::SnapshotElectorate
+        );
+        require!(!proposal.is_revealed, VotingError::AlreadyRevealed);
+        let clock = Clock::get()?;
+        require!(voting_closed(proposal, &clock), VotingError::VotingNotEnded);
+        require!(
+            !past_deadline_by(proposal, &clock, DELEGATE_FALLBACK_WINDOW),
+            VotingError::FallbackWindowClosed
+        );
+        require_proposal_mxe(proposal, &ctx.accounts.cluster_account)?;
+
+        let ballot = ctx.accounts.delegate_record.clone().into_inner();
+        let delegate = ballot.voter;
+        require!(
+            ballot.folded_into == Pubkey::default(),
+            VotingError::InvalidDelegation
+        );
+        let tally = &ctx.accounts.tally;
+        require!(
+            tally.proposal == proposal.key()
+                && tally.shard == tally_shard_for(&delegate, proposal.tally_shards)
+                && tally.ballot_version == ballot.ballot_version,
+            VotingError::WrongTallyShard
+        );
+        require!(
+            tally.shard == 0 || proposal.shards_merged & (1 << tally.shard) == 0,
+            VotingError::ShardAlreadyMerged
+        );
+        require_ballot_version(tally.ballot_version)?;
+
+        let groups = ctx.remaining_accounts.chunks_exact(4);
+        require!(
+            groups.remainder().is_empty()
+                && groups.len() > 0
+                && groups.len() <= MAX_DELEGATORS_PER_VOTE,
+            VotingError::InvalidDelegationBatch
+        );
+        let delegators = groups.len() as u8;
+        let payer = ctx.accounts.cranker.to_account_info();
+        let system_program_info = ctx.accounts.system_program.to_account_info();
+        let mut fallback_weight: u64 = 0;
+        for accounts in groups {
+            require!(
+                *accounts[0].owner == crate::ID,
+                VotingError::InvalidDelegation
+            );
+            let delegation = Delegation::try_deserialize(&mut &accounts[0].try_borrow_data()?[..])?;
+            let (expected, _) = Pubkey::find_program_address(
+                &[DELEGATION_SEED, delegation.delegator.as_ref()],
+                ctx.program_id,
+            );
+            require!(
+                accounts[0].key() == expected
+                    && delegation.delegate == delegate
+                    && delegation.created_at <= proposal.voting_ends_at,
+                VotingError::InvalidDelegation
+            );
+            require!(delegation.accepted, VotingError::DelegationNotAccepted);
+            require!(
+                !proposal.excluded.contains(&delegation.delegator),
+                VotingError::VoterExcluded
+            );
+            require_delegator_not_denied(
+                &ctx.accounts.dao_config,
+                &accounts[3],
+                delegation.delegator,
+                proposal.key(),
+            )?;
+
+            require!(
+                *accounts[1].owner == crate::ID,
+                VotingError::InvalidCheckpoint
+            );
+            let escrow = VoterEscrow::try_deserialize(&mut &accounts[1].try_borrow_data()?[..])?;
+            require!(
+                escrow.owner == delegation.delegator && escrow.mint == proposal.gate_mint,
+                VotingError::InvalidCheckpoint
+            );
+            let balance = escrow_balance_at(&escrow, proposal.snapshot_slot)?;
+            require!(
+                balance >= proposal.min_balance,
+                VotingError::InsufficientTokenBalance
+            );
+            let weight = vote_weight(proposal, balance, &None)?;
+
+            // Fails if the delegator cast a ballot of its own
+            create_vote_record(
+                &accounts[2],
+                &payer,
+                &system_program_info,
+                &VoteRecord {
+                    voter: delegation.delegator,
+                    voted_at: clock.unix_timestamp,
+                    delegators: 0,
+                    relayer: Pubkey::default(),
+                    ballot_hash: [0u8; 32],
+                    receipt: [0u8; 64],
+                    receipt_nonce: [0u8; 16],
+                    no_vote_proven: false,
+                    computation_offset: 0,
+                    folded_into: delegate,
+                    gate_balance: balance,
+                    ragequit_burned: 0,
+                    ..ballot.clone()
+                },
+            )?;
+
+            fallback_weight = fallback_weight
+                .checked_add(weight)
+                .ok_or(VotingError::ArithmeticOverflow)?;
+        }
+
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.cranker.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = Clock::get()?.slot;
+        // The delegate's own weight is already tallied
+        let args = build_args_for_delegated_vote(
+            ballot.encrypted_choice,
+            0,
+            fallback_weight,
+            ctx.accounts.tally.encrypted_data,
+        );
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(DELEGATED_VOTE_COMP),
+            None,
+            args,
+            ctx.accounts.proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.pending_computations += 1;
+        proposal.fallback_delegators += delegators as u32;
+        proposal.fallback_weight = proposal
+            .fallback_weight
+            .checked_add(fallback_weight)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+
+        emit!(DelegateFallbackApplied {
+            proposal: proposal.key(),
+            delegate,
+            delegators,
+            weight: fallback_weight,
+        });
+
+        Ok(())
+    }
+
+    /// Cast a ballot whose weight comes from the proposal's eligibility
+    /// snapshot. The Merkle proof of `(voter, weight)` is checked inside the
+    /// MPC (`cast_vote_with_eligibility`), so neither the weight nor the
+    /// eligibility can be forged and the weight never appears on-chain. A
+    /// ballot with a bad proof counts for nothing and still uses up the vote.
+    pub fn cast_eligible_vote(
+        ctx: Context<CastVote>,
+        encrypted_choice: [u8; 32],
+        nonce: [u8; 16],
+        voter_pubkey: [u8; 32],
+        encrypted_weight: [u8; 32],
+        leaf_index: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let voter = ctx.accounts.voter.key();
+
+        let clock = Clock::get()?;
+        vet_ballot_window(
+            &BallotAccounts {
+                proposal,
+                dao_config: &ctx.accounts.dao_config,
+                denylist_entry: &ctx.accounts.denylist_entry,
+                tally: &ctx.accounts.tally,
+                cluster_account: &ctx.accounts.cluster_account,
+                jury_roster: &ctx.accounts.jury_roster,
+                voter_token_account: &ctx.accounts.voter_token_account,
+                voter_stake_account: &ctx.accounts.voter_stake_account,
+                stake_pool: &ctx.accounts.stake_pool,
+                vesting_position: &ctx.accounts.vesting_position,
+                voter_escrow: &ctx.accounts.voter_escrow,
+                reputation: &ctx.accounts.reputation,
+            },
+            voter,
+            &clock,
+            false,
+        )?;
+        require_vote(
+            proposal.snapshot_root != [0u8; 32],
+            RejectReason::NotInSnapshot,
+            VotingError::MissingSnapshotRoot,
+            proposal.key(),
+            voter,
+        )?;
+        require!(!proposal.revoting, VotingError::RevotingUnsupported);
+        require_vote(
+            proof.len() == SNAPSHOT_DEPTH,
+            RejectReason::NotInSnapshot,
+            VotingError::InvalidMerkleProof,
+            proposal.key(),
+            voter,
+        )?;
+
+        let tally = &ctx.accounts.tally;
+        require!(
+            tally.proposal == proposal.key()
+                && tally.shard == tally_shard_for(&voter, proposal.tally_shards),
+            VotingError::WrongTallyShard
+        );
+        require_ballot_version(tally.ballot_version)?;
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.voter = voter;
+        vote_record.voted_at = clock.unix_timestamp;
+        vote_record.encrypted_choice = encrypted_choice;
+        vote_record.nonce = nonce;
+        vote_record.voter_pubkey = voter_pubkey;
+        vote_record.no_vote_proven = false;
+        vote_record.ballot_version = tally.ballot_version;
+        vote_record.bump = ctx.bumps.vote_record;
+
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.voter.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = Clock::get()?.slot;
+        ctx.accounts.vote_record.computation_offset = computation_offset;
+        let args = build_args_for_eligible_vote(
+            encrypted_choice,
+            encrypted_weight,
+            ctx.accounts.tally.encrypted_data,
+            &voter,
+            leaf_index,
+            &proof,
+            ctx.accounts.proposal.snapshot_root,
+        );
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(ELIGIBLE_VOTE_COMP),
+            None,
+            args,
+            ctx.accounts.proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        ctx.accounts.proposal.ballots_cast += 1;
+        ctx.accounts.proposal.pending_computations += 1;
+
+        emit!(VoteCast {
+            proposal: ctx.accounts.proposal.key(),
+            voter,
+        });
+        emit_sybil_signals(
+            ctx.accounts.proposal.key(),
+            voter,
+            &mut ctx.accounts.wallet_profile,
+            ctx.accounts.voter.key(),
+            ctx.bumps.wallet_profile,
+            &clock,
+        );
+
+        Ok(())
+    }
+
+    /// Cast a ballot weighted by the proposal's oracle snapshot. The proof of
+    /// `(voter, weight)` against `oracle_root` is checked on-chain, so the
+    /// electorate can hold assets the program cannot read (exchange
+    /// attestations, other chains); the weight is public, as in the snapshot.
+    pub fn cast_vote_with_proof(
+        ctx: Context<CastVote>,
+        encrypted_choice: [u8; 32],
+        nonce: [u8; 16],
+        voter_pubkey: [u8; 32],
+        weight: u64,
+        leaf_index: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let voter = ctx.accounts.voter.key();
+
+        let clock = Clock::get()?;
+        vet_ballot_window(
+            &BallotAccounts {
+                proposal,
+                dao_config: &ctx.accounts.dao_config,
+                denylist_entry: &ctx.accounts.denylist_entry,
+                tally: &ctx.accounts.tally,
+                cluster_account: &ctx.accounts.cluster_account,
+                jury_roster: &ctx.accounts.jury_roster,
+                voter_token_account: &ctx.accounts.voter_token_account,
+                voter_stake_account: &ctx.accounts.voter_stake_account,
+                stake_pool: &ctx.accounts.stake_pool,
+                vesting_position: &ctx.accounts.vesting_position,
+                voter_escrow: &ctx.accounts.voter_escrow,
+                reputation: &ctx.accounts.reputation,
+            },
+            voter,
+            &clock,
+            false,
+        )?;
+        require_vote(
+            proposal.oracle_root != [0u8; 32],
+            RejectReason::NotInSnapshot,
+            VotingError::MissingSnapshotRoot,
+            proposal.key(),
+            voter,
+        )?;
+        require!(!proposal.revoting, VotingError::RevotingUnsupported);
+        let leaf = hashv(&[voter.as_ref(), &weight.to_le_bytes()]).to_bytes();
+        require_vote(
+            weight > 0 && verify_merkle_proof(&proposal.oracle_root, leaf, leaf_index, &proof),
+            RejectReason::NotInSnapshot,
+            VotingError::InvalidMerkleProof,
+            proposal.key(),
+            voter,
+        )?;
+
+        let tally = &ctx.accounts.tally;
+        require!(
+            tally.proposal == proposal.key()
+                && tally.shard == tally_shard_for(&voter, proposal.tally_shards),
+            VotingError::WrongTallyShard
+        );
+        require_ballot_version(tally.ballot_version)?;
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.voter = voter;
+        vote_record.voted_at = clock.unix_timestamp;
+        vote_record.encrypted_choice = encrypted_choice;
+        vote_record.nonce = nonce;
+        vote_record.voter_pubkey = voter_pubkey;
+        vote_record.no_vote_proven = false;
+        vote_record.ballot_version = tally.ballot_version;
+        vote_record.gate_balance = weight;
+        vote_record.bump = ctx.bumps.vote_record;
+
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.voter.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = Clock::get()?.slot;
+        ctx.accounts.vote_record.computation_offset = computation_offset;
+        let args = build_args_for_weighted_vote(
+            encrypted_choice,
+            ctx.accounts.tally.encrypted_data,
+            weight,
+        );
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(WEIGHTED_VOTE_COMP),
+            None,
+            args,
+            ctx.accounts.proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        ctx.accounts.proposal.ballots_cast += 1;
+        ctx.accounts.proposal.pending_computations += 1;
+
+        emit!(VoteCast {
+            proposal: ctx.accounts.proposal.key(),
+            voter,
+        });
+        emit_sybil_signals(
+            ctx.accounts.proposal.key(),
+            voter,
+            &mut ctx.accounts.wallet_profile,
+            ctx.accounts.voter.key(),
+            ctx.bumps.wallet_profile,
+            &clock,
+        );
+
+        Ok(())
+    }
+
+    /// Re-vote on a re-voting proposal. The `SequencedBallot`
+    /// (`[choice, sequence]`, encrypted to the MXE like `cast_vote`) must
+    /// carry the voter's next recast number (`VoteRecord.revotes + 1`),
+    /// assigned here and checked inside `recast_vote`, so the latest ballot
+    /// always counts and no ballot can claim a sequence that outranks the
+    /// voter's later ones. The voter must still pass the proposal's screening
+    /// and gate; the first ballot's weight carries over. One recast is in
+    /// flight at a time.
+    pub fn recast_vote(
+        ctx: Context<RecastVote>,
+        encrypted_ballot: [[u8; 32]; 2],
+        nonce: [u8; 16],
+        voter_pubkey: [u8; 32],
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let voter = ctx.accounts.voter.key();
+        require!(proposal.revoting, VotingError::RevotingUnsupported);
+        let clock = Clock::get()?;
+        vet_ballot(
+            &BallotAccounts {
+                proposal,
+                dao_config: &ctx.accounts.dao_config,
+                denylist_entry: &ctx.accounts.denylist_entry,
+                tally: &ctx.accounts.tally,
+                cluster_account: &ctx.accounts.cluster_account,
+                jury_roster: &ctx.accounts.jury_roster,
+                voter_token_account: &ctx.accounts.voter_token_account,
+                voter_stake_account: &ctx.accounts.voter_stake_account,
+                stake_pool: &ctx.accounts.stake_pool,
+                vesting_position: &ctx.accounts.vesting_position,
+                voter_escrow: &ctx.accounts.voter_escrow,
+                reputation: &ctx.accounts.reputation,
+            },
+            voter,
+            ctx.remaining_accounts,
+            &clock,
+            true,
+        )?;
+        let vote_record = &mut ctx.accounts.vote_record;
+        require_vote(
+            !vote_record.ballot_in_flight,
+            RejectReason::BallotInFlight,
+            VotingError::BallotInFlight,
+            proposal.key(),
+            voter,
+        )?;
+        vote_record.ballot_in_flight = true;
+        vote_record.revotes = vote_record
+            .revotes
+            .checked_add(1)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+        vote_record.nonce = nonce;
+        vote_record.voter_pubkey = voter_pubkey;
+        let counted_ballot = vote_record.counted_ballot;
+        let revotes = vote_record.revotes;
+
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.voter.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = Clock::get()?.slot;
+        ctx.accounts.vote_record.computation_offset = computation_offset;
+        let args = build_args_for_recast(
+            ctx.accounts.tally.encrypted_data,
+            encrypted_ballot,
+            counted_ballot,
+            revotes as u64,
+        );
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(RECAST_VOTE_COMP),
+            None,
+            args,
+            ctx.accounts.proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        ctx.accounts.proposal.pending_computations += 1;
+
+        emit!(VoteRecast {
+            proposal: ctx.accounts.proposal.key(),
+            voter,
+            revotes,
+        });
+        Ok(())
+    }
+
+    /// Callback from Arcium after `cast_sequenced_vote` or `recast_vote`
+    /// completes. Stores the updated tally and the voter's MXE-held counted
+    /// ballot; only the first ballot adds to `total_votes`.
+    pub fn counted_ballot_callback(
+        ctx: Context<VoteCallback>,
+        computation_offset: u64,
+        new_encrypted_tally: [u8; 128],
+        nonce: [u8; 16],
+        counted_ballot: [u8; 96],
+        counted_nonce: [u8; 16],
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        settle_ballot(
+            &mut ctx.accounts.vote_record,
+            proposal,
+            &ctx.accounts.tally,
+            computation_offset,
+        )?;
+        require!(!proposal.is_revealed, VotingError::AlreadyRevealed);
+        require!(
+            !past_deadline_by(
+                proposal,
+                &Clock::get()?,
+                ctx.accounts.dao_config.callback_grace_period
+            ),
+            VotingError::CallbackWindowClosed
+        );
+        let shard = ctx.accounts.tally.shard;
+        require!(
+            shard == 0 || proposal.shards_merged & (1 << shard) == 0,
+            VotingError::ShardAlreadyMerged
+        );
+        require_ballot_version(ctx.accounts.tally.ballot_version)?;
+
+        let tally = &mut ctx.accounts.tally;
+        tally.encrypted_data = new_encrypted_tally;
+        tally.nonce = nonce;
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.counted_ballot = counted_ballot;
+        vote_record.counted_nonce = counted_nonce;
+        vote_record.ballot_in_flight = false;
+        let first = vote_record.revotes == 0;
+
+        let proposal = &mut ctx.accounts.proposal;
+        extend_tally_lineage(proposal, &ctx.accounts.tally);
+        extend_ballot_offsets(proposal, computation_offset);
+        if first {
+            proposal.total_votes += 1;
+        }
+        proposal.pending_computations = proposal.pending_computations.saturating_sub(1);
+
+        Ok(())
+    }
+
+    /// Callback for `apply_delegate_fallback`'s `vote_with_delegations`
+    /// computation. Accepted until the fallback window plus the callback
+    /// grace period has passed. The receipt is dropped: the delegate already
+    /// holds one for its own ballot.
+    pub fn delegate_fallback_callback(
+        ctx: Context<DelegateFallbackCallback>,
+        new_encrypted_tally: [u8; 128],
+        nonce: [u8; 16],
+        _receipt: [u8; 64],
+        _receipt_nonce: [u8; 16],
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(!proposal.is_revealed, VotingError::AlreadyRevealed);
+        require!(
+            !past_deadline_by(
+                proposal,
+                &Clock::get()?,
+                DELEGATE_FALLBACK_WINDOW + ctx.accounts.dao_config.callback_grace_period
+            ),
+            VotingError::CallbackWindowClosed
+        );
+        let shard = ctx.accounts.tally.shard;
+        require!(
+            shard == 0 || proposal.shards_merged & (1 << shard) == 0,
+            VotingError::ShardAlreadyMerged
+        );
+        require_ballot_version(ctx.accounts.tally.ballot_version)?;
+
+        let tally = &mut ctx.accounts.tally;
+        tally.encrypted_data = new_encrypted_tally;
+        tally.nonce = nonce;
+
+        let proposal = &mut ctx.accounts.proposal;
+        extend_tally_lineage(proposal, &ctx.accounts.tally);
+        proposal.pending_computations = proposal.pending_computations.saturating_sub(1);
+
+        Ok(())
+    }
+
+    /// Callback from Arcium after `cast_vote_with_eligibility` completes.
+    /// Ineligible ballots leave the (re-encrypted) tally unchanged and are
+    /// not counted in `total_votes`.
+    pub fn eligible_vote_callback(
+        ctx: Context<VoteCallback>,
+        computation_offset: u64,
+        new_encrypted_tally: [u8; 128],
+        nonce: [u8; 16],
+        eligible: bool,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        settle_ballot(
+            &mut ctx.accounts.vote_record,
+            proposal,
+            &ctx.accounts.tally,
+            computation_offset,
+        )?;
+        require!(!proposal.is_revealed, VotingError::AlreadyRevealed);
+        require!(
+            !past_deadline_by(
+                proposal,
+                &Clock::get()?,
+                ctx.accounts.dao_config.callback_grace_period
+            ),
+            VotingError::CallbackWindowClosed
+        );
+        let shard = ctx.accounts.tally.shard;
+        require!(
+            shard == 0 || proposal.shards_merged & (1 << shard) == 0,
+            VotingError::ShardAlreadyMerged
+        );
+        require_ballot_version(ctx.accounts.tally.ballot_version)?;
+
+        let tally = &mut ctx.accounts.tally;
+        tally.encrypted_data = new_encrypted_tally;
+        tally.nonce = nonce;
+
+        let proposal = &mut ctx.accounts.proposal;
+        extend_tally_lineage(proposal, &ctx.accounts.tally);
+        extend_ballot_offsets(proposal, computation_offset);
+        if eligible {
+            proposal.total_votes += 1;
+        }
+        proposal.pending_computations = proposal.pending_computations.saturating_sub(1);
+
+        Ok(())
+    }
+
+    /// Callback from Arcium after vote computation completes. Stores the
+    /// updated tally and the voter's encrypted `BallotReceipt`.
+    pub fn vote_callback(
+        ctx: Context<VoteCallback>,
+        computation_offset: u64,
+        new_encrypted_tally: [u8; 128],
+        nonce: [u8; 16],
+        receipt: [u8; 64],
+        receipt_nonce: [u8; 16],
+    ) -> Result<()> {
+        // Ballots are only queued before the deadline, so a callback landing
+        // after it is a slow confirmation — accept it within a bounded window,
+        // as long as the tally it targets has not been consumed yet
+        let proposal = &ctx.accounts.proposal;
+        settle_ballot(
+            &mut ctx.accounts.vote_record,
+            proposal,
+            &ctx.accounts.tally,
+            computation_offset,
+        )?;
+        require!(!proposal.is_revealed, VotingError::AlreadyRevealed);
+        require!(
+            !past_deadline_by(
+                proposal,
+                &Clock::get()?,
+                ctx.accounts.dao_config.callback_grace_period
+            ),
+            VotingError::CallbackWindowClosed
+        );
+        let shard = ctx.accounts.tally.shard;
+        require!(
+            shard == 0 || proposal.shards_merged & (1 << shard) == 0,
+            VotingError::ShardAlreadyMerged
+        );
+        require_ballot_version(ctx.accounts.tally.ballot_version)?;
+
+        // Update the encrypted tally with new value
+        let tally = &mut ctx.accounts.tally;
+        tally.encrypted_data = new_encrypted_tally;
+        tally.nonce = nonce;
+
+        // Only the voter's key decrypts the receipt
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.receipt = receipt;
+        vote_record.receipt_nonce = receipt_nonce;
+
+        // Increment public vote counter; a delegated ballot counts its
+        // delegators too
+        let voters = 1 + vote_record.delegators as u64;
+        let proposal = &mut ctx.accounts.proposal;
+        extend_tally_lineage(proposal, &ctx.accounts.tally);
+        extend_ballot_offsets(proposal, computation_offset);
+        proposal.total_votes = proposal
+            .total_votes
+            .checked_add(voters)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+        proposal.pending_computations = proposal.pending_computations.saturating_sub(1);
+
+        Ok(())
+    }
+
+    /// Reveal the final vote results.
+    ///
+    /// Refuses while ballots are still being tallied. Once the callback grace
+    /// period has closed, `force` abandons the remaining computations (recorded
+    /// in `dropped_computations`) instead of blocking the reveal forever.
+    pub fn reveal_results(ctx: Context<RevealResults>, force: bool) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+
+        // Only authority can reveal
+        require!(
+            ctx.accounts.authority.key() == proposal.authority,
+            VotingError::Unauthorized
+        );
+
+        // Prevent re-reveal
+        require!(!proposal.is_revealed, VotingError::AlreadyRevealed);
+
+        // Validate voting has ended
+        let clock = Clock::get()?;
+        require!(voting_closed(proposal, &clock), VotingError::VotingNotEnded);
+        require!(
+            !proposal.delegate_fallback
+                || past_deadline_by(proposal, &clock, DELEGATE_FALLBACK_WINDOW),
+            VotingError::FallbackWindowOpen
+        );
+        require!(
+            proposal.pending_computations == 0
+                || (force && votes_settled(proposal, &ctx.accounts.dao_config, &clock)),
+            VotingError::VotesPending
+        );
+        require!(all_shards_merged(proposal), VotingError::ShardsNotMerged);
+        require_proposal_mxe(proposal, &ctx.accounts.cluster_account)?;
+        require!(
+            proposal.pending_mxe_program_id == Pubkey::default(),
+            VotingError::MigrationInProgress
+        );
+        require!(
+            ctx.accounts.tally.proposal == proposal.key() && ctx.accounts.tally.shard == 0,
+            VotingError::WrongTallyShard
+        );
+        require_ballot_version(ctx.accounts.tally.ballot_version)?;
+
+        let dropped = proposal.pending_computations;
+        if dropped > 0 {
+            let proposal = &mut ctx.accounts.proposal;
+            proposal.dropped_computations += dropped;
+            proposal.pending_computations = 0;
+            emit!(ComputationsDropped {
+                proposal: proposal.key(),
+                dropped,
+                total_votes: proposal.total_votes,
+            });
+        }
+
+        // Queue reveal computation
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.authority.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = Clock::get()?.slot as u64;
+        let args = build_args_for_tally(ctx.accounts.tally.encrypted_data);
+
+        // Re-queuing after a lost callback overwrites the previous binding
+        let pending = &mut ctx.accounts.pending_computation;
+        pending.proposal = ctx.accounts.proposal.key();
+        pending.computation_account = ctx.accounts.computation_account.key();
+        pending.computation_offset = computation_offset;
+        pending.comp_def_offset = comp_def_offset(REVEAL_RESULT_COMP);
+        pending.input_commitment = hashv(&[&ctx.accounts.tally.encrypted_data]).to_bytes();
+        pending.queued_at = clock.unix_timestamp;
+        pending.bump = ctx.bumps.pending_computation;
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(REVEAL_RESULT_COMP),
+            None,
+            args,
+            ctx.accounts.proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback from Arcium with revealed results
+    /// Only callable by the Arcium program via CPI (validated by sign PDA signer constraint)
+    ///
+    /// The counts are only accepted for the reveal computation recorded in the
+    /// proposal's `PendingComputation` and the Arcium-owned computation account
+    /// it was queued on, delivered by Arcium's `callback_computation` (see
+    /// `verify_computation_output`), and only while the tally still hashes to
+    /// the `input_commitment` it was queued with. The binding is closed on
+    /// success, so each queued reveal settles at most once.
+    ///
+    /// A reveal queued by `request_recount` on an already revealed proposal
+    /// only compares its counts with the published ones.
+    pub fn reveal_results_callback(
+        ctx: Context<RevealResultsCallback>,
+        computation_offset: u64,
+        yes_count: u64,
+        no_count: u64,
+        abstain_count: u64,
+        total_votes: u64,
+    ) -> Result<()> {
+        let pending = &ctx.accounts.pending_computation;
+        require!(
+            pending.computation_offset == computation_offset
+                && pending.comp_def_offset == comp_def_offset(REVEAL_RESULT_COMP),
+            VotingError::ComputationMismatch
+        );
+        require!(
+            hashv(&[&ctx.accounts.tally.encrypted_data]).to_bytes() == pending.input_commitment,
+            VotingError::ComputationMismatch
+        );
+        verify_computation_output(
+            &ctx.accounts.instructions,
+            pending,
+            &ctx.accounts.proposal.mxe_program_id,
+        )?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        if proposal.is_revealed {
+            let recount = ctx
+                .accounts
+                .recount
+                .as_mut()
+                .ok_or(VotingError::AlreadyRevealed)?;
+            require!(
+                recount.status == RecountStatus::Pending,
+                VotingError::AlreadyRevealed
+            );
+            let matched = yes_count == proposal.yes_votes
+                && no_count == proposal.no_votes
+                && abstain_count == proposal.abstain_votes
+                && total_votes == proposal.total_weight;
+            recount.resolved_at = Clock::get()?.unix_timestamp;
+            if matched {
+                recount.status = RecountStatus::Confirmed;
+                proposal.flagged = false;
+            } else {
+                // The proposal stays halted: one of the two callbacks is corrupt
+                recount.status = RecountStatus::Mismatch;
+                emit!(TallyIncident {
+                    proposal: proposal.key(),
+                    published: [
+                        proposal.yes_votes,
+                        proposal.no_votes,
+                        proposal.abstain_votes,
+                        proposal.total_weight
+                    ],
+                    recounted: [yes_count, no_count, abstain_count, total_votes],
+                });
+            }
+            emit!(RecountCompleted {
+                proposal: proposal.key(),
+                matched,
+            });
+            return Ok(());
+        }
+
+        // Validate vote count consistency
+        let computed_total = yes_count
+            .checked_add(no_count)
+            .and_then(|x| x.checked_add(abstain_count))
+            .ok_or(VotingError::ArithmeticOverflow)?;
+        require!(
+            computed_total == total_votes,
+            VotingError::VoteTallyMismatch
+        );
+
+        // Low-attention proposals become decidable as the quorum decays
+        proposal.quorum = effective_quorum(proposal, Clock::get()?.unix_timestamp);
+
+        // Check threshold for production path too
+        let threshold_met = threshold_met(proposal, yes_count, no_count, abstain_count)?;
+
+        // A missed quorum is a recorded failure, not an aborted reveal, so
+        // the deposit can be graded against it
+        let quorum_met = proposal.quorum == 0 || total_votes >= proposal.quorum;
+
+        proposal.is_active = false;
+        proposal.is_revealed = true;
+        proposal.yes_votes = yes_count;
+        proposal.no_votes = no_count;
+        proposal.abstain_votes = abstain_count;
+        // Counts are ballots under Equal weighting and summed weight otherwise
+        proposal.total_weight = total_votes;
+        proposal.passed = quorum_met && threshold_met;
+        proposal.revealed_at = Clock::get()?.unix_timestamp;
+        // Nothing executes behind a signaling result, so no dispute window
+        if proposal.kind == ProposalKind::Signaling {
+            proposal.finalized_at = proposal.revealed_at;
+        }
+        let config = &mut ctx.accounts.dao_config;
+        config.revealed_proposals += 1;
+        proposal.reveal_number = config.revealed_proposals;
+        proposal.reveal_computation_offset = computation_offset;
+
+        // An exact YES/NO tie that would pass is settled by an MPC coin flip
+        // (`resolve_tie`) instead of the authority or the threshold rounding
+        if proposal.random_tie_break && proposal.passed && yes_count == no_count {
+            proposal.passed = false;
+            proposal.tie_pending = true;
+        }
+
+        let winner: u8 = if yes_count > no_count {
+            1
+        } else if no_count > yes_count {
+            2
+        } else {
+            0
+        };
+
+        emit!(ResultsRevealed {
+            proposal: proposal.key(),
+            yes_votes: yes_count,
+            no_votes: no_count,
+            abstain_votes: abstain_count,
+            total_votes,
+            winner,
+            turnout_bps: turnout_bps(proposal, total_votes),
+        });
+
+        Ok(())
+    }
+
+    /// Dispute a revealed tally by posting `DaoConfig.recount_bond` lamports.
+    /// Re-queues the reveal computation on the stored tally ciphertext and
+    /// halts execution until `reveal_results_callback` compares the outputs.
+    /// The pending-computation rent is refunded to the proposal authority, as
+    /// for `reveal_results`.
+    pub fn request_recount(ctx: Context<RequestRecount>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(proposal.is_revealed, VotingError::NotYetRevealed);
+        require!(
+            !proposal.executed
+                && !proposal.flagged
+                && (proposal.finalized_at == 0 || proposal.kind == ProposalKind::Signaling),
+            VotingError::RecountUnavailable
+        );
+        require_proposal_mxe(proposal, &ctx.accounts.cluster_account)?;
+        require!(
+            ctx.accounts.tally.proposal == proposal.key() && ctx.accounts.tally.shard == 0,
+            VotingError::WrongTallyShard
+        );
+        let amount = ctx.accounts.dao_config.recount_bond;
+        require!(amount > 0, VotingError::RecountUnavailable);
+        // The bond PDA holds no data, so it must stay rent-exempt on its own
+        require!(
+            amount >= Rent::get()?.minimum_balance(0),
+            VotingError::BondBelowRentExemption
+        );
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.requester.to_account_info(),
+                    to: ctx.accounts.recount_bond.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.requester.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let clock = Clock::get()?;
+        let computation_offset = clock.slot;
+        let args = build_args_for_tally(ctx.accounts.tally.encrypted_data);
+
+        let pending = &mut ctx.accounts.pending_computation;
+        pending.proposal = ctx.accounts.proposal.key();
+        pending.computation_account = ctx.accounts.computation_account.key();
+        pending.computation_offset = computation_offset;
+        pending.comp_def_offset = comp_def_offset(REVEAL_RESULT_COMP);
+        pending.input_commitment = hashv(&[&ctx.accounts.tally.encrypted_data]).to_bytes();
+        pending.queued_at = clock.unix_timestamp;
+        pending.bump = ctx.bumps.pending_computation;
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(REVEAL_RESULT_COMP),
+            None,
+            args,
+            ctx.accounts.proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        let recount = &mut ctx.accounts.recount;
+        recount.proposal = ctx.accounts.proposal.key();
+        recount.requester = ctx.accounts.requester.key();
+        recount.bond = amount;
+        recount.status = RecountStatus::Pending;
+        recount.requested_at = clock.unix_timestamp;
+        recount.resolved_at = 0;
+        recount.bump = ctx.bumps.recount;
+        ctx.accounts.proposal.flagged = true;
+
+        emit!(RecountRequested {
+            proposal: recount.proposal,
+            requester: recount.requester,
+            bond: amount,
+        });
+        Ok(())
+    }
+
+    /// Mark revealed results final once `DaoConfig.dispute_window` has passed
+    /// without an open challenge (pending or failed recount, triggered
+    /// petition) or an unresolved tie. Only final results can be executed.
+    /// Permissionless.
+    pub fn finalize_results(ctx: Context<FinalizeResults>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.is_revealed, VotingError::NotYetRevealed);
+        require!(proposal.finalized_at == 0, VotingError::AlreadyFinalized);
+        require!(
+            !proposal.flagged && !proposal.tie_pending,
+            VotingError::ProposalHalted
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= proposal
+                .revealed_at
+                .checked_add(ctx.accounts.dao_config.dispute_window)
+                .ok_or(VotingError::ArithmeticOverflow)?,
+            VotingError::DisputeWindowOpen
+        );
+        proposal.finalized_at = now;
+
+        emit!(ResultsFinalized {
+            proposal: proposal.key(),
+            passed: proposal.passed,
+            finalized_at: now,
+        });
+        Ok(())
+    }
+
+    /// Lift the halt of a recount whose computation has not come back within
+    /// `RECOUNT_TIMEOUT`. The pending computation is closed, so a late
+    /// callback can no longer land, and the bond becomes refundable through
+    /// `settle_recount`. Permissionless.
+    pub fn expire_recount(ctx: Context<ExpireRecount>) -> Result<()> {
+        let recount = &mut ctx.accounts.recount;
+        require!(
+            recount.status == RecountStatus::Pending,
+            VotingError::InvalidRecountStatus
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= recount
+                .requested_at
+                .checked_add(RECOUNT_TIMEOUT)
+                .ok_or(VotingError::ArithmeticOverflow)?,
+            VotingError::RecountNotExpired
+        );
+        recount.status = RecountStatus::Expired;
+        recount.resolved_at = now;
+        ctx.accounts.proposal.flagged = false;
+
+        emit!(RecountExpired {
+            proposal: recount.proposal,
+        });
+        Ok(())
+    }
+
+    /// Council review of a recount that disagreed with the published tally.
+    /// Upholding the recount deactivates the proposal and cancels any pending
+    /// execution; dismissing it lets the published tally stand and lifts the
+    /// halt. Either way the bond is refunded through `settle_recount`.
+    pub fn review_recount(ctx: Context<ReviewRecount>, uphold: bool) -> Result<()> {
+        require!(
+            ctx.accounts.dao_config.council != Pubkey::default(),
+            VotingError::Unauthorized
+        );
+        let recount = &mut ctx.accounts.recount;
+        require!(
+            recount.status == RecountStatus::Mismatch,
+            VotingError::InvalidRecountStatus
+        );
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.flagged = false;
+        if uphold {
+            proposal.is_active = false;
+            if proposal.passed && !proposal.executed {
+                proposal.cancelled = true;
+                emit!(ExecutionCancelled {
+                    proposal: proposal.key(),
+                    cancelled_by: ctx.accounts.council.key(),
+                });
+            }
+            recount.status = RecountStatus::Upheld;
+        } else {
+            recount.status = RecountStatus::Dismissed;
+        }
+        recount.resolved_at = Clock::get()?.unix_timestamp;
+
+        emit!(RecountReviewed {
+            proposal: proposal.key(),
+            upheld: uphold,
+        });
+        Ok(())
+    }
+
+    /// Settle a resolved recount. A confirmed tally forfeits the bond to the
+    /// SOL treasury; a reviewed mismatch or an expired computation refunds it
+    /// to the requester. Permissionless.
+    pub fn settle_recount(ctx: Context<SettleRecount>) -> Result<()> {
+        let recount = &ctx.accounts.recount;
+        let to = match recount.status {
+            RecountStatus::Pending | RecountStatus::Mismatch => {
+                return err!(VotingError::RecountPending)
+            }
+            RecountStatus::Confirmed => ctx.accounts.sol_treasury.to_account_info(),
+            RecountStatus::Upheld | RecountStatus::Dismissed | RecountStatus::Expired => {
+                ctx.accounts.requester.to_account_info()
+            }
+        };
+        let proposal_key = recount.proposal;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            RECOUNT_BOND_SEED,
+            proposal_key.as_ref(),
+            &[ctx.bumps.recount_bond],
+        ]];
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.recount_bond.to_account_info(),
+                    to,
+                },
+                signer_seeds,
+            ),
+            recount.bond,
+        )?;
+        Ok(())
+    }
+
+    /// Queue derivation of `H(voter_secret, proposal_id)` inside the MPC.
+    ///
+    /// Only the nullifier is revealed; the callback records it in a
+    /// `NullifierRecord` PDA keyed by its value, so a second ballot from the
+    /// same secret fails without the program ever storing the voter's key.
+    /// Any payer (e.g. a relayer) may submit.
+    pub fn derive_nullifier(
+        ctx: Context<DeriveNullifier>,
+        encrypted_secret: [u8; 32],
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(proposal.is_active, VotingError::VotingClosed);
+        require!(
+            voting_open(proposal, &Clock::get()?),
+            VotingError::VotingEnded
+        );
+        require_proposal_mxe(proposal, &ctx.accounts.cluster_account)?;
+
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.payer.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = Clock::get()?.slot;
+        let args = build_args_for_nullifier(encrypted_secret, ctx.accounts.proposal.id);
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(NULLIFIER_COMP),
+            None,
+            args,
+            ctx.accounts.proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback from Arcium with the revealed nullifier. `init` on the
+    /// nullifier-keyed PDA rejects a reused secret.
+    pub fn derive_nullifier_callback(
+        ctx: Context<DeriveNullifierCallback>,
+        nullifier: [u8; 32],
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.nullifier_record;
+        record.proposal = ctx.accounts.proposal.key();
+        record.nullifier = nullifier;
+        record.recorded_at = Clock::get()?.unix_timestamp;
+        record.bump = ctx.bumps.nullifier_record;
+
+        emit!(NullifierRecorded {
+            proposal: record.proposal,
+            nullifier,
+        });
+        Ok(())
+    }
+
+    /// Queue the MPC coin flip for a revealed proposal stuck on an exact tie.
+    /// Permissionless — neither the authority nor the caller can bias the coin.
+    /// A flip left unanswered for `TIE_BREAK_TIMEOUT_SLOTS` may be queued
+    /// again; only the latest one can land.
+    pub fn resolve_tie(ctx: Context<ResolveTie>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(proposal.tie_pending, VotingError::NoTiePending);
+        let clock = Clock::get()?;
+        require!(
+            proposal.tie_break_offset == 0
+                || clock.slot
+                    >= proposal
+                        .tie_break_offset
+                        .checked_add(TIE_BREAK_TIMEOUT_SLOTS)
+                        .ok_or(VotingError::ArithmeticOverflow)?,
+            VotingError::TiePending
+        );
+        require_ballot_version(ctx.accounts.tally.ballot_version)?;
+        require_proposal_mxe(proposal, &ctx.accounts.cluster_account)?;
+
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.payer.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = clock.slot;
+        let args = build_args_for_tally(ctx.accounts.tally.encrypted_data);
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(TIE_BREAK_COMP),
+            None,
+            args,
+            ctx.accounts.proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        ctx.accounts.proposal.tie_break_offset = computation_offset;
+        Ok(())
+    }
+
+    /// Callback from Arcium with the tie-break outcome of the latest queued flip
+    pub fn resolve_tie_callback(
+        ctx: Context<ResolveTieCallback>,
+        computation_offset: u64,
+        yes_wins: bool,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.tie_pending, VotingError::NoTiePending);
+        require!(
+            proposal.tie_break_offset == computation_offset,
+            VotingError::ComputationMismatch
+        );
+
+        proposal.passed = yes_wins;
+        proposal.tie_pending = false;
+        proposal.tie_break_offset = 0;
+
+        emit!(TieResolved {
+            proposal: proposal.key(),
+            yes_wins,
+        });
+        Ok(())
+    }
+
+    /// Initialize computation definitions (called once at deployment).
+    ///
+    /// Registers the Arcis circuit bytecode on-chain and stores the circuit hash
+    /// for integrity verification. The `circuit_hash` parameter must match
+    /// `CIRCUIT_HASH` (compiled from `circuit_hash!("voting-circuit")`).
+    ///
+    /// After initialization, the MXE cluster can execute the following instructions:
+    /// - `initialize_voting` → Creates `Enc<Mxe, Tally>` with zero counters
+    /// - `cast_vote` → Accumulates `Enc<Shared, u8>` into `Enc<Mxe, Tally>`
+    /// - `cast_weighted_vote` → Same, adding a public per-ballot weight
+    /// - `cast_vote_encrypted_weight` → Same, with an encrypted weight (linear or quadratic)
+    /// - `vote_with_delegations` → One ballot carrying own plus delegated weight
+    /// - `finalize_and_reveal` → Threshold-decrypts aggregate totals
+    /// - `finalize_with_threshold` → Reveals + checks quorum/threshold
+    /// - `get_live_tally` → Real-time tally for Transparent mode
+    /// - `get_vote_count` → Total participation without breakdown
+    /// - `verify_no_vote` → Reveals only whether one stored ballot was NO (ragequit)
+    /// - `combine_tallies` → Folds a tally shard into the primary tally
+    /// - `migrate_tally` → Re-encrypts a tally under a new cluster's MXE key
+    /// - `resolve_tie` → Reveals only the winner of a tie, decided by an MPC coin
+    /// - `initialize_ranked_voting` / `cast_ranked_vote` → Encrypted ranked-choice tally
+    /// - `run_instant_runoff` → Reveals only each round's eliminated option and the winner
+    /// - `shuffle_ballots` / `tally_ballot_batch` → Permute a ballot batch in MPC, then tally it
+    /// - `derive_nullifier` → Reveals only `H(voter_secret, proposal_id)`
+    /// - `cast_vote_with_eligibility` → Verifies a snapshot Merkle proof of the weight in MPC
+    /// - `apply_conviction` / `finalize_with_conviction` → Time-weighted (conviction) aggregation
+    /// - `cast_sequenced_vote` / `recast_vote` → Re-voting where the highest sequence number counts
+    pub fn init_comp_def(
+        ctx: Context<InitCompDef>,
+        circuit_hash: String,
+        comp_def_data: Vec<u8>,
+    ) -> Result<()> {
+        // Verify circuit integrity: the provided hash must match the compile-time hash.
+        // This prevents deployment of tampered circuits — if any byte of the Arcis
+        // bytecode has been modified, the SHA-256 hash will differ.
+        require!(
+            circuit_hash == CIRCUIT_HASH,
+            VotingError::CircuitHashMismatch
+        );
+
+        msg!(
+            "Initializing computation definitions with circuit hash: {}",
+            circuit_hash
+        );
+        msg!(
+            "Bytecode size: {} bytes ({} computation definitions)",
+            comp_def_data.len(),
+            24 // initialize_voting, cast_vote, cast_weighted_vote, cast_vote_encrypted_weight, vote_with_delegations, finalize_and_reveal, finalize_with_threshold, get_live_tally, get_vote_count, verify_no_vote, combine_tallies, migrate_tally, resolve_tie, initialize_ranked_voting, cast_ranked_vote, run_instant_runoff, shuffle_ballots, tally_ballot_batch, derive_nullifier, cast_vote_with_eligibility, apply_conviction, finalize_with_conviction, cast_sequenced_vote, recast_vote
+        );
+
+        // Store circuit hash in the comp def state for on-chain verification
+        let comp_def_state = &mut ctx.accounts.comp_def_state;
+        comp_def_state.circuit_hash = circuit_hash;
+        comp_def_state.authority = ctx.accounts.authority.key();
+        comp_def_state.initialized = true;
+        comp_def_state.bump = ctx.bumps.comp_def_state;
+
+        Ok(())
+    }
+
+    /// Initialize the computation offset PDA (one-time setup)
+    pub fn init_computation_offset(ctx: Context<InitComputationOffset>) -> Result<()> {
+        let state = &mut ctx.accounts.computation_offset_account;
+        state.bump = ctx.bumps.computation_offset_account;
+        Ok(())
+    }
+
+    // ==================== DEV MODE INSTRUCTIONS ====================
+    // These bypass Arcium MXE CPI for devnet testing.
+    // All other logic (token gating, PDA validation, double-vote
+    // prevention) remains identical to production instructions.
+    // Remove before mainnet deployment.
+
+    /// Dev mode: Create a proposal without Arcium CPI
+    #[allow(clippy::too_many_arguments)]
+    pub fn dev_create_proposal(
+        ctx: Context<DevCreateProposal>,
+        proposal_id: u64,
+        title: String,
+        description: String,
+        voting_ends_at: i64,
+        gate_mint: Pubkey,
+        gate_kind: GateKind,
+        min_balance: u64,
+        weighting_mode: WeightingMode,
+        max_voters: u64,
+        quorum: u64,
+        threshold_bps: u16,
+        privacy_level: u8,
+        links: Vec<ProposalLink>,
+        execution_delay: i64,
+        voting_end_slot: u64,
+        kind: ProposalKind,
+        excluded: Vec<Pubkey>,
+        gate_mints: Vec<Pubkey>,
+    ) -> Result<()> {
+        // Validate V2 fields
+        require!(
+            threshold_bps > 0 && threshold_bps <= 10_000,
+            VotingError::InvalidThreshold
+        );
+        require!(
+            excluded.len() <= MAX_EXCLUDED_VOTERS,
+            VotingError::TooManyExclusions
+        );
+        require_valid_gate_mints(gate_kind, &gate_mints)?;
+        require!(privacy_level <= 2, VotingError::InvalidPrivacyLevel);
+        require!(execution_delay >= 0, VotingError::InvalidExecutionDelay);
+        require!(
+            kind == ProposalKind::Binding || execution_delay == 0,
+            VotingError::InvalidExecutionDelay
+        );
+        let committee = committee_scope(&ctx.accounts.committee, &ctx.accounts.authority.key())?;
+        let clock = Clock::get()?;
+        require!(
+            voting_end_slot == 0 || voting_end_slot > clock.slot,
+            VotingError::InvalidEndSlot
+        );
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.id = proposal_id;
+        proposal.authority = ctx.accounts.authority.key();
+        proposal.title = title;
+        proposal.description = description;
+        // Dev proposals stay time-only unless slot mode is requested
+        let voting_ends_at = if voting_end_slot > 0 {
+            proposal_deadline(&clock, voting_ends_at, voting_end_slot).0
+        } else {
+            voting_ends_at
+        };
+        proposal.voting_ends_at = voting_ends_at;
+        proposal.voting_ends_slot = voting_end_slot;
+        proposal.slot_deadline = voting_end_slot > 0;
+        proposal.is_active = true;
+        proposal.is_revealed = false;
+        proposal.total_votes = 0;
+        proposal.gate_mint = gate_mint;
+        proposal.gate_kind = gate_kind;
+        proposal.min_balance = min_balance;
+        proposal.weighting_mode = weighting_mode;
+        proposal.total_weight = 0;
+        proposal.max_voters = max_voters;
+        proposal.ballots_cast = 0;
+        proposal.jury_size = 0;
+        proposal.ragequit_burned = 0;
+        proposal.tie_pending = false;
+        proposal.tie_break_offset = 0;
+        proposal.snapshot_root = [0u8; 32];
+        proposal.snapshot_total_weight = 0;
+        proposal.snapshot_slot = 0;
+        proposal.created_slot = Clock::get()?.slot;
+        proposal.oracle_root = [0u8; 32];
+        proposal.oracle_total_weight = 0;
+        proposal.revoting = false;
+        proposal.excluded = excluded.clone();
+        proposal.gate_mints = gate_mints;
+        proposal.delegate_fallback = false;
+        proposal.fallback_delegators = 0;
+        proposal.fallback_weight = 0;
+        proposal.reveal_number = 0;
+        proposal.awaiting_sponsorship = false;
+        proposal.voting_starts_at = Clock::get()?.unix_timestamp;
+        proposal.sponsorship_deadline = 0;
+        proposal.endorsement_weight = 0;
+        proposal.sponsorship_expired = false;
+        proposal.quorum_decay_period = 0;
+        proposal.quorum_floor_bps = 0;
+        proposal.threshold_base = ThresholdBase::NonAbstain;
+        proposal.randomness_account = Pubkey::default();
+        proposal.randomness_seed_slot = 0;
+        proposal.randomness = None;
+        proposal.last_ping_at = 0;
+        proposal.vote_records_root = [0u8; 32];
+        proposal.tally_lineage = [0u8; 32];
+        proposal.ballot_offsets_hash = [0u8; 32];
+        proposal.reveal_computation_offset = 0;
+        proposal.compacted_records = 0;
+        proposal.tally_shards = 1;
+        proposal.shards_merged = 0;
+        proposal.merging_shard = 0;
+        proposal.merge_offset = 0;
+        proposal.pending_computations = 0;
+        proposal.dropped_computations = 0;
+        proposal.mxe_program_id = Pubkey::default();
+        proposal.mxe_cluster = Pubkey::default();
+        proposal.random_tie_break = false;
+        proposal.pending_mxe_program_id = Pubkey::default();
+        proposal.pending_mxe_cluster = Pubkey::default();
+        proposal.pending_mxe_offset = 0;
+        proposal.eligible_supply = 0;
+        proposal.quorum = quorum;
+        proposal.threshold_bps = threshold_bps;
+        proposal.privacy_level = privacy_level;
+        proposal.passed = false;
+        validate_links(&links)?;
+        proposal.links = links;
+        proposal.deposit_amount = 0;
+        proposal.deposit_returned = false;
+        proposal.bond_in_sol = false;
+        proposal.holds_rate_slot = false;
+        proposal.execution_delay = execution_delay;
+        proposal.kind = kind;
+        proposal.executed = false;
+        proposal.cancelled = false;
+        proposal.flagged = false;
+        proposal.executed_by = Pubkey::default();
+        proposal.executed_slot = 0;
+        proposal.executed_at = 0;
+        proposal.committee = committee;
+        proposal.revealed_at = 0;
+        proposal.attestations = 0;
+        proposal.attestation_disputes = 0;
+        proposal.moderation_cases = 0;
+        proposal.finalized_at = 0;
+        proposal.bump = ctx.bumps.proposal;
+
+        emit!(ProposalCreated {
+            proposal_id,
+            authority: ctx.accounts.authority.key(),
+            voting_ends_at,
+            voting_end_slot,
+            kind,
+            excluded,
+        });
+
+        Ok(())
+    }
+
+    /// Delegate voting power to another address
+    /// The delegator's token-gated vote weight is transferred to the delegate.
+    /// Delegators cannot vote directly while their delegation is active.
+    pub fn delegate_vote(ctx: Context<DelegateVote>) -> Result<()> {
+        require_delegate_eligible(
+            &ctx.accounts.dao_config,
+            &ctx.accounts.delegate.key(),
+            &ctx.accounts.delegate_token_account,
+        )?;
+
+        let delegation = &mut ctx.accounts.delegation;
+        delegation.delegator = ctx.accounts.delegator.key();
+        delegation.delegate = ctx.accounts.delegate.key();
+        delegation.created_at = Clock::get()?.unix_timestamp;
+        delegation.accepted = !ctx.accounts.dao_config.require_delegate_acceptance;
+        delegation.bump = ctx.bumps.delegation;
+
+        emit!(VoteDelegated {
+            delegator: ctx.accounts.delegator.key(),
+            delegate: ctx.accounts.delegate.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Gasless `delegate_vote`: a relayer submits the delegator's signed
+    /// `delegation_message` (verified by a preceding Ed25519 instruction) and
+    /// pays the Delegation PDA's rent, so wallets without SOL can delegate.
+    /// The message must carry the delegator's next `DelegationNonce`, so a
+    /// signature is used once and can't re-create a revoked delegation.
+    pub fn delegate_vote_signed(
+        ctx: Context<DelegateVoteSigned>,
+        valid_until: i64,
+        nonce: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(now <= valid_until, VotingError::SignatureExpired);
+        require!(
+            nonce == ctx.accounts.delegation_nonce.next,
+            VotingError::DelegationNonceMismatch
+        );
+        let delegator = ctx.accounts.delegator.key();
+        let delegate = ctx.accounts.delegate.key();
+        require_delegate_eligible(
+            &ctx.accounts.dao_config,
+            &delegate,
+            &ctx.accounts.delegate_token_account,
+        )?;
+        verify_ed25519_ix(
+            &ctx.accounts.instructions,
+            &delegator,
+            &delegation_message(&delegator, &delegate, valid_until, nonce),
+        )?;
+
+        let delegation_nonce = &mut ctx.accounts.delegation_nonce;
+        delegation_nonce.delegator = delegator;
+        delegation_nonce.next = nonce + 1;
+        delegation_nonce.bump = ctx.bumps.delegation_nonce;
+
+        let delegation = &mut ctx.accounts.delegation;
+        delegation.delegator = delegator;
+        delegation.delegate = delegate;
+        delegation.created_at = now;
+        delegation.accepted = !ctx.accounts.dao_config.require_delegate_acceptance;
+        delegation.bump = ctx.bumps.delegation;
+
+        emit!(VoteDelegated {
+            delegator,
+            delegate,
+        });
+
+        Ok(())
+    }
+
+    /// Read-only: resolve `voter`'s effective power on `proposal` — own gate
+    /// weight plus accepted incoming delegations, or nothing if its own power
+    /// is delegated away. Returned as return data; simulate to read it.
+    ///
+    /// `remaining_accounts` are `[delegation, delegator_token_account,
+    /// delegator_vesting_position]` triplets for incoming delegations (Token
+    /// gates, as in `cast_delegated_vote`) or
+    /// the price-feed triplets of a UsdValue gate. Delegators below
+    /// `min_balance` or with unaccepted delegations contribute nothing.
+    pub fn get_voting_power<'info>(
+        ctx: Context<'_, '_, '_, 'info, GetVotingPower<'info>>,
+    ) -> Result<VotingPower> {
+        let proposal = &ctx.accounts.proposal;
+        let voter = ctx.accounts.voter.key();
+        let delegated_away = ctx
+            .accounts
+            .delegation
+            .as_ref()
+            .is_some_and(|delegation| delegation.accepted);
+
+        let gate_remaining: &[AccountInfo] = if proposal.gate_kind == GateKind::UsdValue {
+            ctx.remaining_accounts
+        } else {
+            &[]
+        };
+        let own_power = gate_balance(
+            proposal,
+            &voter,
+            &ctx.accounts.voter_token_account,
+            &ctx.accounts.voter_stake_account,
+            &ctx.accounts.stake_pool,
+            gate_remaining,
+            &ctx.accounts.dao_config.mint_multipliers,
+        )?;
+        let own_power = settled_power(
+            own_power,
+            &ctx.accounts.dao_config,
+            proposal,
+            &voter,
+            &ctx.accounts.voter_escrow,
+        )?;
+        let own_power = at_snapshot(own_power, proposal, &voter, &ctx.accounts.voter_escrow)?;
+        let (own_balance, own_weighted) = with_vesting_power(
+            own_power,
+            proposal,
+            &voter,
+            ctx.accounts
+                .voter_token_account
+                .as_ref()
+                .map(|account| account.key()),
+            ctx.accounts.vesting_position.as_deref(),
+            ctx.accounts.dao_config.vesting_boost_bps,
+        )?;
+        let own_weight = if delegated_away || own_balance < proposal.min_balance {
+            0
+        } else {
+            vote_weight(proposal, own_weighted, &ctx.accounts.reputation).unwrap_or(0)
+        };
+
+        let mut delegated_weight: u64 = 0;
+        let mut delegations: u8 = 0;
+        if proposal.gate_kind == GateKind::Token && proposal.snapshot_slot == 0 && !delegated_away {
+            let triplets = ctx.remaining_accounts.chunks_exact(3);
+            require!(
+                triplets.remainder().is_empty() && triplets.len() <= MAX_DELEGATORS_PER_VOTE,
+                VotingError::InvalidDelegationBatch
+            );
+            for accounts in triplets {
+                require!(
+                    *accounts[0].owner == crate::ID,
+                    VotingError::InvalidDelegation
+                );
+                let delegation =
+                    Delegation::try_deserialize(&mut &accounts[0].try_borrow_data()?[..])?;
+                let (expected, _) = Pubkey::find_program_address(
+                    &[DELEGATION_SEED, delegation.delegator.as_ref()],
+                    ctx.program_id,
+                );
+                require!(
+                    accounts[0].key() == expected && delegation.delegate == voter,
+                    VotingError::InvalidDelegation
+                );
+                require!(
+                    *accounts[1].owner == token::ID,
+                    VotingError::InvalidTokenAccount
+                );
+                let token_account =
+                    TokenAccount::try_deserialize(&mut &accounts[1].try_borrow_data()?[..])?;
+                require!(
+                    token_account.owner == delegation.delegator
+                        && token_account.mint == proposal.gate_mint,
+                    VotingError::InvalidTokenAccount
+                );
+                let (balance, weighted) = with_vesting_power(
+                    (token_account.amount, token_account.amount),
+                    proposal,
+                    &delegation.delegator,
+                    Some(accounts[1].key()),
+                    (accounts[2].key() != crate::ID).then_some(&accounts[2]),
+                    ctx.accounts.dao_config.vesting_boost_bps,
+                )?;
+                if !delegation.accepted || balance < proposal.min_balance {
+                    continue;
+                }
+                delegated_weight = delegated_weight
+                    .checked_add(vote_weight(proposal, weighted, &None)?)
+                    .ok_or(VotingError::ArithmeticOverflow)?;
+                delegations += 1;
+            }
+        }
+
+        Ok(VotingPower {
+            own_weight,
+            delegated_weight,
+            total: own_weight
+                .checked_add(delegated_weight)
+                .ok_or(VotingError::ArithmeticOverflow)?,
+            delegations,
+            delegated_away,
+        })
+    }
+
+    /// Read-only: `proposal`'s lifecycle phase, deadlines and counts without
+    /// deserializing the whole account. `quorum` is the decayed quorum in
+    /// force now. Returned as return data; simulate or CPI to read it.
+    pub fn get_proposal_state(ctx: Context<GetProposalState>) -> Result<ProposalState> {
+        let proposal = &ctx.accounts.proposal;
+        let clock = Clock::get()?;
+        let phase = if proposal.sponsorship_expired {
+            ProposalPhase::Expired
+        } else if proposal.cancelled {
+            ProposalPhase::Cancelled
+        } else if proposal.is_revealed {
+            ProposalPhase::Revealed
+        } else if proposal.flagged {
+            ProposalPhase::Paused
+        } else if proposal.awaiting_sponsorship {
+            ProposalPhase::AwaitingSponsorship
+        } else if !proposal.is_active {
+            ProposalPhase::Closed
+        } else if voting_open(proposal, &clock) {
+            ProposalPhase::Voting
+        } else {
+            ProposalPhase::AwaitingReveal
+        };
+        let quorum = if proposal.is_revealed {
+            proposal.quorum
+        } else {
+            effective_quorum(proposal, clock.unix_timestamp)
+        };
+        Ok(ProposalState {
+            id: proposal.id,
+            phase,
+            voting_starts_at: proposal.voting_starts_at,
+            voting_ends_at: proposal.voting_ends_at,
+            voting_ends_slot: proposal.voting_ends_slot,
+            ballots_cast: proposal.ballots_cast,
+            total_votes: proposal.total_votes,
+            quorum,
+            threshold_bps: proposal.threshold_bps,
+            yes_votes: proposal.yes_votes,
+            no_votes: proposal.no_votes,
+            abstain_votes: proposal.abstain_votes,
+            passed: proposal.passed,
+            executed: proposal.executed,
+        })
+    }
+
+    /// Read-only: progress of `proposal`'s encrypted tally through shard
+    /// merging and outstanding MPC computations. Returned as return data.
+    pub fn get_tally_status(ctx: Context<GetTallyStatus>) -> Result<TallyStatus> {
+        let proposal = &ctx.accounts.proposal;
+        Ok(TallyStatus {
+            ballots_cast: proposal.ballots_cast,
+            total_votes: proposal.total_votes,
+            tally_shards: proposal.tally_shards,
+            shards_merged: proposal.shards_merged,
+            pending_computations: proposal.pending_computations,
+            dropped_computations: proposal.dropped_computations,
+            tally_lineage: proposal.tally_lineage,
+            is_revealed: proposal.is_revealed,
+        })
+    }
+
+    /// Activate a delegation made to the signer. Only needed when the DAO sets
+    /// `require_delegate_acceptance`; until then the delegation adds no power
+    /// and doesn't stop the delegator from voting directly.
+    pub fn accept_delegation(ctx: Context<AcceptDelegation>) -> Result<()> {
+        let delegation = &mut ctx.accounts.delegation;
+        require!(!delegation.accepted, VotingError::DelegationAlreadyAccepted);
+        delegation.accepted = true;
+        // Power counts from acceptance, not from the original request
+        delegation.created_at = Clock::get()?.unix_timestamp;
+
+        emit!(DelegationAccepted {
+            delegator: delegation.delegator,
+            delegate: delegation.delegate,
+        });
+
+        Ok(())
+    }
+
+    /// Revoke a previously created delegation
+    pub fn revoke_delegation(_ctx: Context<RevokeDelegation>) -> Result<()> {
+        // Account is closed by the close constraint
+        emit!(DelegationRevoked {
+            delegator: _ctx.accounts.delegation.delegator,
+            delegate: _ctx.accounts.delegation.delegate,
+        });
+
+        Ok(())
+    }
+
+    /// Dev mode: Initialize tally without Arcium callback
+    pub fn dev_init_tally(ctx: Context<DevInitTally>) -> Result<()> {
+        let tally = &mut ctx.accounts.tally;
+        tally.proposal = ctx.accounts.proposal.key();
+        tally.shard = 0;
+        tally.ballot_version = BALLOT_VERSION;
+        tally.encrypted_data = [0u8; 128];
+        tally.nonce = [0u8; 16];
+        tally.bump = ctx.bumps.tally;
+        Ok(())
+    }
+
+    /// Dev mode: Cast vote without Arcium CPI (token gating still enforced)
+    pub fn dev_cast_vote(
+        ctx: Context<DevCastVote>,
+        encrypted_choice: [u8; 32],
+        nonce: [u8; 16],
+        voter_pubkey: [u8; 32],
+    ) -> Result<()> {
+        require!(ctx.accounts.proposal.is_active, VotingError::VotingClosed);
+        require!(
+            !ctx.accounts
+                .proposal
+                .excluded
+                .contains(&ctx.accounts.voter.key()),
+            VotingError::VoterExcluded
+        );
+
+        let clock = Clock::get()?;
+        require!(
+            voting_open(&ctx.accounts.proposal, &clock),
+            VotingError::VotingEnded
+        );
+        require!(
+            ctx.accounts.proposal.max_voters == 0
+                || ctx.accounts.proposal.ballots_cast < ctx.accounts.proposal.max_voters,
+            VotingError::VoterCapReached
+        );
+        if ctx.accounts.proposal.jury_size > 0 {
+            let roster = ctx
+                .accounts
+                .jury_roster
+                .as_ref()
+                .ok_or(VotingError::NotOnJury)?;
+            require!(
+                roster.jurors.contains(&ctx.accounts.voter.key()),
+                VotingError::NotOnJury
+            );
+        }
+
+        // Check no active delegation — delegators must revoke before voting directly
+        let (delegation_pda, _) = Pubkey::find_program_address(
+            &[DELEGATION_SEED, ctx.accounts.voter.key().as_ref()],
+            ctx.program_id,
+        );
+        let delegation_info = ctx
+            .remaining_accounts
+            .iter()
+            .find(|a| a.key() == delegation_pda);
+        if let Some(acct) = delegation_info {
+            if acct.data_len() > 0 && acct.owner == ctx.program_id {
+                // Delegations still awaiting `accept_delegation` carry no power
+                let delegation = Delegation::try_deserialize(&mut &acct.try_borrow_data()?[..])?;
+                require!(!delegation.accepted, VotingError::ActiveDelegation);
+            }
+        }
+
+        // Gate: voter must hold the required token, stake or LST balance
+        let (balance, _) = gate_balance(
+            &ctx.accounts.proposal,
+            &ctx.accounts.voter.key(),
+            &ctx.accounts.voter_token_account,
+            &ctx.accounts.voter_stake_account,
+            &ctx.accounts.stake_pool,
+            ctx.remaining_accounts,
+            &[],
+        )?;
+        require!(
+            balance >= ctx.accounts.proposal.min_balance,
+            VotingError::InsufficientTokenBalance
+        );
+
+        // Record that this voter has voted
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = ctx.accounts.proposal.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.voted_at = clock.unix_timestamp;
+        vote_record.encrypted_choice = encrypted_choice;
+        vote_record.nonce = nonce;
+        vote_record.voter_pubkey = voter_pubkey;
+        vote_record.no_vote_proven = false;
+        vote_record.ballot_version = ctx.accounts.tally.ballot_version;
+        vote_record.gate_balance = balance;
+        vote_record.bump = ctx.bumps.vote_record;
+
+        // Dev mode: directly update tally nonce and vote counter
+        ctx.accounts.tally.nonce = nonce;
+        ctx.accounts.proposal.total_votes += 1;
+        ctx.accounts.proposal.ballots_cast += 1;
+
+        emit!(VoteCast {
+            proposal: ctx.accounts.proposal.key(),
+            voter: ctx.accounts.voter.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Dev mode: Reveal results with provided counts (simulates MXE callback)
+    pub fn dev_reveal_results(
+        ctx: Context<DevRevealResults>,
+        yes_count: u64,
+        no_count: u64,
+        abstain_count: u64,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+
+        require!(
+            ctx.accounts.authority.key() == proposal.authority,
+            VotingError::Unauthorized
+        );
+
+        // Prevent re-reveal
+        require!(!proposal.is_revealed, VotingError::AlreadyRevealed);
+
+        let clock = Clock::get()?;
+        require!(voting_closed(proposal, &clock), VotingError::VotingNotEnded);
+
+        // Checked arithmetic to prevent overflow
+        let total_votes = yes_count
+            .checked_add(no_count)
+            .and_then(|x| x.checked_add(abstain_count))
+            .ok_or(VotingError::ArithmeticOverflow)?;
+
+        proposal.quorum = effective_quorum(proposal, clock.unix_timestamp);
+
+        // Check threshold: yes_votes must be >= threshold_bps of the threshold base
+        let threshold_met = threshold_met(proposal, yes_count, no_count, abstain_count)?;
+
+        // A missed quorum reveals as failed, as in `reveal_results_callback`
+        let quorum_met = proposal.quorum == 0 || total_votes >= proposal.quorum;
+
+        proposal.is_active = false;
+        proposal.is_revealed = true;
+        proposal.yes_votes = yes_count;
+        proposal.no_votes = no_count;
+        proposal.abstain_votes = abstain_count;
+        // Counts are ballots under Equal weighting and summed weight otherwise
+        proposal.total_weight = total_votes;
+        proposal.passed = quorum_met && threshold_met;
+        proposal.revealed_at = Clock::get()?.unix_timestamp;
+        if proposal.kind == ProposalKind::Signaling {
+            proposal.finalized_at = proposal.revealed_at;
+        }
+        let config = &mut ctx.accounts.dao_config;
+        config.revealed_proposals += 1;
+        proposal.reveal_number = config.revealed_proposals;
+
+        // An exact YES/NO tie that would pass is settled by an MPC coin flip
+        // (`resolve_tie`) instead of the authority or the threshold rounding
+        if proposal.random_tie_break && proposal.passed && yes_count == no_count {
+            proposal.passed = false;
+            proposal.tie_pending = true;
+        }
+
+        let winner = if yes_count > no_count {
+            1u8
+        } else if no_count > yes_count {
+            2u8
+        } else {
+            0u8
+        };
+
+        emit!(ResultsRevealed {
+            proposal: proposal.key(),
+            yes_votes: yes_count,
+            no_votes: no_count,
+            abstain_votes: abstain_count,
+            total_votes,
+            winner,
+            turnout_bps: turnout_bps(proposal, total_votes),
+        });
+
+        Ok(())
+    }
+
+    /// Initialize DAO configuration (one-time setup)
+    pub fn init_dao_config(
+        ctx: Context<InitDaoConfig>,
+        deposit_mint: Pubkey,
+        proposal_deposit: u64,
+        treasury: Pubkey,
+        slash_if_no_quorum: bool,
+        default_quorum: u64,
+        deposit_in_usd: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.dao_config;
+        config.authority = ctx.accounts.authority.key();
+        config.deposit_mint = deposit_mint;
+        config.proposal_deposit = proposal_deposit;
+        config.deposit_in_usd = deposit_in_usd;
+        config.deposit_in_sol = false;
+        config.treasury = treasury;
+        config.slash_if_no_quorum = slash_if_no_quorum;
+        config.default_quorum = default_quorum;
+        config.reminder_window = 0;
+        config.reminder_interval = 0;
+        config.crank_reward = 0;
+        config.callback_grace_period = DEFAULT_CALLBACK_GRACE_PERIOD;
+        config.approved_mxes = Vec::new();
+        config.random_tie_break = false;
+        config.graded_slash = false;
+        config.graded_slash_partial_bps = DEFAULT_GRADED_SLASH_PARTIAL_BPS;
+        config.revenue_share_bps = 0;
+        config.revenue_epoch_duration = 0;
+        config.delegator_reward_share_bps = 0;
+        config.require_delegate_acceptance = false;
+        config.require_delegate_eligibility = false;
+        config.delegate_gate_mint = Pubkey::default();
+        config.delegate_min_balance = 0;
+        config.quorum_bps = 0;
+        config.quorum_decay_period = 0;
+        config.threshold_base = ThresholdBase::NonAbstain;
+        config.quorum_floor_bps = 0;
+        config.mint_multipliers = Vec::new();
+        config.tier_mint = Pubkey::default();
+        config.voter_tiers = Vec::new();
+        config.allowed_programs = Vec::new();
+        config.observers = Vec::new();
+        config.proposer_programs = Vec::new();
+        config.turnout_boosts = Vec::new();
+        config.turnout_match_budget = 0;
+        config.compliance_authority = Pubkey::default();
+        config.moderator = Pubkey::default();
+        config.snapshot_oracle = Pubkey::default();
+        config.outbox_executor = Pubkey::default();
+        // The bond PDA holds no data, so it must stay rent-exempt on its own
+        config.appeal_bond = Rent::get()?.minimum_balance(0);
+        config.recount_bond = 0;
+        config.dispute_window = 0;
+        config.max_ballot_validity = 0;
+        config.relayer_bond = 0;
+        config.tokens_per_active_proposal = 0;
+        config.proposer_min_votes = 0;
+        config.proposer_vote_window = 0;
+        config.revealed_proposals = 0;
+        config.sponsorship_bps = 0;
+        config.sponsorship_period = 7 * 86_400;
+        config.unsponsored_slash_bps = 0;
+        config.vesting_boost_bps = 10_000;
+        config.same_slot_guard = false;
+        config.bump = ctx.bumps.dao_config;
+        Ok(())
+    }
+
+    /// Return the proposal deposit to its creator once results are revealed, or
+    /// slash it to the treasury when quorum was missed and slashing is enabled.
+    /// Proposals that expired unsponsored forfeit `unsponsored_slash_bps`.
+    ///
+    /// Under the graded schedule a partial slash splits the escrow: the
+    /// creator's share goes to `destination` and the forfeited share to
+    /// `treasury_destination`.
+    pub fn settle_deposit(ctx: Context<SettleDeposit>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(
+            proposal.is_revealed || proposal.sponsorship_expired,
+            VotingError::NotYetRevealed
+        );
+        require!(
+            !proposal.deposit_returned,
+            VotingError::DepositAlreadyProcessed
+        );
+        require!(!proposal.bond_in_sol, VotingError::WrongDepositMode);
+
+        let amount = ctx.accounts.deposit_escrow.amount;
+        let slash_bps = deposit_slash_bps(&ctx.accounts.dao_config, proposal);
+        let slashed_amount = (amount as u128 * slash_bps as u128 / 10_000) as u64;
+        let returned_amount = amount - slashed_amount;
+
+        // Full slash keeps the single-destination form: `destination` is the treasury's
+        let (return_to, slash_to) = if returned_amount == 0 {
+            (None, Some(&ctx.accounts.destination))
+        } else if slashed_amount == 0 {
+            (Some(&ctx.accounts.destination), None)
+        } else {
+            let treasury_destination = ctx
+                .accounts
+                .treasury_destination
+                .as_ref()
+                .ok_or(VotingError::InvalidRecipient)?;
+            (Some(&ctx.accounts.destination), Some(treasury_destination))
+        };
+        if let Some(to) = return_to {
+            require!(
+                to.owner == proposal.authority,
+                VotingError::InvalidRecipient
+            );
+        }
+        if let Some(to) = slash_to {
+            require!(
+                to.owner == ctx.accounts.dao_config.treasury,
+                VotingError::InvalidRecipient
+            );
+        }
+
+        let id_bytes = proposal.id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[PROPOSAL_SEED, id_bytes.as_ref(), &[proposal.bump]]];
+        for (to, share) in [(return_to, returned_amount), (slash_to, slashed_amount)] {
+            let Some(to) = to else { continue };
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.deposit_escrow.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: ctx.accounts.proposal.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                share,
+            )?;
+        }
+
+        ctx.accounts.proposal.deposit_returned = true;
+
+        emit!(DepositSettled {
+            proposal: ctx.accounts.proposal.key(),
+            amount,
+            slashed: slashed_amount > 0,
+            slashed_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Lamport-bond counterpart of `settle_deposit`: returns the bond to the
+    /// creator or routes the slashed share (same schedule) to the SOL treasury.
+    /// A partial slash leaves the creator at least the rent-exempt minimum, so
+    /// the PDA never sits below rent between the two transfers.
+    pub fn settle_sol_bond(ctx: Context<SettleSolBond>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(
+            proposal.is_revealed || proposal.sponsorship_expired,
+            VotingError::NotYetRevealed
+        );
+        require!(
+            !proposal.deposit_returned,
+            VotingError::DepositAlreadyProcessed
+        );
+        require!(proposal.bond_in_sol, VotingError::WrongDepositMode);
+
+        let amount = ctx.accounts.sol_bond.lamports();
+        let slash_bps = deposit_slash_bps(&ctx.accounts.dao_config, proposal);
+        let mut slashed_amount = (amount as u128 * slash_bps as u128 / 10_000) as u64;
+        if slashed_amount < amount {
+            slashed_amount =
+                slashed_amount.min(amount.saturating_sub(Rent::get()?.minimum_balance(0)));
+        }
+        let returned_amount = amount - slashed_amount;
+
+        let proposal_key = proposal.key();
+        let signer_seeds: &[&[&[u8]]] =
+            &[&[SOL_BOND_SEED, proposal_key.as_ref(), &[ctx.bumps.sol_bond]]];
+        for (to, share) in [
+            (ctx.accounts.sol_treasury.to_account_info(), slashed_amount),
+            (ctx.accounts.creator.to_account_info(), returned_amount),
+        ] {
+            if share == 0 {
+                continue;
+            }
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.sol_bond.to_account_info(),
+                        to,
+                    },
+                    signer_seeds,
+                ),
+                share,
+            )?;
+        }
+
+        ctx.accounts.proposal.deposit_returned = true;
+
+        emit!(DepositSettled {
+            proposal: proposal_key,
+            amount,
+            slashed: slashed_amount > 0,
+            slashed_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Create a committee under the DAO with its own proposer set and budget.
+    /// Committee proposals spend from the committee sub-vault (token accounts
+    /// owned by the `["committee_vault", committee]` PDA), capped by `spending_cap`.
+    pub fn create_committee(
+        ctx: Context<CreateCommittee>,
+        committee_id: u64,
+        name: String,
+        proposers: Vec<Pubkey>,
+        budget_mint: Pubkey,
+        spending_cap: u64,
+    ) -> Result<()> {
+        require!(
+            proposers.len() <= MAX_COMMITTEE_PROPOSERS,
+            VotingError::TooManyProposers
+        );
+
+        let committee = &mut ctx.accounts.committee;
+        committee.id = committee_id;
+        committee.dao_config = ctx.accounts.dao_config.key();
+        committee.name = name;
+        committee.proposers = proposers;
+        committee.budget_mint = budget_mint;
+        committee.spending_cap = spending_cap;
+        committee.spent = 0;
+        committee.bump = ctx.bumps.committee;
+
+        emit!(CommitteeCreated {
+            committee: committee.key(),
+            committee_id,
+            budget_mint,
+            spending_cap,
+        });
+
+        Ok(())
+    }
+
+    /// Attach an ordered list of execution actions to a proposal. Only the
+    /// proposal authority can set it, and only before any vote is cast so voters
+    /// always see the exact actions they are voting on. The payload cannot be
+    /// changed afterwards.
+    pub fn set_execution_payload(
+        ctx: Context<SetExecutionPayload>,
+        actions: Vec<ProposalAction>,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(
+            ctx.accounts.authority.key() == proposal.authority,
+            VotingError::Unauthorized
+        );
+        require!(
+            proposal.kind == ProposalKind::Binding,
+            VotingError::SignalingProposal
+        );
+        require!(
+            proposal.is_active && proposal.ballots_cast == 0,
+            VotingError::PayloadLocked
+        );
+
+        require!(
+            !actions.is_empty() && actions.len() <= MAX_PROPOSAL_ACTIONS,
+            VotingError::InvalidActionCount
+        );
+
+        for action in &actions {
+            match action {
+                ProposalAction::Invoke {
+                    program_id,
+                    accounts,
+                    data,
+                } => {
+                    require_allowed_program(&ctx.accounts.dao_config, program_id)?;
+                    require!(
+                        accounts.len() <= MAX_INVOKE_ACCOUNTS && data.len() <= MAX_INVOKE_DATA,
+                        VotingError::InvalidInvokeAccounts
+                    );
+                }
+                ProposalAction::StreamPayment {
+                    amount, duration, ..
+                } => {
+                    require!(*amount > 0 && *duration > 0, VotingError::InvalidStream);
+                }
+                ProposalAction::PostObligation { data, .. } => {
+                    require!(
+                        data.len() <= MAX_OBLIGATION_DATA,
+                        VotingError::InvalidObligation
+                    );
+                    require!(
+                        ctx.accounts.dao_config.outbox_executor != Pubkey::default(),
+                        VotingError::NoOutboxExecutor
+                    );
+                }
+                ProposalAction::MilestoneGrant {
+                    tranches,
+                    milestone_period,
+                    ..
+                } => {
+                    require!(
+                        !tranches.is_empty()
+                            && tranches.len() <= MAX_GRANT_TRANCHES
+                            && tranches.iter().all(|tranche| *tranche > 0)
+                            && *milestone_period > 0,
+                        VotingError::InvalidGrant
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        // Committee proposals may only spend their SPL budget; budget changes
+        // and native SOL movements are reserved for parent-DAO proposals
+        if proposal.committee.is_some() {
+            require!(
+                actions
+                    .iter()
+                    .all(|action| matches!(action, ProposalAction::TokenTransfer { .. })),
+                VotingError::CommitteeScopeViolation
+            );
+        }
+
+        let payload = &mut ctx.accounts.execution_payload;
+        payload.proposal = proposal.key();
+        payload.actions = actions;
+        payload.actions_executed = 0;
+        payload.validated_at = 0;
+        payload.bump = ctx.bumps.execution_payload;
+
+        Ok(())
+    }
+
+    /// Dry-check a proposal's execution payload against current state, so voters
+    /// can trust it is at least structurally executable: every referenced
+    /// account exists under an allowed owner (SPL Token or this program), token
+    /// spends fit their source vault (and committee budget), lamport spends
+    /// fit the SOL treasury and `Invoke` targets are allowlisted.
+    /// Permissionless; records `validated_at`.
+    ///
+    /// `remaining_accounts`, per action in order: TokenTransfer
+    /// `[source_vault, recipient]`, MintTokens `[mint, recipient]`,
+    /// StreamPayment `[source_vault, recipient]`, MilestoneGrant `[source_vault]`,
+    /// ReleaseTranche `[grant]`, SetCommitteeBudget `[committee]`,
+    /// CancelExecution `[target_proposal]`.
+    pub fn validate_payload(ctx: Context<ValidatePayload>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let mut remaining = ctx.remaining_accounts.iter();
+        let mut next = || remaining.next().ok_or(VotingError::MissingExecutionAccount);
+        let mut token_spends: Vec<(Pubkey, u64)> = Vec::new();
+        let mut budget_spend: u64 = 0;
+        let mut lamport_spend: u64 = 0;
+
+        for action in &ctx.accounts.execution_payload.actions {
+            match action {
+                ProposalAction::TokenTransfer {
+                    mint,
+                    recipient,
+                    amount,
+                } => {
+                    let source_info = next()?;
+                    let source = token_account_at(source_info)?;
+                    require!(source.mint == *mint, VotingError::InvalidTokenMint);
+                    let (vault_authority, _) = match proposal.committee {
+                        Some(committee) => Pubkey::find_program_address(
+                            &[COMMITTEE_VAULT_SEED, committee.as_ref()],
+                            ctx.program_id,
+                        ),
+                        None => Pubkey::find_program_address(&[TREASURY_SEED], ctx.program_id),
+                    };
+                    require!(
+                        source.owner == vault_authority,
+                        VotingError::InvalidTreasuryVault
+                    );
+                    let destination_info = next()?;
+                    require!(
+                        destination_info.key() == *recipient,
+                        VotingError::InvalidRecipient
+                    );
+                    require!(
+                        token_account_at(destination_info)?.mint == *mint,
+                        VotingError::InvalidTokenMint
+                    );
+
+                    let spent = accumulate_spend(&mut token_spends, source_info.key(), *amount)?;
+                    require!(spent <= source.amount, VotingError::PayloadExceedsTreasury);
+                    budget_spend = budget_spend
+                        .checked_add(*amount)
+                        .ok_or(VotingError::ArithmeticOverflow)?;
+                }
+                ProposalAction::MintTokens {
+                    mint, recipient, ..
+                } => {
+                    let mint_info = next()?;
+                    require!(
+                        mint_info.key() == *mint && *mint_info.owner == token::ID,
+                        VotingError::InvalidTokenMint
+                    );
+                    let mint_account =
+                        Mint::try_deserialize(&mut &mint_info.try_borrow_data()?[..])?;
+                    let (mint_authority, _) =
+                        Pubkey::find_program_address(&[MINT_AUTHORITY_SEED], ctx.program_id);
+                    require!(
+                        mint_account.mint_authority == COption::Some(mint_authority),
+                        VotingError::InvalidMintAuthority
+                    );
+                    let destination_info = next()?;
+                    require!(
+                        destination_info.key() == *recipient,
+                        VotingError::InvalidRecipient
+                    );
+                    require!(
+                        token_account_at(destination_info)?.mint == *mint,
+                        VotingError::InvalidTokenMint
+                    );
+                }
+                ProposalAction::SetCommitteeBudget { committee, .. } => {
+                    let info = next()?;
+                    require!(
+                        info.key() == *committee && *info.owner == crate::ID,
+                        VotingError::CommitteeScopeViolation
+                    );
+                    Committee::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+                }
+                ProposalAction::CancelExecution { proposal: target } => {
+                    let info = next()?;
+                    require!(
+                        info.key() == *target && *info.owner == crate::ID,
+                        VotingError::InvalidTargetProposal
+                    );
+                    Proposal::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+                }
+                ProposalAction::SolTransfer { lamports, .. } => {
+                    lamport_spend = lamport_spend
+                        .checked_add(*lamports)
+                        .ok_or(VotingError::ArithmeticOverflow)?;
+                }
+                ProposalAction::FundRentExempt { space, .. } => {
+                    lamport_spend = lamport_spend
+                        .checked_add(Rent::get()?.minimum_balance(*space as usize))
+                        .ok_or(VotingError::ArithmeticOverflow)?;
+                }
+                ProposalAction::StreamPayment {
+                    mint,
+                    recipient,
+                    amount,
+                    ..
+                } => {
+                    let source_info = next()?;
+                    let source = token_account_at(source_info)?;
+                    let (treasury_authority, _) =
+                        Pubkey::find_program_address(&[TREASURY_SEED], ctx.program_id);
+                    require!(source.mint == *mint, VotingError::InvalidTokenMint);
+                    require!(
+                        source.owner == treasury_authority,
+                        VotingError::InvalidTreasuryVault
+                    );
+                    let spent = accumulate_spend(&mut token_spends, source_info.key(), *amount)?;
+                    require!(spent <= source.amount, VotingError::PayloadExceedsTreasury);
+                    let destination_info = next()?;
+                    require!(
+                        destination_info.key() == *recipient,
+                        VotingError::InvalidRecipient
+                    );
+                    require!(
+                        token_account_at(destination_info)?.mint == *mint,
+                        VotingError::InvalidTokenMint
+                    );
+                }
+                ProposalAction::MilestoneGrant { mint, tranches, .. } => {
+                    let source_info = next()?;
+                    let source = token_account_at(source_info)?;
+                    let (treasury_authority, _) =
+                        Pubkey::find_program_address(&[TREASURY_SEED], ctx.program_id);
+                    require!(source.mint == *mint, VotingError::InvalidTokenMint);
+                    require!(
+                        source.owner == treasury_authority,
+                        VotingError::InvalidTreasuryVault
+                    );
+                    let total = tranches
+                        .iter()
+                        .try_fold(0u64, |sum, tranche| sum.checked_add(*tranche))
+                        .ok_or(VotingError::ArithmeticOverflow)?;
+                    let spent = accumulate_spend(&mut token_spends, source_info.key(), total)?;
+                    require!(spent <= source.amount, VotingError::PayloadExceedsTreasury);
+                }
+                ProposalAction::ReleaseTranche { grant } => {
+                    let info = next()?;
+                    require!(
+                        info.key() == *grant && *info.owner == crate::ID,
+                        VotingError::InvalidGrant
+                    );
+                    Grant::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+                }
+                ProposalAction::Invoke { program_id, .. } => {
+                    require_allowed_program(&ctx.accounts.dao_config, program_id)?;
+                }
+                ProposalAction::UpdateConfig { .. } | ProposalAction::PostObligation { .. } => {}
+            }
+        }
+
+        if let Some(committee_key) = proposal.committee {
+            let committee = ctx
+                .accounts
+                .committee
+                .as_ref()
+                .ok_or(VotingError::MissingExecutionAccount)?;
+            require!(
+                committee.key() == committee_key,
+                VotingError::CommitteeScopeViolation
+            );
+            require!(
+                committee.spent.saturating_add(budget_spend) <= committee.spending_cap,
+                VotingError::CommitteeBudgetExceeded
+            );
+        }
+        if lamport_spend > 0 {
+            let treasury_floor = Rent::get()?.minimum_balance(0);
+            require!(
+                ctx.accounts.sol_treasury.lamports()
+                    >= lamport_spend.saturating_add(treasury_floor),
+                VotingError::PayloadExceedsTreasury
+            );
+        }
+
+        let payload = &mut ctx.accounts.execution_payload;
+        payload.validated_at = Clock::get()?.unix_timestamp;
+
+        emit!(PayloadValidated {
+            proposal: proposal.key(),
+            actions: payload.actions.len() as u8,
+        });
+        Ok(())
+    }
+
+    /// Council sign-off on a grant milestone: release the next tranche.
+    pub fn release_grant_tranche(ctx: Context<ReleaseGrantTranche>) -> Result<()> {
+        require!(
+            ctx.accounts.dao_config.council != Pubkey::default(),
+            VotingError::Unauthorized
+        );
+        release_next_tranche(
+            &mut ctx.accounts.grant,
+            &ctx.accounts.grant_escrow,
+            &ctx.accounts.recipient_token_account,
+            &ctx.accounts.token_program,
+        )
+    }
+
+    /// Return a grant's unreleased funds to the treasury once its next
+    /// milestone has lapsed. Permissionless.
+    pub fn clawback_grant(ctx: Context<ClawbackGrant>) -> Result<()> {
+        let grant = &ctx.accounts.grant;
+        require!(
+            !grant.clawed_back && (grant.released as usize) < grant.tranches.len(),
+            VotingError::GrantClosed
+        );
+        require!(
+            Clock::get()?.unix_timestamp > tranche_deadline(grant, grant.released),
+            VotingError::MilestoneNotLapsed
+        );
+
+        let amount = ctx.accounts.grant_escrow.amount;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            GRANT_SEED,
+            grant.proposal.as_ref(),
+            &[grant.index],
+            &[grant.bump],
+        ]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.grant_escrow.to_account_info(),
+                    to: ctx.accounts.treasury_vault.to_account_info(),
+                    authority: ctx.accounts.grant.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        ctx.accounts.grant.clawed_back = true;
+
+        emit!(GrantClawedBack {
+            grant: ctx.accounts.grant.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Pay out the vested part of a `StreamPayment` from the stream escrow.
+    /// Vesting is linear from execution to `end_at`. Permissionless.
+    pub fn claim_stream(ctx: Context<ClaimStream>) -> Result<()> {
+        let stream = &mut ctx.accounts.stream;
+        let now = Clock::get()?.unix_timestamp.min(stream.end_at);
+        let vested = (stream.total as u128 * (now - stream.start_at).max(0) as u128
+            / (stream.end_at - stream.start_at) as u128) as u64;
+        let amount = vested - stream.claimed;
+        require!(amount > 0, VotingError::NothingVested);
+        stream.claimed = vested;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            STREAM_SEED,
+            stream.proposal.as_ref(),
+            &[stream.index],
+            &[stream.bump],
+        ]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.stream_escrow.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: stream.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(StreamClaimed {
+            stream: stream.key(),
+            amount,
+            claimed: stream.claimed,
+        });
+        Ok(())
+    }
+
+    /// Execute the next pending action of a passed proposal after its timelock
+    /// has elapsed. Actions run in order, one per call, to stay within compute
+    /// limits; a failed action leaves the cursor unchanged so it can be retried.
+    /// Permissionless: any cranker can trigger execution once the delay passes.
+    pub fn execute_next_action<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(proposal.is_revealed, VotingError::NotYetRevealed);
+        require!(proposal.passed, VotingError::ProposalNotPassed);
+        require!(!proposal.executed, VotingError::AlreadyExecuted);
+        require!(!proposal.cancelled, VotingError::ExecutionCancelled);
+        require!(!proposal.flagged, VotingError::ProposalHalted);
+        require!(proposal.finalized_at > 0, VotingError::NotFinalized);
+        require!(
+            Clock::get()?.unix_timestamp >= executable_at(proposal)?,
+            VotingError::TimelockNotElapsed
+        );
+
+        let index = ctx.accounts.execution_payload.actions_executed as usize;
+        let action = ctx.accounts.execution_payload.actions[index].clone();
+        execute_action(ctx.accounts, ctx.program_id, ctx.remaining_accounts, action)?;
+
+        let payload = &mut ctx.accounts.execution_payload;
+        payload.actions_executed += 1;
+        let remaining = payload.actions.len() - payload.actions_executed as usize;
+
+        let executor = ctx.accounts.executor.key();
+        emit!(ActionExecuted {
+            proposal: ctx.accounts.proposal.key(),
+            index: index as u8,
+            remaining: remaining as u8,
+            executor,
+        });
+
+        if remaining == 0 {
+            // Record who completed execution and when, for accountability and
+            // downstream incentive payouts to execution bots
+            let clock = Clock::get()?;
+            let proposal = &mut ctx.accounts.proposal;
+            proposal.executed = true;
+            proposal.executed_by = executor;
+            proposal.executed_slot = clock.slot;
+            proposal.executed_at = clock.unix_timestamp;
+
+            emit!(ProposalExecuted {
+                proposal: proposal.key(),
+                executor,
+                slot: clock.slot,
+                executed_at: clock.unix_timestamp,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Create the DAO's `Outbox` sequencing off-chain obligations.
+    /// Permissionless.
+    pub fn init_outbox(ctx: Context<InitOutbox>) -> Result<()> {
+        let outbox = &mut ctx.accounts.outbox;
+        outbox.next_seq = 0;
+        outbox.bump = ctx.bumps.outbox;
+        Ok(())
+    }
+
+    /// Mark an obligation as carried out. Signed by `DaoConfig.outbox_executor`.
+    pub fn acknowledge_obligation(ctx: Context<AcknowledgeObligation>) -> Result<()> {
+        let obligation = &mut ctx.accounts.obligation;
+        require!(
+            obligation.acknowledged_at == 0,
+            VotingError::UnknownObligation
+        );
+        obligation.acknowledged_at = Clock::get()?.unix_timestamp;
+
+        emit!(ObligationAcknowledged {
+            seq: obligation.seq,
+            proposal: obligation.proposal,
+            executor: ctx.accounts.executor.key(),
+        });
+        Ok(())
+    }
+
+    /// Council safety valve: cancel a passed proposal's execution while it is
+    /// still in its `execution_delay` window (e.g. a malicious payload was
+    /// discovered after the vote). A supermajority follow-up proposal can do
+    /// the same through a `CancelExecution` action.
+    pub fn cancel_execution(ctx: Context<CancelExecution>) -> Result<()> {
+        let council = ctx.accounts.council.key();
+        require!(
+            ctx.accounts.dao_config.council != Pubkey::default()
+                && ctx.accounts.dao_config.council == council,
+            VotingError::Unauthorized
+        );
+
+        cancel_queued_execution(&mut ctx.accounts.proposal, council)
+    }
+
+    /// Create the DAO's governance-power mint. Power tokens are minted 1:1 for
+    /// deposited assets, whole token for whole token whatever each asset's
+    /// decimals, and are meant to be used as the proposals' `gate_mint`.
+    /// The power authority PDA is both mint and freeze authority.
+    pub fn init_governance_mint(ctx: Context<InitGovernanceMint>, _decimals: u8) -> Result<()> {
+        ctx.accounts.dao_config.governance_mint = ctx.accounts.governance_mint.key();
+        Ok(())
+    }
+
+    /// Accept a new asset (community token, LP token, ...) as backing for
+    /// governance power. Deposits are held in a vault owned by the power authority.
+    pub fn register_power_asset(ctx: Context<RegisterPowerAsset>) -> Result<()> {
+        let asset = &mut ctx.accounts.power_asset;
+        asset.asset_mint = ctx.accounts.asset_mint.key();
+        asset.vault = ctx.accounts.power_vault.key();
+        asset.total_deposited = 0;
+        asset.decimals = ctx.accounts.asset_mint.decimals;
+        asset.bump = ctx.bumps.power_asset;
+        Ok(())
+    }
+
+    /// Lock `amount` of a registered asset and mint the same value of
+    /// non-transferable governance power, rescaled from the asset's decimals
+    /// to the power mint's, to the depositor.
+    pub fn deposit_for_power(ctx: Context<DepositForPower>, amount: u64) -> Result<()> {
+        let power = normalize_decimals(
+            amount,
+            ctx.accounts.power_asset.decimals,
+            ctx.accounts.governance_mint.decimals,
+        )?;
+        // Dust below one power base unit would lock assets for nothing
+        require!(power > 0, VotingError::InvalidAmount);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner_asset_account.to_account_info(),
+                    to: ctx.accounts.power_vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let bump = ctx.bumps.power_authority;
+        if ctx.accounts.owner_power_account.is_frozen() {
+            set_power_account_frozen(
+                &ctx.accounts.token_program,
+                &ctx.accounts.owner_power_account,
+                &ctx.accounts.governance_mint,
+                &ctx.accounts.power_authority,
+                bump,
+                false,
+            )?;
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[POWER_AUTHORITY_SEED, &[bump]]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.governance_mint.to_account_info(),
+                    to: ctx.accounts.owner_power_account.to_account_info(),
+                    authority: ctx.accounts.power_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            power,
+        )?;
+
+        set_power_account_frozen(
+            &ctx.accounts.token_program,
+            &ctx.accounts.owner_power_account,
+            &ctx.accounts.governance_mint,
+            &ctx.accounts.power_authority,
+            bump,
+            true,
+        )?;
+
+        let deposit = &mut ctx.accounts.power_deposit;
+        deposit.owner = ctx.accounts.owner.key();
+        deposit.asset_mint = ctx.accounts.power_asset.asset_mint;
+        deposit.amount = deposit
+            .amount
+            .checked_add(amount)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+        deposit.power = deposit
+            .power
+            .checked_add(power)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+        deposit.bump = ctx.bumps.power_deposit;
+
+        let asset = &mut ctx.accounts.power_asset;
+        asset.total_deposited = asset
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+
+        emit!(PowerDeposited {
+            owner: ctx.accounts.owner.key(),
+            asset_mint: asset.asset_mint,
+            amount,
+            power,
+        });
+
+        Ok(())
+    }
+
+    /// Unlock `amount` of the underlying asset back to the depositor, burning
+    /// the matching share of the power it minted (rounded up, so partial
+    /// withdrawals can't leave power behind).
+    pub fn withdraw_power(ctx: Context<WithdrawPower>, amount: u64) -> Result<()> {
+        require!(amount > 0, VotingError::InvalidAmount);
+        let deposit = &ctx.accounts.power_deposit;
+        require!(deposit.amount >= amount, VotingError::InsufficientDeposit);
+        let power =
+            (deposit.power as u128 * amount as u128).div_ceil(deposit.amount as u128) as u64;
+
+        let bump = ctx.bumps.power_authority;
+        set_power_account_frozen(
+            &ctx.accounts.token_program,
+            &ctx.accounts.owner_power_account,
+            &ctx.accounts.governance_mint,
+            &ctx.accounts.power_authority,
+            bump,
+            false,
+        )?;
+
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.governance_mint.to_account_info(),
+                    from: ctx.accounts.owner_power_account.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            power,
+        )?;
+
+        set_power_account_frozen(
+            &ctx.accounts.token_program,
+            &ctx.accounts.owner_power_account,
+            &ctx.accounts.governance_mint,
+            &ctx.accounts.power_authority,
+            bump,
+            true,
+        )?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[POWER_AUTHORITY_SEED, &[bump]]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.power_vault.to_account_info(),
+                    to: ctx.accounts.owner_asset_account.to_account_info(),
+                    authority: ctx.accounts.power_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        let deposit = &mut ctx.accounts.power_deposit;
+        deposit.amount -= amount;
+        deposit.power -= power;
+        let asset = &mut ctx.accounts.power_asset;
+        asset.total_deposited = asset
+            .total_deposited
+            .checked_sub(amount)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+
+        emit!(PowerWithdrawn {
+            owner: ctx.accounts.owner.key(),
+            asset_mint: asset.asset_mint,
+            amount,
+            power,
+        });
+
+        Ok(())
+    }
+
+    /// Lock `amount` tokens in a vesting position released linearly until
+    /// `end_at`. Locked and unwithdrawn tokens count toward Token gates of
+    /// that mint, with the locked part boosted by `vesting_boost_bps`.
+    pub fn lock_vesting(ctx: Context<LockVesting>, amount: u64, end_at: i64) -> Result<()> {
+        require!(amount > 0, VotingError::InvalidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        require!(end_at > now, VotingError::InvalidVestingPosition);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    to: ctx.accounts.vesting_vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let position = &mut ctx.accounts.vesting_position;
+        position.owner = ctx.accounts.owner.key();
+        position.mint = ctx.accounts.mint.key();
+        position.vault = ctx.accounts.vesting_vault.key();
+        position.total = amount;
+        position.withdrawn = 0;
+        position.start_at = now;
+        position.end_at = end_at;
+        position.bump = ctx.bumps.vesting_position;
+
+        emit!(VestingLocked {
+            owner: position.owner,
+            mint: position.mint,
+            amount,
+            end_at,
+        });
+        Ok(())
+    }
+
+    /// Withdraw the released part of a vesting position. The position and its
+    /// vault are closed once fully withdrawn.
+    pub fn withdraw_vested(ctx: Context<WithdrawVested>) -> Result<()> {
+        let position = &ctx.accounts.vesting_position;
+        let amount = vested_amount(position, Clock::get()?.unix_timestamp) - position.withdrawn;
+        require!(amount > 0, VotingError::NothingVested);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            VESTING_SEED,
+            position.owner.as_ref(),
+            position.mint.as_ref(),
+            &[position.bump],
+        ]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vesting_vault.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: position.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        let withdrawn = position.withdrawn + amount;
+        emit!(VestingWithdrawn {
+            owner: position.owner,
+            mint: position.mint,
+            amount,
+        });
+        if withdrawn == position.total {
+            token::close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: ctx.accounts.vesting_vault.to_account_info(),
+                    destination: ctx.accounts.owner.to_account_info(),
+                    authority: position.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+            return ctx
+                .accounts
+                .vesting_position
+                .close(ctx.accounts.owner.to_account_info());
+        }
+        ctx.accounts.vesting_position.withdrawn = withdrawn;
+        Ok(())
+    }
+
+    /// Permissionless reminder crank. Within `reminder_window` of the voting
+    /// deadline, and at most once per `reminder_interval`, emits
+    /// `DeadlineApproaching` and pays the cranker `crank_reward` lamports from
+    /// the SOL treasury (skipped if the treasury cannot cover it).
+    pub fn ping_deadline(ctx: Context<PingDeadline>) -> Result<()> {
+        let config = &ctx.accounts.dao_config;
+        let proposal = &mut ctx.accounts.proposal;
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        require!(
+            proposal.is_active && voting_open(proposal, &clock),
+            VotingError::VotingEnded
+        );
+
+        // A paid crank with no interval could be drained in one slot
+        require!(
+            config.crank_reward == 0 || config.reminder_interval > 0,
+            VotingError::InvalidReminderInterval
+        );
+        let seconds_remaining = seconds_until_deadline(proposal, &clock);
+        require!(
+            config.reminder_window > 0 && seconds_remaining <= config.reminder_window,
+            VotingError::ReminderTooEarly
+        );
+        require!(
+            proposal.last_ping_at == 0 || now - proposal.last_ping_at >= config.reminder_interval,
+            VotingError::ReminderTooSoon
+        );
+        proposal.last_ping_at = now;
+
+        let reward = config.crank_reward;
+        let treasury_floor = Rent::get()?.minimum_balance(0);
+        let treasury_lamports = ctx.accounts.sol_treasury.lamports();
+        if reward > 0 && treasury_lamports >= reward.saturating_add(treasury_floor) {
+            let signer_seeds: &[&[&[u8]]] = &[&[SOL_TREASURY_SEED, &[ctx.bumps.sol_treasury]]];
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.sol_treasury.to_account_info(),
+                        to: ctx.accounts.cranker.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                reward,
+            )?;
+        }
+
+        emit!(DeadlineApproaching {
+            proposal: ctx.accounts.proposal.key(),
+            seconds_remaining,
+            total_votes: ctx.accounts.proposal.total_votes,
+            quorum: ctx.accounts.proposal.quorum,
+            cranker: ctx.accounts.cranker.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Pay lamports into the SOL treasury as DAO revenue. `revenue_share_bps`
+    /// of the inflow is credited to the current revenue epoch's voter pool.
+    pub fn deposit_revenue(ctx: Context<DepositRevenue>, epoch: u64, amount: u64) -> Result<()> {
+        let config = &ctx.accounts.dao_config;
+        let current = revenue_epoch_of(config, Clock::get()?.unix_timestamp)?;
+        require!(epoch == current, VotingError::NotCurrentRevenueEpoch);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.sol_treasury.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let shared = (amount as u128 * config.revenue_share_bps as u128 / 10_000) as u64;
+        let revenue_epoch = &mut ctx.accounts.revenue_epoch;
+        revenue_epoch.epoch = epoch;
+        revenue_epoch.revenue = revenue_epoch
+            .revenue
+            .checked_add(shared)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+        revenue_epoch.bump = ctx.bumps.revenue_epoch;
+
+        emit!(RevenueDeposited {
+            epoch,
+            amount,
+            shared,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: count a proposal revealed during `epoch` towards that
+    /// epoch's participation, so its voters can claim from the pool. Every
+    /// ballot cast is one claimable share, whether or not its computation was
+    /// tallied. The epoch takes on the DAO's current turnout-boost schedule
+    /// and match budget. Closes once claims for the epoch open.
+    pub fn register_revenue_proposal(
+        ctx: Context<RegisterRevenueProposal>,
+        epoch: u64,
+    ) -> Result<()> {
+        let config = &ctx.accounts.dao_config;
+        let proposal = &ctx.accounts.proposal;
+        require!(proposal.is_revealed, VotingError::NotYetRevealed);
+        require!(
+            revenue_epoch_of(config, proposal.revealed_at)? == epoch,
+            VotingError::ProposalNotInEpoch
+        );
+        require!(
+            Clock::get()?.unix_timestamp < revenue_claims_open_at(config, epoch)?,
+            VotingError::EpochRegistrationClosed
+        );
+
+        let revenue_epoch = &mut ctx.accounts.revenue_epoch;
+        require!(
+            !revenue_epoch.proposals.contains(&proposal.key()),
+            VotingError::ProposalAlreadyRegistered
+        );
+        require!(
+            revenue_epoch.proposals.len() < MAX_EPOCH_PROPOSALS,
+            VotingError::EpochProposalsFull
+        );
+        revenue_epoch.epoch = epoch;
+        revenue_epoch.boosts = config.turnout_boosts.clone();
+        revenue_epoch.match_budget = config.turnout_match_budget;
+        revenue_epoch.proposals.push(proposal.key());
+        revenue_epoch
+            .turnouts
+            .push(turnout_bps(proposal, proposal.total_weight));
+        revenue_epoch.total_participation = revenue_epoch
+            .total_participation
+            .checked_add(proposal.ballots_cast)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+        revenue_epoch.bump = ctx.bumps.revenue_epoch;
+
+        Ok(())
+    }
+
+    /// Claim one participation share of `epoch`'s revenue pool for a
+    /// `VoteRecord` on a registered proposal. Each (voter, proposal) pair is
+    /// marked in the voter's claim bitmap, indexed by registration order.
+    ///
+    /// For a ballot cast with delegated power, `delegator_reward_share_bps` of
+    /// the share is split evenly over the `delegators` folded into it.
+    /// `remaining_accounts` holds `[delegator_vote_record, delegator]` pairs;
+    /// the portion of any omitted delegator stays in the treasury rather than
+    /// going to the delegate. Folded records (including the delegate
+    /// fallback's) are never claimed on their own, so each ballot is paid
+    /// once, and base shares never add up to more than the epoch's revenue.
+    ///
+    /// If the proposal's turnout reached a tier of the epoch's turnout-boost
+    /// schedule, the share is first matched from the treasury at that tier's
+    /// multiplier, limited by what is left of the epoch's match budget.
+    pub fn claim_revenue<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimRevenue<'info>>,
+        epoch: u64,
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp
+                >= revenue_claims_open_at(&ctx.accounts.dao_config, epoch)?,
+            VotingError::EpochClaimsNotOpen
+        );
+
+        let revenue_epoch = &ctx.accounts.revenue_epoch;
+        let proposal = ctx.accounts.vote_record.proposal;
+        require!(
+            ctx.accounts.vote_record.folded_into == Pubkey::default(),
+            VotingError::BallotFolded
+        );
+        let index = revenue_epoch
+            .proposals
+            .iter()
+            .position(|registered| *registered == proposal)
+            .ok_or(VotingError::ProposalNotInEpoch)?;
+        let bit = 1u64 << index;
+        require!(
+            ctx.accounts.revenue_claim.claimed & bit == 0,
+            VotingError::RevenueAlreadyClaimed
+        );
+
+        let base_paid = revenue_epoch.claimed.saturating_sub(revenue_epoch.matched);
+        let base = revenue_epoch
+            .revenue
+            .checked_div(revenue_epoch.total_participation)
+            .map_or(0, |share| {
+                share.min(revenue_epoch.revenue.saturating_sub(base_paid))
+            });
+        let multiplier = turnout_multiplier(&revenue_epoch.boosts, revenue_epoch.turnouts[index]);
+        let matched = ((base as u128 * multiplier as u128 / 10_000) as u64)
+            .saturating_sub(base)
+            .min(
+                revenue_epoch
+                    .match_budget
+                    .saturating_sub(revenue_epoch.matched),
+            );
+        let amount = base + matched;
+        let vote_record = &ctx.accounts.vote_record;
+        let delegator_pool = if vote_record.delegators > 0 {
+            (amount as u128 * ctx.accounts.dao_config.delegator_reward_share_bps as u128 / 10_000)
+                as u64
+        } else {
+            0
+        };
+        let per_delegator = if delegator_pool > 0 {
+            delegator_pool / vote_record.delegators as u64
+        } else {
+            0
+        };
+
+        let pairs = ctx.remaining_accounts.chunks_exact(2);
+        require!(
+            pairs.remainder().is_empty() && pairs.len() <= vote_record.delegators as usize,
+            VotingError::InvalidDelegationBatch
+        );
+        let mut payouts: Vec<(AccountInfo<'info>, u64)> = vec![(
+            ctx.accounts.voter.to_account_info(),
+            amount - delegator_pool,
+        )];
+        let mut shared: Vec<Pubkey> = Vec::new();
+        for accounts in pairs {
+            require!(
+                *accounts[0].owner == crate::ID,
+                VotingError::InvalidDelegation
+            );
+            let folded = VoteRecord::try_deserialize(&mut &accounts[0].try_borrow_data()?[..])?;
+            let (expected, _) = Pubkey::find_program_address(
+                &[VOTE_RECORD_SEED, proposal.as_ref(), folded.voter.as_ref()],
+                ctx.program_id,
+            );
+            // Folded by `cast_delegated_vote` with this very ballot; fallback
+            // records are written after the deadline and share nothing
+            require!(
+                accounts[0].key() == expected
+                    && folded.folded_into == vote_record.voter
+                    && folded.voted_at == vote_record.voted_at
+                    && accounts[1].key() == folded.voter
+                    && !shared.contains(&folded.voter),
+                VotingError::InvalidDelegation
+            );
+            shared.push(folded.voter);
+            payouts.push((accounts[1].clone(), per_delegator));
+        }
+
+        let paid: u64 = payouts.iter().map(|(_, share)| share).sum();
+        let treasury_floor = Rent::get()?.minimum_balance(0);
+        require!(
+            ctx.accounts.sol_treasury.lamports() >= paid.saturating_add(treasury_floor),
+            VotingError::InsufficientTreasury
+        );
+        let signer_seeds: &[&[&[u8]]] = &[&[SOL_TREASURY_SEED, &[ctx.bumps.sol_treasury]]];
+        for (to, share) in payouts {
+            if share == 0 {
+                continue;
+            }
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.sol_treasury.to_account_info(),
+                        to,
+                    },
+                    signer_seeds,
+                ),
+                share,
+            )?;
+        }
+
+        if !shared.is_empty() {
+            emit!(DelegatorRewardsShared {
+                epoch,
+                delegate: ctx.accounts.voter.key(),
+                proposal,
+                delegators: shared.len() as u8,
+                amount: per_delegator * shared.len() as u64,
+            });
+        }
+
+        let claim = &mut ctx.accounts.revenue_claim;
+        claim.epoch = epoch;
+        claim.voter = ctx.accounts.voter.key();
+        claim.claimed |= bit;
+        claim.bump = ctx.bumps.revenue_claim;
+        let revenue_epoch = &mut ctx.accounts.revenue_epoch;
+        revenue_epoch.claimed = revenue_epoch.claimed.saturating_add(paid);
+        revenue_epoch.matched = revenue_epoch.matched.saturating_add(matched);
+
+        emit!(RevenueClaimed {
+            epoch,
+            voter: ctx.accounts.voter.key(),
+            proposal,
+            amount: amount - delegator_pool,
+            matched,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless crank: fold a batch of finished `VoteRecord`s into the
+    /// proposal's `vote_records_root` and close them, refunding rent to voters.
+    ///
+    /// `remaining_accounts` holds `[vote_record, voter]` pairs. Each leaf is
+    /// `sha256(voter || voted_at || encrypted_choice || nonce)`; the batch root is
+    /// chained as `root = sha256(root || batch_root)` and emitted, so a voter can
+    /// still prove participation from the `VoteRecordsCompacted` event log.
+    pub fn compact_vote_records(ctx: Context<CompactVoteRecords>) -> Result<()> {
+        let proposal_key = ctx.accounts.proposal.key();
+        let proposal = &ctx.accounts.proposal;
+        require!(proposal.is_revealed, VotingError::NotYetRevealed);
+        // Records stay available for prove_no_vote during the ragequit window
+        require!(
+            !proposal.passed || proposal.executed || proposal.cancelled,
+            VotingError::ExecutionPending
+        );
+
+        let pairs = ctx.remaining_accounts.chunks_exact(2);
+        require!(
+            pairs.len() > 0 && pairs.len() <= MAX_COMPACTION_BATCH && pairs.remainder().is_empty(),
+            VotingError::InvalidCompactionBatch
+        );
+
+        let mut leaves = Vec::with_capacity(pairs.len());
+        for pair in pairs {
+            let (record_info, voter_info) = (&pair[0], &pair[1]);
+            require!(
+                *record_info.owner == crate::ID,
+                VotingError::InvalidVoteRecord
+            );
+            let record = VoteRecord::try_deserialize(&mut &record_info.try_borrow_data()?[..])?;
+            require!(
+                record.proposal == proposal_key && record.voter == voter_info.key(),
+                VotingError::InvalidVoteRecord
+            );
+
+            leaves.push(
+                hashv(&[
+                    record.voter.as_ref(),
+                    &record.voted_at.to_le_bytes(),
+                    &record.encrypted_choice,
+                    &record.nonce,
+                ])
+                .to_bytes(),
+            );
+            close_program_account(record_info, voter_info)?;
+        }
+
+        let count = leaves.len() as u64;
+        let batch_root = merkle_root(leaves);
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.vote_records_root = hashv(&[&proposal.vote_records_root, &batch_root]).to_bytes();
+        proposal.compacted_records = proposal
+            .compacted_records
+            .checked_add(count)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+
+        emit!(VoteRecordsCompacted {
+            proposal: proposal_key,
+            batch_root,
+            root: proposal.vote_records_root,
+            count,
+            compacted_records: proposal.compacted_records,
+        });
+
+        Ok(())
+    }
+
+    /// Register the content CID of a translated proposal text for `locale`.
+    /// Append-only: each locale can be registered once and never changed.
+    pub fn register_translation(
+        ctx: Context<RegisterTranslation>,
+        locale: [u8; 8],
+        content_cid: String,
+    ) -> Result<()> {
+        validate_locale(&locale)?;
+        require!(
+            !content_cid.is_empty()
+                && content_cid.len() <= MAX_CID_LEN
+                && content_cid.bytes().all(|b| b.is_ascii_alphanumeric()),
+            VotingError::InvalidContentCid
+        );
+
+        let translation = &mut ctx.accounts.translation;
+        translation.proposal = ctx.accounts.proposal.key();
+        translation.locale = locale;
+        translation.content_cid = content_cid;
+        translation.registered_at = Clock::get()?.unix_timestamp;
+        translation.bump = ctx.bumps.translation;
+
+        emit!(TranslationRegistered {
+            proposal: translation.proposal,
+            locale,
+            content_cid: translation.content_cid.clone(),
+        });
+        Ok(())
+    }
+
+    /// Record an independent observer's attestation of the `[yes, no,
+    /// abstain, total]` counts and final tally ciphertext hash they verified
+    /// for a revealed proposal. Attestations that disagree with the proposal
+    /// are stored as disputes rather than rejected. Only observers registered
+    /// in `DaoConfig::observers` move the proposal's counters, so frontends
+    /// can show "verified by N observers". `report_cid` optionally points to
+    /// the observer's audit report.
+    pub fn attest_results(
+        ctx: Context<AttestResults>,
+        tally_hash: [u8; 32],
+        results: [u64; 4],
+        report_cid: String,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(proposal.is_revealed, VotingError::NotYetRevealed);
+        require!(
+            ctx.accounts.tally.proposal == proposal.key() && ctx.accounts.tally.shard == 0,
+            VotingError::WrongTallyShard
+        );
+        let matches = tally_hash == hashv(&[&ctx.accounts.tally.encrypted_data]).to_bytes()
+            && results
+                == [
+                    proposal.yes_votes,
+                    proposal.no_votes,
+                    proposal.abstain_votes,
+                    proposal.total_weight,
+                ];
+        require!(
+            report_cid.len() <= MAX_CID_LEN
+                && report_cid.bytes().all(|b| b.is_ascii_alphanumeric()),
+            VotingError::InvalidContentCid
+        );
+
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.proposal = proposal.key();
+        attestation.observer = ctx.accounts.observer.key();
+        attestation.tally_hash = tally_hash;
+        attestation.results = results;
+        attestation.report_cid = report_cid;
+        attestation.matches = matches;
+        attestation.attested_at = Clock::get()?.unix_timestamp;
+        attestation.bump = ctx.bumps.attestation;
+
+        let proposal = &mut ctx.accounts.proposal;
+        if ctx
+            .accounts
+            .dao_config
+            .observers
+            .contains(&attestation.observer)
+        {
+            let counter = if matches {
+                &mut proposal.attestations
+            } else {
+                &mut proposal.attestation_disputes
+            };
+            *counter = counter
+                .checked_add(1)
+                .ok_or(VotingError::ArithmeticOverflow)?;
+        }
+
+        emit!(ResultsAttested {
+            proposal: attestation.proposal,
+            observer: attestation.observer,
+            matches,
+            attestations: proposal.attestations,
+            disputes: proposal.attestation_disputes,
+        });
+        Ok(())
+    }
+
+    /// Write a compact `ProposalArchive` preserving a finished proposal's
+    /// outcome, and the `AuditBundle` auditors fetch to verify the election.
+    /// Required before `close_proposal` reclaims the large account.
+    pub fn archive_proposal(ctx: Context<ArchiveProposal>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require_closable(proposal)?;
+
+        let mut metadata: Vec<&[u8]> =
+            vec![proposal.title.as_bytes(), proposal.description.as_bytes()];
+        metadata.extend(proposal.links.iter().map(|link| link.url.as_bytes()));
+        let metadata_hash = hashv(&metadata).to_bytes();
+
+        let archive = &mut ctx.accounts.proposal_archive;
+        archive.proposal = proposal.key();
+        archive.id = proposal.id;
+        archive.authority = proposal.authority;
+        archive.yes_votes = proposal.yes_votes;
+        archive.no_votes = proposal.no_votes;
+        archive.abstain_votes = proposal.abstain_votes;
+        archive.total_votes = proposal.total_votes;
+        archive.total_weight = proposal.total_weight;
+        archive.passed = proposal.passed;
+        archive.executed = proposal.executed;
+        archive.cancelled = proposal.cancelled;
+        archive.executed_at = proposal.executed_at;
+        archive.revealed_at = proposal.revealed_at;
+        archive.attestations = proposal.attestations;
+        archive.finalized_at = proposal.finalized_at;
+        archive.metadata_hash = metadata_hash;
+        archive.vote_records_root = proposal.vote_records_root;
+        archive.archived_at = Clock::get()?.unix_timestamp;
+        archive.bump = ctx.bumps.proposal_archive;
+
+        let bundle = &mut ctx.accounts.audit_bundle;
+        bundle.proposal = proposal.key();
+        bundle.tally_lineage = proposal.tally_lineage;
+        bundle.ballot_offsets_hash = proposal.ballot_offsets_hash;
+        bundle.vote_records_root = proposal.vote_records_root;
+        bundle.compacted_records = proposal.compacted_records;
+        bundle.reveal_computation_offset = proposal.reveal_computation_offset;
+        bundle.dropped_computations = proposal.dropped_computations;
+        bundle.ballot_version = BALLOT_VERSION;
+        bundle.mxe_program_id = proposal.mxe_program_id;
+        bundle.bump = ctx.bumps.audit_bundle;
+
+        emit!(ProposalArchived {
+            proposal: proposal.key(),
+            id: proposal.id,
+            metadata_hash,
+        });
+
+        Ok(())
+    }
+
+    /// Close an archived proposal (and its tally), returning rent to the authority.
+    pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+        require_closable(&ctx.accounts.proposal)
+    }
+
+    /// Bind a Switchboard randomness account to the proposal. The account must
+    /// have been committed in the previous slot (same transaction bundle as the
+    /// Switchboard commit), so its value is unknown to everyone at binding time.
+    pub fn request_randomness(ctx: Context<RequestRandomness>) -> Result<()> {
+        let (seed_slot, _, _) = switchboard_randomness(&ctx.accounts.randomness_account)?;
+        require!(
+            seed_slot == Clock::get()?.slot.saturating_sub(1),
+            VotingError::RandomnessAlreadyRevealed
+        );
+
+        let proposal = &mut ctx.accounts.proposal;
+        // Binding once prevents re-rolling after seeing a revealed value
+        require!(
+            proposal.randomness_account == Pubkey::default(),
+            VotingError::RandomnessLocked
+        );
+        proposal.randomness_account = ctx.accounts.randomness_account.key();
+        proposal.randomness_seed_slot = seed_slot;
+        Ok(())
+    }
+
+    /// Store the revealed randomness on the proposal. Used for jury selection
+    /// and anywhere else the program needs an unbiased random value.
+    pub fn consume_randomness(ctx: Context<ConsumeRandomness>) -> Result<()> {
+        let (seed_slot, reveal_slot, value) =
+            switchboard_randomness(&ctx.accounts.randomness_account)?;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.randomness.is_none(), VotingError::RandomnessLocked);
+        require!(
+            seed_slot == proposal.randomness_seed_slot,
+            VotingError::InvalidRandomness
+        );
+        // Switchboard only exposes the value in the slot it was revealed
+        require!(
+            reveal_slot != 0 && reveal_slot == Clock::get()?.slot,
+            VotingError::RandomnessNotReady
+        );
+        proposal.randomness = Some(value);
+
+        emit!(RandomnessRevealed {
+            proposal: proposal.key(),
+            randomness_account: ctx.accounts.randomness_account.key(),
+            value,
+        });
+
+        Ok(())
+    }
+
+    /// Split the proposal's tally into `tally_shards` shards so concurrent votes
+    /// don't all write-lock one account. Only before any ballot is cast; shards
+    /// 1..K are then created with `init_tally_shard`.
+    pub fn set_tally_shards(ctx: Context<SetTallyShards>, tally_shards: u8) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.is_active && proposal.ballots_cast == 0,
+            VotingError::ShardingLocked
+        );
+        require!(
+            (1..=MAX_TALLY_SHARDS).contains(&tally_shards),
+            VotingError::InvalidShardCount
+        );
+        proposal.tally_shards = tally_shards;
+        Ok(())
+    }
+
+    /// Set the eligibility snapshot root — a depth-`SNAPSHOT_DEPTH` SHA3 Merkle
+    /// tree over `(voter, weight)` leaves — used by `cast_eligible_vote`, and
+    /// the tree's total weight, which becomes the proposal's `eligible_supply`.
+    /// Only `DaoConfig.snapshot_oracle` may set it, before any ballot is cast.
+    pub fn set_snapshot_root(
+        ctx: Context<SetSnapshotRoot>,
+        snapshot_root: [u8; 32],
+        total_weight: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.snapshot_oracle.key() != Pubkey::default(),
+            VotingError::Unauthorized
+        );
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.is_active && proposal.ballots_cast == 0,
+            VotingError::SnapshotLocked
+        );
+        require!(
+            snapshot_root != [0u8; 32] && total_weight > 0,
+            VotingError::MissingSnapshotRoot
+        );
+        proposal.snapshot_root = snapshot_root;
+        proposal.snapshot_total_weight = total_weight;
+        adopt_snapshot_supply(proposal, &ctx.accounts.dao_config, total_weight);
+        Ok(())
+    }
+
+    /// Publish an off-chain electorate for a proposal: the root of a SHA-256
+    /// Merkle tree over `hash(voter, weight)` leaves plus the snapshot's
+    /// total weight, which becomes the proposal's `eligible_supply`. Only
+    /// `DaoConfig.snapshot_oracle` may post, before any ballot is cast.
+    pub fn post_oracle_snapshot(
+        ctx: Context<PostOracleSnapshot>,
+        root: [u8; 32],
+        total_weight: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.snapshot_oracle.key() != Pubkey::default(),
+            VotingError::Unauthorized
+        );
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.is_active && proposal.ballots_cast == 0,
+            VotingError::SnapshotLocked
+        );
+        require!(
+            root != [0u8; 32] && total_weight > 0,
+            VotingError::MissingSnapshotRoot
+        );
+        proposal.oracle_root = root;
+        proposal.oracle_total_weight = total_weight;
+        adopt_snapshot_supply(proposal, &ctx.accounts.dao_config, total_weight);
+
+        emit!(OracleSnapshotPosted {
+            proposal: proposal.key(),
+            oracle: ctx.accounts.snapshot_oracle.key(),
+            root,
+            total_weight,
+        });
+        Ok(())
+    }
+
+    /// Record in the voter's `VoterProfile` that they voted on a revealed
+    /// proposal. Permissionless; must run before the vote record is compacted.
+    pub fn record_participation(ctx: Context<RecordParticipation>) -> Result<()> {
+        let reveal_number = ctx.accounts.proposal.reveal_number;
+        require!(reveal_number > 0, VotingError::NotYetRevealed);
+
+        let profile = &mut ctx.accounts.voter_profile;
+        profile.voter = ctx.accounts.voter.key();
+        profile.bump = ctx.bumps.voter_profile;
+        if reveal_number > profile.latest {
+            let shift = reveal_number - profile.latest;
+            profile.participation = if shift >= 64 {
+                0
+            } else {
+                profile.participation << shift
+            };
+            profile.latest = reveal_number;
+        }
+        let age = profile.latest - reveal_number;
+        if age < 64 {
+            profile.participation |= 1 << age;
+        }
+
+        emit!(ParticipationRecorded {
+            voter: profile.voter,
+            proposal: ctx.accounts.proposal.key(),
+            reveal_number,
+        });
+        Ok(())
+    }
+
+    /// Let voters replace their ballot with `recast_vote`. Only before any
+    /// ballot is cast.
+    pub fn set_revoting(ctx: Context<SetSnapshotRoot>, revoting: bool) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.is_active && proposal.ballots_cast == 0,
+            VotingError::RevotingLocked
+        );
+        proposal.revoting = revoting;
+        Ok(())
+    }
+
+    /// Count accepted delegators who never vote with their delegate's ballot,
+    /// via `apply_delegate_fallback` before reveal. Token gates with a
+    /// `snapshot_slot` only (set it first); only before any ballot is cast.
+    pub fn set_delegate_fallback(ctx: Context<SetSnapshotRoot>, enabled: bool) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.is_active && proposal.ballots_cast == 0,
+            VotingError::SnapshotLocked
+        );
+        require!(
+            !enabled || (proposal.gate_kind == GateKind::Token && proposal.snapshot_slot != 0),
+            VotingError::DelegateFallbackUnsupported
+        );
+        proposal.delegate_fallback = enabled;
+        Ok(())
+    }
+
+    /// Measure Token-gate balances as of a past `snapshot_slot`: voters then
+    /// count what their `VoterEscrow` held at the start of that slot. Only
+    /// before any ballot is cast.
+    pub fn set_snapshot_slot(ctx: Context<SetSnapshotRoot>, snapshot_slot: u64) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.is_active && proposal.ballots_cast == 0,
+            VotingError::SnapshotLocked
+        );
+        require!(
+            proposal.gate_kind == GateKind::Token && snapshot_slot <= Clock::get()?.slot,
+            VotingError::InvalidSnapshotSlot
+        );
+        proposal.snapshot_slot = snapshot_slot;
+        Ok(())
+    }
+
+    /// Record which wallet funded `wallet` and when, for the `SybilSignals`
+    /// emitted with its ballots. Both wallets sign so a profile can't be
+    /// attributed to someone else's funder. Must precede the wallet's first
+    /// ballot, which otherwise creates the profile with the ballot's payer as
+    /// funder.
+    pub fn register_wallet_profile(ctx: Context<RegisterWalletProfile>) -> Result<()> {
+        let clock = Clock::get()?;
+        let profile = &mut ctx.accounts.wallet_profile;
+        profile.wallet = ctx.accounts.wallet.key();
+        profile.funded_by = ctx.accounts.funder.key();
+        profile.created_at = clock.unix_timestamp;
+        profile.created_slot = clock.slot;
+        profile.bump = ctx.bumps.wallet_profile;
+        Ok(())
+    }
+
+    /// Move gate tokens into the owner's `VoterEscrow`. The escrow checkpoints
+    /// every balance change, so snapshot proposals can weigh its holder at any
+    /// recent slot, including ones before they were created.
+    pub fn deposit_escrow(ctx: Context<DepositEscrow>, amount: u64) -> Result<()> {
+        require!(amount > 0, VotingError::InvalidAmount);
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    to: ctx.accounts.escrow_vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let slot = Clock::get()?.slot;
+        let escrow = &mut ctx.accounts.voter_escrow;
+        if escrow.owner == Pubkey::default() {
+            escrow.owner = ctx.accounts.owner.key();
+            escrow.mint = ctx.accounts.mint.key();
+            escrow.vault = ctx.accounts.escrow_vault.key();
+            escrow.created_slot = slot;
+            escrow.bump = ctx.bumps.voter_escrow;
+        }
+        escrow.amount = escrow
+            .amount
+            .checked_add(amount)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+        checkpoint_escrow(escrow, slot);
+
+        emit!(CheckpointRecorded {
+            owner: escrow.owner,
+            mint: escrow.mint,
+            slot,
+            amount: escrow.amount,
+        });
+        Ok(())
+    }
+
+    /// Return escrowed tokens to the owner. The withdrawal is checkpointed, so
+    /// it never changes a balance already measured at an earlier slot.
+    pub fn withdraw_escrow(ctx: Context<WithdrawEscrow>, amount: u64) -> Result<()> {
+        let escrow = &ctx.accounts.voter_escrow;
+        require!(
+            amount > 0 && amount <= escrow.amount,
+            VotingError::InvalidAmount
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            VOTER_ESCROW_SEED,
+            escrow.owner.as_ref(),
+            escrow.mint.as_ref(),
+            &[escrow.bump],
+        ]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow_vault.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: escrow.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        let slot = Clock::get()?.slot;
+        let escrow = &mut ctx.accounts.voter_escrow;
+        escrow.amount -= amount;
+        checkpoint_escrow(escrow, slot);
+
+        emit!(CheckpointRecorded {
+            owner: escrow.owner,
+            mint: escrow.mint,
+            slot,
+            amount: escrow.amount,
+        });
+        Ok(())
+    }
+
+    /// Replace a proposal's links. Once a ballot is cast the list becomes
+    /// append-only: existing links must be kept, in order.
+    pub fn update_links(ctx: Context<UpdateLinks>, links: Vec<ProposalLink>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.is_active, VotingError::VotingClosed);
+        validate_links(&links)?;
+        if proposal.ballots_cast > 0 || proposal.total_votes > 0 {
+            require!(
+                links.len() >= proposal.links.len()
+                    && proposal
+                        .links
+                        .iter()
+                        .zip(&links)
+                        .all(|(old, new)| old == new),
+                VotingError::LinksLocked
+            );
+        }
+        proposal.links = links;
+
+        emit!(LinksUpdated {
+            proposal: proposal.key(),
+            count: proposal.links.len() as u8,
+        });
+        Ok(())
+    }
+
+    /// Queue creation of an encrypted zero tally for secondary shard `shard`
+    pub fn init_tally_shard(ctx: Context<InitTallyShard>, shard: u8) -> Result<()> {
+        require!(
+            shard >= 1 && shard < ctx.accounts.proposal.tally_shards,
+            VotingError::WrongTallyShard
+        );
+        require_proposal_mxe(&ctx.accounts.proposal, &ctx.accounts.cluster_account)?;
+
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.authority.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = Clock::get()?.slot;
+        let args = ArgumentList {
+            args: vec![],
+            byte_arrays: vec![],
+            plaintext_numbers: vec![],
+            values_128_bit: vec![],
+            accounts: vec![],
+        };
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(INIT_TALLY_COMP),
+            None,
+            args,
+            ctx.accounts.proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback from Arcium after a secondary shard's init_tally completes
+    pub fn init_tally_shard_callback(
+        ctx: Context<InitTallyShardCallback>,
+        shard: u8,
+        encrypted_tally: [u8; 128],
+        nonce: [u8; 16],
+    ) -> Result<()> {
+        let tally = &mut ctx.accounts.tally;
+        tally.proposal = ctx.accounts.proposal.key();
+        tally.shard = shard;
+        tally.ballot_version = BALLOT_VERSION;
+        tally.encrypted_data = encrypted_tally;
+        tally.nonce = nonce;
+        tally.bump = ctx.bumps.tally;
+        extend_tally_lineage(&mut ctx.accounts.proposal, tally);
+        Ok(())
+    }
+
+    /// After voting ends, fold secondary shard `shard` into the primary tally
+    /// with the `combine_tallies` computation. Merges run one at a time so
+    /// each callback builds on the previous result; a merge left unanswered
+    /// for `MERGE_TIMEOUT_SLOTS` is abandoned by queueing another.
+    pub fn merge_tally_shard(ctx: Context<MergeTallyShard>, shard: u8) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let clock = Clock::get()?;
+        require!(voting_closed(proposal, &clock), VotingError::VotingNotEnded);
+        require!(
+            votes_settled(proposal, &ctx.accounts.dao_config, &clock),
+            VotingError::VotesPending
+        );
+        require!(
+            shard >= 1 && shard < proposal.tally_shards,
+            VotingError::WrongTallyShard
+        );
+        require!(
+            proposal.shards_merged & (1 << shard) == 0,
+            VotingError::ShardAlreadyMerged
+        );
+        require!(
+            proposal.merging_shard == 0
+                || clock.slot
+                    >= proposal
+                        .merge_offset
+                        .checked_add(MERGE_TIMEOUT_SLOTS)
+                        .ok_or(VotingError::ArithmeticOverflow)?,
+            VotingError::MergeInProgress
+        );
+        require_ballot_version(ctx.accounts.tally.ballot_version)?;
+        require_ballot_version(ctx.accounts.shard_tally.ballot_version)?;
+        require_proposal_mxe(proposal, &ctx.accounts.cluster_account)?;
+
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.authority.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = clock.slot;
+        let args = build_args_for_tally_pair(
+            ctx.accounts.tally.encrypted_data,
+            ctx.accounts.shard_tally.encrypted_data,
+        );
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(COMBINE_TALLIES_COMP),
+            None,
+            args,
+            ctx.accounts.proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.merging_shard = shard;
+        proposal.merge_offset = computation_offset;
+        Ok(())
+    }
+
+    /// Callback from Arcium with the primary tally after merging a shard.
+    /// Only the merge currently recorded on the proposal — same computation
+    /// offset and shard — may land.
+    pub fn merge_tally_shard_callback(
+        ctx: Context<MergeTallyShardCallback>,
+        computation_offset: u64,
+        merged_tally: [u8; 128],
+        nonce: [u8; 16],
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.merging_shard != 0, VotingError::MergeInProgress);
+        require!(
+            proposal.merge_offset == computation_offset
+                && ctx.accounts.shard_tally.shard == proposal.merging_shard,
+            VotingError::ComputationMismatch
+        );
+        require_ballot_version(ctx.accounts.tally.ballot_version)?;
+
+        let tally = &mut ctx.accounts.tally;
+        tally.encrypted_data = merged_tally;
+        tally.nonce = nonce;
+        extend_tally_lineage(proposal, tally);
+
+        proposal.shards_merged |= 1 << proposal.merging_shard;
+        proposal.merging_shard = 0;
+        proposal.merge_offset = 0;
+        Ok(())
+    }
+
+    /// Move a proposal's encrypted tally to a new MXE program / cluster.
+    ///
+    /// The old cluster re-encrypts the tally under the new cluster's MXE key
+    /// (`migrate_tally` circuit), read from the new program's MXE account; the
+    /// callback then switches the proposal over. Ballots and reveal are held
+    /// until the migration lands or is cancelled. Only a single, quiescent
+    /// tally can be migrated — in-flight votes would land on the old key.
+    pub fn migrate_tally(ctx: Context<MigrateTally>, new_mxe_program_id: Pubkey) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(!proposal.is_revealed, VotingError::AlreadyRevealed);
+        require!(
+            proposal.pending_mxe_program_id == Pubkey::default(),
+            VotingError::MigrationInProgress
+        );
+        require!(
+            proposal.pending_computations == 0
+                && proposal.merging_shard == 0
+                && proposal.tally_shards == 1,
+            VotingError::MigrationBlocked
+        );
+        require_ballot_version(ctx.accounts.tally.ballot_version)?;
+        require_proposal_mxe(proposal, &ctx.accounts.cluster_account)?;
+        require_approved_mxe(
+            &ctx.accounts.dao_config,
+            &new_mxe_program_id,
+            &ctx.accounts.new_cluster_account.key(),
+        )?;
+        let new_mxe_pubkey = ctx
+            .accounts
+            .new_mxe_account
+            .x25519_pubkey()
+            .ok_or(VotingError::MxeKeyUnavailable)?;
+
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.authority.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = Clock::get()?.slot;
+        let args =
+            build_args_for_tally_migration(ctx.accounts.tally.encrypted_data, new_mxe_pubkey);
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(MIGRATE_TALLY_COMP),
+            None,
+            args,
+            ctx.accounts.proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.pending_mxe_program_id = new_mxe_program_id;
+        proposal.pending_mxe_cluster = ctx.accounts.new_cluster_account.key();
+        proposal.pending_mxe_offset = computation_offset;
+        Ok(())
+    }
+
+    /// Callback from Arcium with the tally re-encrypted for the new cluster.
+    /// Only the migration currently recorded on the proposal may land.
+    pub fn migrate_tally_callback(
+        ctx: Context<MigrateTallyCallback>,
+        computation_offset: u64,
+        migrated_tally: [u8; 128],
+        nonce: [u8; 16],
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.pending_mxe_program_id != Pubkey::default(),
+            VotingError::MigrationBlocked
+        );
+        require!(
+            proposal.pending_mxe_offset == computation_offset,
+            VotingError::ComputationMismatch
+        );
+
+        let tally = &mut ctx.accounts.tally;
+        tally.encrypted_data = migrated_tally;
+        tally.nonce = nonce;
+        extend_tally_lineage(proposal, tally);
+
+        let old_mxe_program_id = proposal.mxe_program_id;
+        proposal.mxe_program_id = proposal.pending_mxe_program_id;
+        proposal.mxe_cluster = proposal.pending_mxe_cluster;
+        proposal.pending_mxe_program_id = Pubkey::default();
+        proposal.pending_mxe_cluster = Pubkey::default();
+        proposal.pending_mxe_offset = 0;
+
+        emit!(TallyMigrated {
+            proposal: proposal.key(),
+            old_mxe_program_id,
+            mxe_program_id: proposal.mxe_program_id,
+            mxe_cluster: proposal.mxe_cluster,
+        });
+        Ok(())
+    }
+
+    /// Abandon a `migrate_tally` whose callback has not landed within
+    /// `MIGRATION_TIMEOUT_SLOTS`. The proposal stays on its current cluster and
+    /// ballots resume; a late callback is rejected. Permissionless.
+    pub fn cancel_tally_migration(ctx: Context<CancelTallyMigration>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.pending_mxe_program_id != Pubkey::default(),
+            VotingError::MigrationBlocked
+        );
+        require!(
+            Clock::get()?.slot
+                >= proposal
+                    .pending_mxe_offset
+                    .checked_add(MIGRATION_TIMEOUT_SLOTS)
+                    .ok_or(VotingError::ArithmeticOverflow)?,
+            VotingError::MigrationInProgress
+        );
+
+        let abandoned_mxe_program_id = proposal.pending_mxe_program_id;
+        proposal.pending_mxe_program_id = Pubkey::default();
+        proposal.pending_mxe_cluster = Pubkey::default();
+        proposal.pending_mxe_offset = 0;
+
+        emit!(TallyMigrationCancelled {
+            proposal: proposal.key(),
+            abandoned_mxe_program_id,
+        });
+        Ok(())
+    }
+
+    /// Enable sortition on a proposal: voters register into a pool and a random
+    /// jury of `jury_size` is drawn as the eligible electorate. Only possible
+    /// on Token-gate proposals before any ballot has been cast.
+    pub fn open_jury_pool(ctx: Context<OpenJuryPool>, jury_size: u8) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.is_active && proposal.ballots_cast == 0,
+            VotingError::JuryLocked
+        );
+        require!(
+            proposal.gate_kind == GateKind::Token,
+            VotingError::SortitionRequiresToken
+        );
+        require!(
+            jury_size > 0 && jury_size as usize <= MAX_JURY_SIZE,
+            VotingError::InvalidJurySize
+        );
+        proposal.jury_size = jury_size;
+
+        let pool = &mut ctx.accounts.jury_pool;
+        pool.proposal = proposal.key();
+        pool.candidates = Vec::new();
+        pool.selected = false;
+        pool.bump = ctx.bumps.jury_pool;
+        Ok(())
+    }
+
+    /// Register the caller as a sortition candidate for a proposal. The
+    /// candidate's escrowed gate balance at the proposal's fixed point
+    /// (`escrowed_weight`) must reach `min_balance`, so one balance can't
+    /// seat several wallets.
+    pub fn register_juror(ctx: Context<RegisterJuror>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let pool = &mut ctx.accounts.jury_pool;
+        let candidate = ctx.accounts.candidate.key();
+        require!(!pool.selected, VotingError::JuryLocked);
+        require!(
+            !proposal.excluded.contains(&candidate),
+            VotingError::VoterExcluded
+        );
+        let balance = escrowed_weight(proposal, &ctx.accounts.candidate_escrow)?;
+        require!(
+            balance > 0 && balance >= proposal.min_balance,
+            VotingError::InsufficientTokenBalance
+        );
+        require!(
+            !pool.candidates.contains(&candidate),
+            VotingError::AlreadyRegistered
+        );
+        require!(
+            pool.candidates.len() < MAX_JURY_POOL,
+            VotingError::JuryPoolFull
+        );
+        pool.candidates.push(candidate);
+        Ok(())
+    }
+
+    /// Close registration and draw the jury from the pool, seeded by the
+    /// proposal's verifiable randomness. Voting is limited to the roster.
+    pub fn select_jury(ctx: Context<SelectJury>) -> Result<()> {
+        let pool = &mut ctx.accounts.jury_pool;
+        require!(!pool.selected, VotingError::JuryLocked);
+
+        let proposal_key = ctx.accounts.proposal.key();
+        let seed = ctx
+            .accounts
+            .proposal
+            .randomness
+            .ok_or(VotingError::RandomnessNotReady)?;
+        let jurors = draw_jury(
+            &pool.candidates,
+            ctx.accounts.proposal.jury_size as usize,
+            seed,
+        );
+        pool.selected = true;
+
+        let roster = &mut ctx.accounts.jury_roster;
+        roster.proposal = proposal_key;
+        roster.jurors = jurors;
+        roster.seed = seed;
+        roster.selected_at = Clock::get()?.unix_timestamp;
+        roster.bump = ctx.bumps.jury_roster;
+
+        emit!(JurySelected {
+            proposal: proposal_key,
+            jurors: roster.jurors.len() as u8,
+            seed,
+        });
+
+        Ok(())
+    }
+
+    /// Create the `SpendTracker` of `mint` (default key = lamports) with no
+    /// limit, for an `UpdateConfig` action's `spend_limit` to configure.
+    /// Permissionless.
+    pub fn init_spend_tracker(ctx: Context<InitSpendTracker>, mint: Pubkey) -> Result<()> {
+        let tracker = &mut ctx.accounts.spend_tracker;
+        tracker.mint = mint;
+        tracker.limit = 0;
+        tracker.window = 0;
+        tracker.spent = 0;
+        tracker.updated_at = Clock::get()?.unix_timestamp;
+        tracker.bump = ctx.bumps.spend_tracker;
+        Ok(())
+    }
+
+    /// Set a wallet's reputation score, used as vote weight on proposals with
+    /// `WeightingMode::Reputation`.
+    pub fn set_reputation(ctx: Context<SetReputation>, score: u64) -> Result<()> {
+        let reputation = &mut ctx.accounts.reputation;
+        reputation.voter = ctx.accounts.voter.key();
+        reputation.score = score;
+        reputation.bump = ctx.bumps.reputation;
+        Ok(())
+    }
+
+    /// Allow `program` to create proposals through CPI, signing as its
+    /// `[PROGRAM_PROPOSER_SEED]` PDA.
+    pub fn allow_proposer_program(
+        ctx: Context<ManageProgramAllowlist>,
+        program: Pubkey,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.dao_config;
+        require!(
+            !config.proposer_programs.contains(&program),
+            VotingError::AlreadyRegistered
+        );
+        require!(
+            config.proposer_programs.len() < MAX_PROPOSER_PROGRAMS,
+            VotingError::ProposerAllowlistFull
+        );
+        config.proposer_programs.push(program);
+        Ok(())
+    }
+
+    /// Stop `program` from creating proposals. Its open proposals are unaffected.
+    pub fn disallow_proposer_program(
+        ctx: Context<ManageProgramAllowlist>,
+        program: Pubkey,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.dao_config;
+        let before = config.proposer_programs.len();
+        config.proposer_programs.retain(|p| *p != program);
+        require!(
+            config.proposer_programs.len() < before,
+            VotingError::ProposerProgramNotAllowed
+        );
+        Ok(())
+    }
+
+    /// Free the creator's rate-limit slot once a proposal is no longer active
+    /// and return the tokens it locked. Permissionless.
+    pub fn release_proposal_slot(ctx: Context<ReleaseProposalSlot>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(!proposal.is_active, VotingError::ProposalStillActive);
+        require!(proposal.holds_rate_slot, VotingError::RateSlotNotHeld);
+
+        let id_bytes = proposal.id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[PROPOSAL_SEED, id_bytes.as_ref(), &[proposal.bump]]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.slot_stake.to_account_info(),
+                    to: ctx.accounts.creator_token_account.to_account_info(),
+                    authority: ctx.accounts.proposal.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            ctx.accounts.slot_stake.amount,
+        )?;
+        ctx.accounts.proposal.holds_rate_slot = false;
+
+        let counter = &mut ctx.accounts.proposal_counter;
+        counter.active_count = counter.active_count.saturating_sub(1);
+        Ok(())
+    }
+
+    /// Flag a proposal as spam or abusive, pausing voting. Opens the next
+    /// numbered `ModerationCase`, which the creator may appeal within
+    /// `MODERATION_APPEAL_WINDOW`; an overturned proposal can be flagged again.
+    pub fn flag_proposal(ctx: Context<FlagProposal>, reason_code: u16) -> Result<()> {
+        require!(
+            ctx.accounts.dao_config.council != Pubkey::default(),
+            VotingError::ModerationDisabled
+        );
+        let clock = Clock::get()?;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.is_active && voting_open(proposal, &clock),
+            VotingError::VotingEnded
+        );
+        proposal.flagged = true;
+        let number = proposal.moderation_cases;
+        proposal.moderation_cases = number
+            .checked_add(1)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+
+        let case = &mut ctx.accounts.moderation_case;
+        case.proposal = proposal.key();
+        case.number = number;
+        case.moderator = ctx.accounts.moderator.key();
+        case.reason_code = reason_code;
+        case.flagged_at = clock.unix_timestamp;
+        case.flagged_slot = clock.slot;
+        case.appeal_bond = 0;
+        case.status = ModerationStatus::Flagged;
+        case.resolved_at = 0;
+        case.bump = ctx.bumps.moderation_case;
+
+        emit!(ProposalFlagged {
+            proposal: case.proposal,
+            moderator: case.moderator,
+            reason_code,
+        });
+        Ok(())
+    }
+
+    /// Appeal a moderation flag by posting `DaoConfig.appeal_bond` lamports,
+    /// sending the case to the council. Creator only.
+    pub fn appeal_moderation(ctx: Context<AppealModeration>) -> Result<()> {
+        let case = &mut ctx.accounts.moderation_case;
+        require!(
+            case.status == ModerationStatus::Flagged,
+            VotingError::InvalidModerationStatus
+        );
+        require!(
+            Clock::get()?.unix_timestamp <= case.flagged_at + MODERATION_APPEAL_WINDOW,
+            VotingError::AppealWindowClosed
+        );
+        let amount = ctx.accounts.dao_config.appeal_bond;
+        // The bond PDA holds no data, so it must stay rent-exempt on its own
+        require!(
+            amount >= Rent::get()?.minimum_balance(0),
+            VotingError::BondBelowRentExemption
+        );
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.appeal_bond.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        case.appeal_bond = amount;
+        case.status = ModerationStatus::Appealed;
+
+        emit!(ModerationAppealed {
+            proposal: case.proposal,
+            bond: amount,
+        });
+        Ok(())
+    }
+
+    /// Council ruling on a moderation case. Upholding deactivates the proposal
+    /// and sends any appeal bond to the SOL treasury; overturning resumes
+    /// voting, extends the deadline by the time spent paused and refunds the bond.
+    pub fn resolve_moderation(ctx: Context<ResolveModeration>, uphold: bool) -> Result<()> {
+        require!(
+            ctx.accounts.dao_config.council != Pubkey::default(),
+            VotingError::Unauthorized
+        );
+        let case = &mut ctx.accounts.moderation_case;
+        require!(
+            case.status == ModerationStatus::Flagged || case.status == ModerationStatus::Appealed,
+            VotingError::InvalidModerationStatus
+        );
+        let clock = Clock::get()?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.flagged = false;
+        if uphold {
+            proposal.is_active = false;
+            case.status = ModerationStatus::Upheld;
+        } else {
+            proposal.voting_ends_at += clock.unix_timestamp - case.flagged_at;
+            if proposal.voting_ends_slot > 0 {
+                proposal.voting_ends_slot += clock.slot - case.flagged_slot;
+            }
+            case.status = ModerationStatus::Overturned;
+        }
+        case.resolved_at = clock.unix_timestamp;
+
+        let bond = case.appeal_bond;
+        if bond > 0 {
+            let to = if uphold {
+                ctx.accounts.sol_treasury.to_account_info()
+            } else {
+                ctx.accounts.creator.to_account_info()
+            };
+            let case_key = case.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                APPEAL_BOND_SEED,
+                case_key.as_ref(),
+                &[ctx.bumps.appeal_bond],
+            ]];
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.appeal_bond.to_account_info(),
+                        to,
+                    },
+                    signer_seeds,
+                ),
+                bond,
+            )?;
+        }
+
+        emit!(ModerationResolved {
+            proposal: case.proposal,
+            upheld: uphold,
+            bond,
+        });
+        Ok(())
+    }
+
+    /// Uphold a flag the creator did not appeal within
+    /// `MODERATION_APPEAL_WINDOW`, deactivating the proposal. Permissionless.
+    pub fn expire_moderation(ctx: Context<ExpireModeration>) -> Result<()> {
+        let case = &mut ctx.accounts.moderation_case;
+        require!(
+            case.status == ModerationStatus::Flagged,
+            VotingError::InvalidModerationStatus
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now > case
+                .flagged_at
+                .checked_add(MODERATION_APPEAL_WINDOW)
+                .ok_or(VotingError::ArithmeticOverflow)?,
+            VotingError::AppealWindowOpen
+        );
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.flagged = false;
+        proposal.is_active = false;
+        case.status = ModerationStatus::Upheld;
+        case.resolved_at = now;
+
+        emit!(ModerationResolved {
+            proposal: case.proposal,
+            upheld: true,
+            bond: 0,
+        });
+        Ok(())
+    }
+
+    /// Add the signer's gate balance to the emergency-brake petition against a
+    /// Token-gate proposal. Once signatures reach `SUPERMAJORITY_BPS` of the
+    /// supply frozen at creation, voting and execution halt until the council
+    /// reviews the petition. Signatures weigh the signer's escrowed balance at
+    /// a point fixed before the petition opened (`escrowed_weight`).
+    pub fn sign_petition(ctx: Context<SignPetition>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let signer = ctx.accounts.signer.key();
+        require_vote(
+            proposal.gate_kind == GateKind::Token
+                && proposal.eligible_supply > 0
+                && !proposal.flagged
+                && !proposal.executed
+                && !proposal.cancelled,
+            RejectReason::PetitionUnavailable,
+            VotingError::PetitionUnavailable,
+            proposal.key(),
+            signer,
+        )?;
+        let rights = resolve_tier(&ctx.accounts.dao_config, &signer, &ctx.accounts.tier_escrow)?;
+        require_vote(
+            rights & TIER_RIGHT_SIGN_PETITION != 0,
+            RejectReason::MissingTierRight,
+            VotingError::MissingTierRight,
+            proposal.key(),
+            signer,
+        )?;
+        let weight = escrowed_weight(proposal, &ctx.accounts.signer_escrow)?;
+        require_vote(
+            weight > 0,
+            RejectReason::InsufficientBalance,
+            VotingError::InsufficientTokenBalance,
+            proposal.key(),
+            signer,
+        )?;
+
+        let signature = &mut ctx.accounts.petition_signature;
+        signature.proposal = proposal.key();
+        signature.signer = ctx.accounts.signer.key();
+        signature.weight = weight;
+        signature.bump = ctx.bumps.petition_signature;
+
+        let petition = &mut ctx.accounts.petition;
+        petition.proposal = proposal.key();
+        petition.weight = petition
+            .weight
+            .checked_add(weight)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+        petition.signers += 1;
+        petition.bump = ctx.bumps.petition;
+
+        emit!(PetitionSigned {
+            proposal: petition.proposal,
+            signer: signature.signer,
+            weight,
+            total_weight: petition.weight,
+        });
+
+        let threshold = proposal.eligible_supply as u128 * SUPERMAJORITY_BPS as u128 / 10_000;
+        if petition.status == PetitionStatus::Open && petition.weight as u128 >= threshold {
+            let clock = Clock::get()?;
+            let proposal = &mut ctx.accounts.proposal;
+            petition.status = PetitionStatus::Triggered;
+            petition.paused_voting = proposal.is_active && voting_open(proposal, &clock);
+            petition.triggered_at = clock.unix_timestamp;
+            petition.triggered_slot = clock.slot;
+            proposal.flagged = true;
+
+            emit!(PetitionTriggered {
+                proposal: petition.proposal,
+                weight: petition.weight,
+                signers: petition.signers,
+            });
+        }
+        Ok(())
+    }
+
+    /// Council review of a triggered petition. Upholding deactivates the
+    /// proposal and cancels any pending execution; dismissing lifts the halt
+    /// and extends the deadline by the time voting spent paused.
+    pub fn review_petition(ctx: Context<ReviewPetition>, uphold: bool) -> Result<()> {
+        require!(
+            ctx.accounts.dao_config.council != Pubkey::default(),
+            VotingError::Unauthorized
+        );
+        let petition = &mut ctx.accounts.petition;
+        require!(
+            petition.status == PetitionStatus::Triggered,
+            VotingError::InvalidPetitionStatus
+        );
+        let clock = Clock::get()?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.flagged = false;
+        if uphold {
+            proposal.is_active = false;
+            if proposal.passed && !proposal.executed {
+                proposal.cancelled = true;
+                emit!(ExecutionCancelled {
+                    proposal: proposal.key(),
+                    cancelled_by: ctx.accounts.council.key(),
+                });
+            }
+            petition.status = PetitionStatus::Upheld;
+        } else {
+            if petition.paused_voting {
+                proposal.voting_ends_at += clock.unix_timestamp - petition.triggered_at;
+                if proposal.voting_ends_slot > 0 {
+                    proposal.voting_ends_slot += clock.slot - petition.triggered_slot;
+                }
+            }
+            petition.status = PetitionStatus::Dismissed;
+        }
+        petition.reviewed_at = clock.unix_timestamp;
+
+        emit!(PetitionReviewed {
+            proposal: petition.proposal,
+            upheld: uphold,
+        });
+        Ok(())
+    }
+
+    /// Endorse a proposal awaiting sponsorship with the endorser's escrowed
+    /// gate-token weight (`escrowed_weight`). Voting opens once endorsements
+    /// reach `sponsorship_bps` of the eligible supply, and the deadline shifts
+    /// by the time spent waiting.
+    pub fn endorse_proposal(ctx: Context<EndorseProposal>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(
+            proposal.is_active && proposal.awaiting_sponsorship,
+            VotingError::SponsorshipUnavailable
+        );
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp < proposal.sponsorship_deadline,
+            VotingError::SponsorshipLapsed
+        );
+        let weight = escrowed_weight(proposal, &ctx.accounts.endorser_escrow)?;
+        require!(weight > 0, VotingError::InsufficientTokenBalance);
+
+        let endorsement = &mut ctx.accounts.endorsement;
+        endorsement.proposal = proposal.key();
+        endorsement.endorser = ctx.accounts.endorser.key();
+        endorsement.weight = weight;
+        endorsement.bump = ctx.bumps.endorsement;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.endorsement_weight = proposal
+            .endorsement_weight
+            .checked_add(weight)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+
+        emit!(ProposalEndorsed {
+            proposal: proposal.key(),
+            endorser: endorsement.endorser,
+            weight,
+            total_weight: proposal.endorsement_weight,
+        });
+
+        let required =
+            proposal.eligible_supply as u128 * ctx.accounts.dao_config.sponsorship_bps as u128;
+        if proposal.endorsement_weight as u128 * 10_000 >= required {
+            let waited = clock.unix_timestamp - proposal.voting_starts_at;
+            proposal.awaiting_sponsorship = false;
+            proposal.voting_starts_at = clock.unix_timestamp;
+            proposal.voting_ends_at += waited;
+            if proposal.voting_ends_slot > 0 {
+                proposal.voting_ends_slot += slots_for(waited);
+            }
+
+            emit!(ProposalSponsored {
+                proposal: proposal.key(),
+                endorsement_weight: proposal.endorsement_weight,
+                voting_ends_at: proposal.voting_ends_at,
+            });
+        }
+        Ok(())
+    }
+
+    /// Deactivate a proposal whose sponsorship period ended short of the
+    /// endorsement bar. Its deposit then settles at `unsponsored_slash_bps`.
+    /// Permissionless.
+    pub fn expire_unsponsored(ctx: Context<ExpireUnsponsored>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.is_active && proposal.awaiting_sponsorship,
+            VotingError::SponsorshipUnavailable
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= proposal.sponsorship_deadline,
+            VotingError::SponsorshipPending
+        );
+        proposal.is_active = false;
+        proposal.awaiting_sponsorship = false;
+        proposal.sponsorship_expired = true;
+
+        emit!(SponsorshipExpired {
+            proposal: proposal.key(),
+            endorsement_weight: proposal.endorsement_weight,
+        });
+        Ok(())
+    }
+
+    /// Deny `wallet` from voting and creating proposals. Compliance authority only.
+    pub fn deny_wallet(ctx: Context<DenyWallet>, wallet: Pubkey, reason_code: u16) -> Result<()> {
+        let entry = &mut ctx.accounts.denylist_entry;
+        entry.wallet = wallet;
+        entry.reason_code = reason_code;
+        entry.added_at = Clock::get()?.unix_timestamp;
+        entry.bump = ctx.bumps.denylist_entry;
+
+        emit!(WalletDenied {
+            wallet,
+            reason_code,
+        });
+        Ok(())
+    }
+
+    /// Lift a denial, closing the entry to the compliance authority.
+    pub fn allow_wallet(ctx: Context<AllowWallet>) -> Result<()> {
+        emit!(WalletAllowed {
+            wallet: ctx.accounts.denylist_entry.wallet,
+        });
+        Ok(())
+    }
+
+    /// Register a Pyth price feed for a mint so its holdings count towards
+    /// USD-value gates. `max_confidence_bps` bounds conf / price.
+    pub fn register_price_feed(
+        ctx: Context<RegisterPriceFeed>,
+        feed_id: [u8; 32],
+        max_staleness: i64,
+        max_confidence_bps: u16,
+    ) -> Result<()> {
+        require!(max_staleness > 0, VotingError::InvalidOracleConfig);
+        require!(
+            max_confidence_bps > 0 && max_confidence_bps <= 10_000,
+            VotingError::InvalidOracleConfig
+        );
+
+        let feed = &mut ctx.accounts.price_feed;
+        feed.mint = ctx.accounts.mint.key();
+        feed.decimals = ctx.accounts.mint.decimals;
+        feed.feed_id = feed_id;
+        feed.max_staleness = max_staleness;
+        feed.max_confidence_bps = max_confidence_bps;
+        feed.bump = ctx.bumps.price_feed;
+        Ok(())
+    }
+
+    /// Prove that the caller's ballot on a passed treasury-spending proposal
+    /// was NO, making them eligible to ragequit. Queues the `verify_no_vote`
+    /// computation, which reveals only that single bit of the caller's own
+    /// ballot — never the tally or anyone else's choice.
+    pub fn prove_no_vote(ctx: Context<ProveNoVote>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require_ragequit_window(proposal, &ctx.accounts.execution_payload)?;
+        require_ballot_version(ctx.accounts.vote_record.ballot_version)?;
+        require_proposal_mxe(proposal, &ctx.accounts.cluster_account)?;
+
+        let cpi_accounts = QueueComputation {
+            signer: ctx.accounts.voter.to_account_info(),
+            sign_seed: ctx.accounts.sign_seed.to_account_info(),
+            comp: ctx.accounts.computation_account.to_account_info(),
+            mxe: ctx.accounts.mxe_account.to_account_info(),
+            mempool: ctx.accounts.mempool_account.to_account_info(),
+            executing_pool: ctx.accounts.executing_pool.to_account_info(),
+            comp_def_acc: ctx.accounts.comp_def_account.to_account_info(),
+            cluster: ctx.accounts.cluster_account.to_account_info(),
+            pool_account: ctx.accounts.pool_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock_account.to_account_info(),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"sign", &[ctx.bumps.sign_seed]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.arcium_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        let computation_offset = Clock::get()?.slot;
+        ctx.accounts.vote_record.computation_offset = computation_offset;
+        let args = build_args_for_ballot(ctx.accounts.vote_record.encrypted_choice);
+
+        queue_computation(
+            cpi_ctx,
+            computation_offset,
+            comp_def_offset(VERIFY_NO_VOTE_COMP),
+            None,
+            args,
+            proposal.mxe_program_id,
+            Vec::<CallbackInstruction>::new(),
+            0,
+            0,
+            0,
+        )?;
+
+        Ok(())
+    }
+
+    /// Callback from Arcium with the result of `verify_no_vote`, for the
+    /// computation `prove_no_vote` last queued on this ballot
+    pub fn verify_no_vote_callback(
+        ctx: Context<VerifyNoVoteCallback>,
+        computation_offset: u64,
+        voted_no: bool,
+    ) -> Result<()> {
+        let vote_record = &mut ctx.accounts.vote_record;
+        require!(
+            vote_record.computation_offset != 0
+                && vote_record.computation_offset == computation_offset,
+            VotingError::ComputationMismatch
+        );
+        require_ballot_version(vote_record.ballot_version)?;
+        vote_record.computation_offset = 0;
+        vote_record.no_vote_proven = voted_no;
+        Ok(())
+    }
+
+    /// Exit before a passed treasury-spending proposal executes. A member with
+    /// a proven NO ballot burns `amount` of the proposal's gate token — at
+    /// most the balance the ballot was counted with — and receives
+    /// `amount / eligible_supply` of the given treasury vault, net of earlier
+    /// exits. Frozen holdings (e.g. governance power) cannot be burned.
+    pub fn ragequit(ctx: Context<Ragequit>, amount: u64) -> Result<()> {
+        require!(amount > 0, VotingError::InvalidAmount);
+        require_ragequit_window(&ctx.accounts.proposal, &ctx.accounts.execution_payload)?;
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        let burned = vote_record
+            .ragequit_burned
+            .checked_add(amount)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+        require!(
+            burned <= vote_record.gate_balance,
+            VotingError::RagequitCapExceeded
+        );
+        vote_record.ragequit_burned = burned;
+
+        // Pro-rata share of the electorate frozen at creation; netting out
+        // earlier exits keeps the shares equal as the vault shrinks
+        let proposal = &mut ctx.accounts.proposal;
+        let supply = proposal
+            .eligible_supply
+            .checked_sub(proposal.ragequit_burned)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+        let withdrawn = (ctx.accounts.treasury_vault.amount as u128)
+            .checked_mul(amount as u128)
+            .and_then(|x| x.checked_div(supply as u128))
+            .ok_or(VotingError::ArithmeticOverflow)? as u64;
+        proposal.ragequit_burned = proposal
+            .ragequit_burned
+            .checked_add(amount)
+            .ok_or(VotingError::ArithmeticOverflow)?;
+
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.gate_mint.to_account_info(),
+                    from: ctx.accounts.member_token_account.to_account_info(),
+                    authority: ctx.accounts.member.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[TREASURY_SEED, &[ctx.bumps.treasury_authority]]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.treasury_vault.to_account_info(),
+                    to: ctx.accounts.member_receive_account.to_account_info(),
+                    authority: ctx.accounts.treasury_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            withdrawn,
+        )?;
+
+        emit!(RagequitExecuted {
+            proposal: ctx.accounts.proposal.key(),
+            member: ctx.accounts.member.key(),
+            burned: amount,
+            vault: ctx.accounts.treasury_vault.key(),
+            withdrawn,
+        });
+
+        Ok(())
+    }
+}
+
+// ==================== ACCOUNT STRUCTURES ====================
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Creator's denylist entry; empty unless the wallet is denied
+    #[account(seeds = [DENYLIST_SEED, authority.key().as_ref()], bump)]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [PROPOSAL_SEED, proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Sign PDA for Arcium CPI
+    #[account(
+        seeds = [SIGN_SEED],
+        bump
+    )]
+    pub sign_seed: AccountInfo<'info>,
+
+    // Arcium accounts
+    pub arcium_program: Program<'info, Arcium>,
+    /// CHECK: MXE account
+    pub mxe_account: AccountInfo<'info>,
+    /// CHECK: Cluster account
+    pub cluster_account: AccountInfo<'info>,
+    /// CHECK: Fee pool
+    pub pool_account: AccountInfo<'info>,
+    /// CHECK: Clock account
+    pub clock_account: AccountInfo<'info>,
+    /// CHECK: Mempool
+    pub mempool_account: AccountInfo<'info>,
+    /// CHECK: Executing pool
+    pub executing_pool: AccountInfo<'info>,
+    /// CHECK: Computation account
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    /// CHECK: Comp def account
+    pub comp_def_account: AccountInfo<'info>,
+    /// CHECK: Computation offset account
+    #[account(
+        mut,
+        seeds = [COMPUTATION_OFFSET_SEED],
+        bump = computation_offset_account.bump
+    )]
+    pub computation_offset_account: Account<'info, ComputationOffsetState>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    /// Optional committee scope — the authority must be one of its proposers
+    pub committee: Option<Account<'info, Committee>>,
+
+    /// Gate mint, required for Token gates to snapshot `eligible_supply`;
+    /// omitted for gates without a mint supply
+    pub gate_mint_account: Option<Account<'info, Mint>>,
+
+    // Deposit accounts, required when the DAO charges a proposal deposit
+    #[account(address = dao_config.deposit_mint @ VotingError::InvalidTokenMint)]
+    pub deposit_mint: Option<Account<'info, Mint>>,
+    #[account(mut)]
+    pub creator_deposit_account: Option<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [DEPOSIT_ESCROW_SEED, proposal.key().as_ref()],
+        bump,
+        token::mint = deposit_mint,
+        token::authority = proposal
+    )]
+    pub deposit_escrow: Option<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [PRICE_FEED_SEED, dao_config.deposit_mint.as_ref()],
+        bump = deposit_price_feed.bump
+    )]
+    pub deposit_price_feed: Option<Account<'info, PriceFeed>>,
+    /// CHECK: Pyth price update, validated in `pyth_price`
+    pub deposit_oracle: Option<UncheckedAccount<'info>>,
+    /// Lamport bond escrow, required when the DAO takes deposits in SOL
+    #[account(mut, seeds = [SOL_BOND_SEED, proposal.key().as_ref()], bump)]
+    pub sol_bond: Option<SystemAccount<'info>>,
+    /// Creator's active-proposal counter, required when the holdings-based
+    /// rate limit is enabled
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ProposalCounter::INIT_SPACE,
+        seeds = [PROPOSAL_COUNTER_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub proposal_counter: Option<Account<'info, ProposalCounter>>,
+    /// Escrow for the tokens the rate-limit slot locks, required with the
+    /// counter
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SLOT_STAKE_SEED, proposal.key().as_ref()],
+        bump,
+        token::mint = deposit_mint,
+        token::authority = proposal
+    )]
+    pub slot_stake: Option<Account<'info, TokenAccount>>,
+    /// Creator's participation record, required with `proposer_min_votes`
+    #[account(seeds = [VOTER_PROFILE_SEED, authority.key().as_ref()], bump = voter_profile.bump)]
+    pub voter_profile: Option<Account<'info, VoterProfile>>,
+
+    /// CHECK: Program whose proposer PDA signs as `authority`; required and
+    /// checked in `require_cpi_proposer` when called through CPI
+    #[account(executable)]
+    pub caller_program: Option<UncheckedAccount<'info>>,
+
+    /// Creator's `tier_mint` escrow, resolving its voter tier
+    #[account(
+        seeds = [VOTER_ESCROW_SEED, authority.key().as_ref(), dao_config.tier_mint.as_ref()],
+        bump = tier_escrow.bump
+    )]
+    pub tier_escrow: Option<Account<'info, VoterEscrow>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitTallyCallback<'info> {
+    #[account(
+        mut,
+        constraint = proposal.authority == authority.key() @ VotingError::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Proposal authority — validated by constraint above
+    pub authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Tally::INIT_SPACE,
+        seeds = [TALLY_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub tally: Account<'info, Tally>,
+
+    /// CHECK: Sign PDA ensures this callback was invoked via Arcium CPI
+    #[account(
+        seeds = [SIGN_SEED],
+        bump,
+        signer
+    )]
+    pub sign_seed: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    /// CHECK: Voter's denylist entry; empty unless the wallet is denied
+    #[account(seeds = [DENYLIST_SEED, voter.key().as_ref()], bump)]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub tally: Account<'info, Tally>,
+
+    /// Gate token account (Token and StakePool gates)
+    pub voter_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Native stake account, parsed in `active_stake` (StakeAccount gate)
+    pub voter_stake_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL stake pool, parsed in `stake_pool_lamports` (StakePool gate)
+    pub stake_pool: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Locked or vesting position of the gate mint, parsed in
+    /// `with_vesting_power` (Token gate)
+    pub vesting_position: Option<UncheckedAccount<'info>>,
+
+    /// Voter's gate-mint escrow (snapshot proposals and `same_slot_guard`)
+    pub voter_escrow: Option<Account<'info, VoterEscrow>>,
+
+    /// Voter's funding and age record, reported in `SybilSignals`; created
+    /// on the wallet's first ballot
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + WalletProfile::INIT_SPACE,
+        seeds = [WALLET_PROFILE_SEED, voter.key().as_ref()],
+        bump
+    )]
+    pub wallet_profile: Box<Account<'info, WalletProfile>>,
+
+    /// Voter's reputation (Reputation weighting)
+    #[account(seeds = [REPUTATION_SEED, voter.key().as_ref()], bump = reputation.bump)]
+    pub reputation: Option<Account<'info, Reputation>>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteRecord::INIT_SPACE,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    /// Drawn jury, required when the proposal uses sortition
+    #[account(seeds = [JURY_ROSTER_SEED, proposal.key().as_ref()], bump = jury_roster.bump)]
+    pub jury_roster: Option<Account<'info, JuryRoster>>,
+
+    /// CHECK: Sign PDA
+    #[account(seeds = [SIGN_SEED], bump)]
+    pub sign_seed: AccountInfo<'info>,
+
+    // Arcium accounts (same as CreateProposal)
+    pub arcium_program: Program<'info, Arcium>,
+    /// CHECK: MXE account
+    pub mxe_account: AccountInfo<'info>,
+    /// CHECK: Cluster account
+    pub cluster_account: AccountInfo<'info>,
+    /// CHECK: Fee pool
+    pub pool_account: AccountInfo<'info>,
+    /// CHECK: Clock account
+    pub clock_account: AccountInfo<'info>,
+    /// CHECK: Mempool
+    pub mempool_account: AccountInfo<'info>,
+    /// CHECK: Executing pool
+    pub executing_pool: AccountInfo<'info>,
+    /// CHECK: Computation account
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    /// CHECK: Comp def account
+    pub comp_def_account: AccountInfo<'info>,
+    /// CHECK: Computation offset account
+    #[account(
+        mut,
+        seeds = [COMPUTATION_OFFSET_SEED],
+        bump = computation_offset_account.bump
+    )]
+    pub computation_offset_account: Account<'info, ComputationOffsetState>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVoteSigned<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: Authorizes via the Ed25519-signed ballot message
+    pub voter: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    /// CHECK: Voter's denylist entry; empty unless the wallet is denied
+    #[account(seeds = [DENYLIST_SEED, voter.key().as_ref()], bump)]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub tally: Account<'info, Tally>,
+
+    /// Gate token account (Token and StakePool gates)
+    pub voter_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Native stake account, parsed in `active_stake` (StakeAccount gate)
+    pub voter_stake_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL stake pool, parsed in `stake_pool_lamports` (StakePool gate)
+    pub stake_pool: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Locked or vesting position of the gate mint, parsed in
+    /// `with_vesting_power` (Token gate)
+    pub vesting_position: Option<UncheckedAccount<'info>>,
+
+    /// Voter's gate-mint escrow (snapshot proposals and `same_slot_guard`)
+    pub voter_escrow: Option<Account<'info, VoterEscrow>>,
+
+    /// Voter's funding and age record, reported in `SybilSignals`; created
+    /// on the wallet's first ballot
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + WalletProfile::INIT_SPACE,
+        seeds = [WALLET_PROFILE_SEED, voter.key().as_ref()],
+        bump
+    )]
+    pub wallet_profile: Box<Account<'info, WalletProfile>>,
+
+    /// Voter's reputation (Reputation weighting)
+    #[account(seeds = [REPUTATION_SEED, voter.key().as_ref()], bump = reputation.bump)]
+    pub reputation: Option<Account<'info, Reputation>>,
+
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + VoteRecord::INIT_SPACE,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + BallotSequence::INIT_SPACE,
+        seeds = [BALLOT_SEQUENCE_SEED, voter.key().as_ref()],
+        bump
+    )]
+    pub ballot_sequence: Account<'info, BallotSequence>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Account<'info, Relayer>,
+
+    /// Drawn jury, required when the proposal uses sortition
+    #[account(seeds = [JURY_ROSTER_SEED, proposal.key().as_ref()], bump = jury_roster.bump)]
+    pub jury_roster: Option<Account<'info, JuryRoster>>,
+
+    /// CHECK: Instructions sysvar, read for the Ed25519 signature check
+    #[account(address = solana_instructions_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    /// CHECK: Sign PDA
+    #[account(seeds = [SIGN_SEED], bump)]
+    pub sign_seed: AccountInfo<'info>,
+
+    // Arcium accounts (same as CreateProposal)
+    pub arcium_program: Program<'info, Arcium>,
+    /// CHECK: MXE account
+    pub mxe_account: AccountInfo<'info>,
+    /// CHECK: Cluster account
+    pub cluster_account: AccountInfo<'info>,
+    /// CHECK: Fee pool
+    pub pool_account: AccountInfo<'info>,
+    /// CHECK: Clock account
+    pub clock_account: AccountInfo<'info>,
+    /// CHECK: Mempool
+    pub mempool_account: AccountInfo<'info>,
+    /// CHECK: Executing pool
+    pub executing_pool: AccountInfo<'info>,
+    /// CHECK: Computation account
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    /// CHECK: Comp def account
+    pub comp_def_account: AccountInfo<'info>,
+    /// CHECK: Computation offset account
+    #[account(
+        mut,
+        seeds = [COMPUTATION_OFFSET_SEED],
+        bump = computation_offset_account.bump
+    )]
+    pub computation_offset_account: Account<'info, ComputationOffsetState>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterRelayer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Relayer::INIT_SPACE,
+        seeds = [RELAYER_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub relayer_registration: Account<'info, Relayer>,
+
+    #[account(mut, seeds = [RELAYER_BOND_SEED, authority.key().as_ref()], bump)]
+    pub relayer_bond: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RelayerUnbonding<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [RELAYER_SEED, authority.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Account<'info, Relayer>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawRelayerBond<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority,
+        seeds = [RELAYER_SEED, authority.key().as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Account<'info, Relayer>,
+
+    #[account(mut, seeds = [RELAYER_BOND_SEED, authority.key().as_ref()], bump)]
+    pub relayer_bond: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ChallengeRelayer<'info> {
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Voter whose ballot the relayer acknowledged
+    pub voter: AccountInfo<'info>,
+
+    /// CHECK: Voter's record, possibly uninitialized; parsed in the handler
+    #[account(seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()], bump)]
+    pub vote_record: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = challenger,
+        seeds = [RELAYER_SEED, relayer_registration.authority.as_ref()],
+        bump = relayer_registration.bump
+    )]
+    pub relayer_registration: Account<'info, Relayer>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_BOND_SEED, relayer_registration.authority.as_ref()],
+        bump
+    )]
+    pub relayer_bond: SystemAccount<'info>,
+
+    /// CHECK: Instructions sysvar, read for the Ed25519 signature check
+    #[account(address = solana_instructions_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordParticipation<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Voter whose participation is recorded
+    pub voter: AccountInfo<'info>,
+
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VoterProfile::INIT_SPACE,
+        seeds = [VOTER_PROFILE_SEED, voter.key().as_ref()],
+        bump
+    )]
+    pub voter_profile: Account<'info, VoterProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecastVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    /// CHECK: Voter's denylist entry; empty unless the wallet is denied
+    #[account(seeds = [DENYLIST_SEED, voter.key().as_ref()], bump)]
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(mut)]
+    pub tally: Account<'info, Tally>,
+
+    /// Gate token account (Token and StakePool gates)
+    pub voter_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Native stake account, parsed in `active_stake` (StakeAccount gate)
+    pub voter_stake_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL stake pool, parsed in `stake_pool_lamports` (StakePool gate)
+    pub stake_pool: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Locked or vesting position of the gate mint, parsed in
+    /// `with_vesting_power` (Token gate)
+    pub vesting_position: Option<UncheckedAccount<'info>>,
+
+    /// Voter's gate-mint escrow (snapshot proposals and `same_slot_guard`)
+    pub voter_escrow: Option<Account<'info, VoterEscrow>>,
+
+    /// Voter's reputation (Reputation weighting)
+    #[account(seeds = [REPUTATION_SEED, voter.key().as_ref()], bump = reputation.bump)]
+    pub reputation: Option<Account<'info, Reputation>>,
+
+    /// Drawn jury, required when the proposal uses sortition
+    #[account(seeds = [JURY_ROSTER_SEED, proposal.key().as_ref()], bump = jury_roster.bump)]
+    pub jury_roster: Option<Account<'info, JuryRoster>>,
+
+    /// CHECK: Sign PDA
+    #[account(seeds = [SIGN_SEED], bump)]
+    pub sign_seed: AccountInfo<'info>,
+
+    // Arcium accounts
+    pub arcium_program: Program<'info, Arcium>,
+    /// CHECK: MXE account
+    pub mxe_account: AccountInfo<'info>,
+    /// CHECK: Cluster account
+    pub cluster_account: AccountInfo<'info>,
+    /// CHECK: Fee pool
+    pub pool_account: AccountInfo<'info>,
+    /// CHECK: Clock account
+    pub clock_account: AccountInfo<'info>,
+    /// CHECK: Mempool
+    pub mempool_account: AccountInfo<'info>,
+    /// CHECK: Executing pool
+    pub executing_pool: AccountInfo<'info>,
+    /// CHECK: Computation account
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    /// CHECK: Comp def account
+    pub comp_def_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyDelegateFallback<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    /// Delegate's ballot the fallback weight follows
+    #[account(
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), delegate_record.voter.as_ref()],
+        bump = delegate_record.bump
+    )]
+    pub delegate_record: Account<'info, VoteRecord>,
+
+    #[account(mut)]
+    pub tally: Account<'info, Tally>,
+
+    /// CHECK: Sign PDA
+    #[account(seeds = [SIGN_SEED], bump)]
+    pub sign_seed: AccountInfo<'info>,
+
+    // Arcium accounts
+    pub arcium_program: Program<'info, Arcium>,
+    /// CHECK: MXE account
+    pub mxe_account: AccountInfo<'info>,
+    /// CHECK: Cluster account
+    pub cluster_account: AccountInfo<'info>,
+    /// CHECK: Fee pool
+    pub pool_account: AccountInfo<'info>,
+    /// CHECK: Clock account
+    pub clock_account: AccountInfo<'info>,
+    /// CHECK: Mempool
+    pub mempool_account: AccountInfo<'info>,
+    /// CHECK: Executing pool
+    pub executing_pool: AccountInfo<'info>,
+    /// CHECK: Computation account
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    /// CHECK: Comp def account
+    pub comp_def_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateFallbackCallback<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        mut,
+        constraint = tally.proposal == proposal.key()
+    )]
+    pub tally: Account<'info, Tally>,
+
+    /// CHECK: Sign PDA ensures this callback was invoked via Arcium CPI
+    #[account(
+        seeds = [SIGN_SEED],
+        bump,
+        signer
+    )]
+    pub sign_seed: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VoteCallback<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        mut,
+        constraint = tally.proposal == proposal.key()
+    )]
+    pub tally: Account<'info, Tally>,
+
+    /// Ballot the computation tallied; receives the voter's receipt
+    #[account(
+        mut,
+        constraint = vote_record.proposal == proposal.key()
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    /// CHECK: Sign PDA ensures this callback was invoked via Arcium CPI
+    #[account(
+        seeds = [SIGN_SEED],
+        bump,
+        signer
+    )]
+    pub sign_seed: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealResults<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    pub tally: Account<'info, Tally>,
+
+    /// CHECK: Sign PDA
+    #[account(seeds = [SIGN_SEED], bump)]
+    pub sign_seed: AccountInfo<'info>,
+
+    // Arcium accounts
+    pub arcium_program: Program<'info, Arcium>,
+    /// CHECK: MXE account
+    pub mxe_account: AccountInfo<'info>,
+    /// CHECK: Cluster account
+    pub cluster_account: AccountInfo<'info>,
+    /// CHECK: Fee pool
+    pub pool_account: AccountInfo<'info>,
+    /// CHECK: Clock account
+    pub clock_account: AccountInfo<'info>,
+    /// CHECK: Mempool
+    pub mempool_account: AccountInfo<'info>,
+    /// CHECK: Executing pool
+    pub executing_pool: AccountInfo<'info>,
+    /// CHECK: Computation account
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    /// CHECK: Comp def account
+    pub comp_def_account: AccountInfo<'info>,
+    /// CHECK: Computation offset account
+    #[account(
+        mut,
+        seeds = [COMPUTATION_OFFSET_SEED],
+        bump = computation_offset_account.bump
+    )]
+    pub computation_offset_account: Account<'info, ComputationOffsetState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PendingComputation::INIT_SPACE,
+        seeds = [PENDING_COMPUTATION_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub pending_computation: Account<'info, PendingComputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealResultsCallback<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [PENDING_COMPUTATION_SEED, proposal.key().as_ref()],
+        bump = pending_computation.bump,
+        constraint = pending_computation.proposal == proposal.key() @ VotingError::ComputationMismatch
+    )]
+    pub pending_computation: Account<'info, PendingComputation>,
+
+    /// CHECK: Must be the Arcium computation account the reveal was queued on
+    #[account(
+        address = pending_computation.computation_account @ VotingError::ComputationMismatch,
+        owner = Arcium::id() @ VotingError::ComputationMismatch
+    )]
+    pub computation_account: AccountInfo<'info>,
+
+    /// Tally the reveal was queued on, checked against `input_commitment`
+    #[account(constraint = tally.proposal == proposal.key() @ VotingError::ComputationMismatch)]
+    pub tally: Account<'info, Tally>,
+
+    /// CHECK: Instructions sysvar, read to authenticate the Arcium callback
+    #[account(address = solana_instructions_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    #[account(mut, seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    /// CHECK: Proposal authority, refunded the pending-computation rent
+    #[account(mut, address = proposal.authority @ VotingError::Unauthorized)]
+    pub authority: AccountInfo<'info>,
+
+    /// CHECK: Sign PDA ensures this callback was invoked via Arcium CPI
+    #[account(
+        seeds = [SIGN_SEED],
+        bump,
+        signer
+    )]
+    pub sign_seed: AccountInfo<'info>,
+
+    /// Open recount, when the reveal was queued by `request_recount`
+    #[account(
+        mut,
+        seeds = [RECOUNT_SEED, proposal.key().as_ref()],
+        bump = recount.bump
+    )]
+    pub recount: Option<Account<'info, Recount>>,
+}
+
+#[derive(Accounts)]
+pub struct RequestRecount<'info> {
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    pub tally: Account<'info, Tally>,
+
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + Recount::INIT_SPACE,
+        seeds = [RECOUNT_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub recount: Account<'info, Recount>,
+
+    #[account(
+        mut,
+        seeds = [RECOUNT_BOND_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub recount_bond: SystemAccount<'info>,
+
+    /// CHECK: Sign PDA
+    #[account(seeds = [SIGN_SEED], bump)]
+    pub sign_seed: AccountInfo<'info>,
+
+    // Arcium accounts
+    pub arcium_program: Program<'info, Arcium>,
+    /// CHECK: MXE account
+    pub mxe_account: AccountInfo<'info>,
+    /// CHECK: Cluster account
+    pub cluster_account: AccountInfo<'info>,
+    /// CHECK: Fee pool
+    pub pool_account: AccountInfo<'info>,
+    /// CHECK: Clock account
+    pub clock_account: AccountInfo<'info>,
+    /// CHECK: Mempool
+    pub mempool_account: AccountInfo<'info>,
+    /// CHECK: Executing pool
+    pub executing_pool: AccountInfo<'info>,
+    /// CHECK: Computation account
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    /// CHECK: Comp def account
+    pub comp_def_account: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = requester,
+        space = 8 + PendingComputation::INIT_SPACE,
+        seeds = [PENDING_COMPUTATION_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub pending_computation: Account<'info, PendingComputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeResults<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireRecount<'info> {
+    #[account(mut, address = recount.proposal @ VotingError::ComputationMismatch)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [RECOUNT_SEED, proposal.key().as_ref()],
+        bump = recount.bump
+    )]
+    pub recount: Account<'info, Recount>,
+
+    #[account(
+        mut,
+        close = requester,
+        seeds = [PENDING_COMPUTATION_SEED, proposal.key().as_ref()],
+        bump = pending_computation.bump
+    )]
+    pub pending_computation: Account<'info, PendingComputation>,
+
+    /// Paid the pending-computation rent in `request_recount`
+    #[account(mut, address = recount.requester @ VotingError::InvalidRecipient)]
+    pub requester: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewRecount<'info> {
+    pub council: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = council @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [RECOUNT_SEED, proposal.key().as_ref()],
+        bump = recount.bump
+    )]
+    pub recount: Account<'info, Recount>,
+}
+
+#[derive(Accounts)]
+pub struct SettleRecount<'info> {
+    #[account(
+        mut,
+        close = requester,
+        seeds = [RECOUNT_SEED, recount.proposal.as_ref()],
+        bump = recount.bump
+    )]
+    pub recount: Account<'info, Recount>,
+
+    #[account(mut, seeds = [RECOUNT_BOND_SEED, recount.proposal.as_ref()], bump)]
+    pub recount_bond: SystemAccount<'info>,
+
+    #[account(mut, address = recount.requester @ VotingError::InvalidRecipient)]
+    pub requester: SystemAccount<'info>,
+
+    #[account(mut, seeds = [SOL_TREASURY_SEED], bump)]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeriveNullifier<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    /// CHECK: Sign PDA
+    #[account(seeds = [SIGN_SEED], bump)]
+    pub sign_seed: AccountInfo<'info>,
+
+    // Arcium accounts
+    pub arcium_program: Program<'info, Arcium>,
+    /// CHECK: MXE account
+    pub mxe_account: AccountInfo<'info>,
+    /// CHECK: Cluster account
+    pub cluster_account: AccountInfo<'info>,
+    /// CHECK: Fee pool
+    pub pool_account: AccountInfo<'info>,
+    /// CHECK: Clock account
+    pub clock_account: AccountInfo<'info>,
+    /// CHECK: Mempool
+    pub mempool_account: AccountInfo<'info>,
+    /// CHECK: Executing pool
+    pub executing_pool: AccountInfo<'info>,
+    /// CHECK: Computation account
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    /// CHECK: Comp def account
+    pub comp_def_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(nullifier: [u8; 32])]
+pub struct DeriveNullifierCallback<'info> {
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + NullifierRecord::INIT_SPACE,
+        seeds = [NULLIFIER_SEED, proposal.key().as_ref(), nullifier.as_ref()],
+        bump
+    )]
+    pub nullifier_record: Account<'info, NullifierRecord>,
+
+    /// CHECK: Sign PDA ensures this callback was invoked via Arcium CPI
+    #[account(
+        seeds = [SIGN_SEED],
+        bump,
+        signer
+    )]
+    pub sign_seed: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveTie<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        seeds = [TALLY_SEED, proposal.key().as_ref()],
+        bump = tally.bump
+    )]
+    pub tally: Account<'info, Tally>,
+
+    /// CHECK: Sign PDA
+    #[account(seeds = [SIGN_SEED], bump)]
+    pub sign_seed: AccountInfo<'info>,
+
+    // Arcium accounts
+    pub arcium_program: Program<'info, Arcium>,
+    /// CHECK: MXE account
+    pub mxe_account: AccountInfo<'info>,
+    /// CHECK: Cluster account
+    pub cluster_account: AccountInfo<'info>,
+    /// CHECK: Fee pool
+    pub pool_account: AccountInfo<'info>,
+    /// CHECK: Clock account
+    pub clock_account: AccountInfo<'info>,
+    /// CHECK: Mempool
+    pub mempool_account: AccountInfo<'info>,
+    /// CHECK: Executing pool
+    pub executing_pool: AccountInfo<'info>,
+    /// CHECK: Computation account
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    /// CHECK: Comp def account
+    pub comp_def_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveTieCallback<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Sign PDA ensures this callback was invoked via Arcium CPI
+    #[account(
+        seeds = [SIGN_SEED],
+        bump,
+        signer
+    )]
+    pub sign_seed: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitCompDef<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CompDefState::INIT_SPACE,
+        seeds = [b"comp_def_state"],
+        bump
+    )]
+    pub comp_def_state: Account<'info, CompDefState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitComputationOffset<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ComputationOffsetState::INIT_SPACE,
+        seeds = [COMPUTATION_OFFSET_SEED],
+        bump
+    )]
+    pub computation_offset_account: Account<'info, ComputationOffsetState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ==================== DEV MODE ACCOUNT STRUCTURES ====================
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct DevCreateProposal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [PROPOSAL_SEED, proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Optional committee scope — the authority must be one of its proposers
+    pub committee: Option<Account<'info, Committee>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DevInitTally<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Tally::INIT_SPACE,
+        seeds = [TALLY_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub tally: Account<'info, Tally>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DevCastVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub tally: Account<'info, Tally>,
+
+    /// Gate token account (Token and StakePool gates)
+    pub voter_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Native stake account, parsed in `active_stake` (StakeAccount gate)
+    pub voter_stake_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL stake pool, parsed in `stake_pool_lamports` (StakePool gate)
+    pub stake_pool: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteRecord::INIT_SPACE,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    /// Drawn jury, required when the proposal uses sortition
+    #[account(seeds = [JURY_ROSTER_SEED, proposal.key().as_ref()], bump = jury_roster.bump)]
+    pub jury_roster: Option<Account<'info, JuryRoster>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DevRevealResults<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Numbers reveals in `revealed_proposals`
+    #[account(mut, seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateVote<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    /// CHECK: The delegate address (any valid pubkey)
+    pub delegate: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = delegator,
+        space = 8 + Delegation::INIT_SPACE,
+        seeds = [DELEGATION_SEED, delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    /// Delegate's gate-mint account, required with `require_delegate_eligibility`
+    pub delegate_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetVotingPower<'info> {
+    /// CHECK: Wallet whose power is resolved; read-only, no signature needed
+    pub voter: AccountInfo<'info>,
+
+    pub proposal: Account<'info, Proposal>,
+
+    pub voter_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Native stake account, parsed in `active_stake` (StakeAccount gate)
+    pub voter_stake_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL stake pool, parsed in `stake_pool_lamports` (StakePool gate)
+    pub stake_pool: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Locked or vesting position of the gate mint, parsed in
+    /// `with_vesting_power` (Token gate)
+    pub vesting_position: Option<UncheckedAccount<'info>>,
+
+    /// Voter's gate-mint escrow (snapshot proposals and `same_slot_guard`)
+    pub voter_escrow: Option<Account<'info, VoterEscrow>>,
+
+    /// Voter's reputation (Reputation weighting)
+    #[account(seeds = [REPUTATION_SEED, voter.key().as_ref()], bump = reputation.bump)]
+    pub reputation: Option<Account<'info, Reputation>>,
+
+    /// Voter's own outgoing delegation, if any
+    #[account(seeds = [DELEGATION_SEED, voter.key().as_ref()], bump = delegation.bump)]
+    pub delegation: Option<Account<'info, Delegation>>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+}
+
+#[derive(Accounts)]
+pub struct GetProposalState<'info> {
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct GetTallyStatus<'info> {
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptDelegation<'info> {
+    pub delegate: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DELEGATION_SEED, delegation.delegator.as_ref()],
+        bump = delegation.bump,
+        constraint = delegation.delegate == delegate.key() @ VotingError::InvalidDelegation
+    )]
+    pub delegation: Account<'info, Delegation>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateVoteSigned<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: Authorizes via the Ed25519-signed delegation message
+    pub delegator: AccountInfo<'info>,
+
+    /// CHECK: The delegate address (any valid pubkey)
+    pub delegate: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + Delegation::INIT_SPACE,
+        seeds = [DELEGATION_SEED, delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + DelegationNonce::INIT_SPACE,
+        seeds = [DELEGATION_NONCE_SEED, delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation_nonce: Account<'info, DelegationNonce>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    /// Delegate's gate-mint account, required with `require_delegate_eligibility`
+    pub delegate_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Instructions sysvar, read for the Ed25519 signature check
+    #[account(address = solana_instructions_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = delegator,
+        seeds = [DELEGATION_SEED, delegator.key().as_ref()],
+        bump = delegation.bump,
+        constraint = delegation.delegator == delegator.key()
+    )]
+    pub delegation: Account<'info, Delegation>,
+}
+
+#[derive(Accounts)]
+pub struct SettleDeposit<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        mut,
+        seeds = [DEPOSIT_ESCROW_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub deposit_escrow: Account<'info, TokenAccount>,
+
+    /// Creator's token account on return, treasury's on slash
+    #[account(
+        mut,
+        constraint = destination.mint == deposit_escrow.mint @ VotingError::InvalidTokenMint
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    /// Treasury's token account, required for a partial (graded) slash
+    #[account(
+        mut,
+        constraint = treasury_destination.mint == deposit_escrow.mint @ VotingError::InvalidTokenMint
+    )]
+    pub treasury_destination: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SettleSolBond<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        mut,
+        seeds = [SOL_BOND_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub sol_bond: SystemAccount<'info>,
+
+    #[account(mut, address = proposal.authority @ VotingError::InvalidRecipient)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(mut, seeds = [SOL_TREASURY_SEED], bump)]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitDaoConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DaoConfig::INIT_SPACE,
+        seeds = [DAO_CONFIG_SEED],
+        bump
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(committee_id: u64)]
+pub struct CreateCommittee<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = authority @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Committee::INIT_SPACE,
+        seeds = [COMMITTEE_SEED, committee_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub committee: Account<'info, Committee>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetExecutionPayload<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ExecutionPayload::INIT_SPACE,
+        seeds = [EXECUTION_PAYLOAD_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub execution_payload: Account<'info, ExecutionPayload>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ValidatePayload<'info> {
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [EXECUTION_PAYLOAD_SEED, proposal.key().as_ref()],
+        bump = execution_payload.bump
+    )]
+    pub execution_payload: Account<'info, ExecutionPayload>,
+
+    #[account(seeds = [SOL_TREASURY_SEED], bump)]
+    pub sol_treasury: SystemAccount<'info>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    /// Committee the proposal is scoped to, required for committee proposals
+    pub committee: Option<Account<'info, Committee>>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseGrantTranche<'info> {
+    pub council: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = council @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(mut)]
+    pub grant: Account<'info, Grant>,
+
+    #[account(mut)]
+    pub grant_escrow: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClawbackGrant<'info> {
+    #[account(mut)]
+    pub grant: Account<'info, Grant>,
+
+    #[account(mut, address = grant.escrow @ VotingError::InvalidTreasuryVault)]
+    pub grant_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = treasury_vault.owner == treasury_authority.key() @ VotingError::InvalidTreasuryVault,
+        constraint = treasury_vault.mint == grant.mint @ VotingError::InvalidTokenMint
+    )]
+    pub treasury_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Treasury PDA that owns the treasury vaults
+    #[account(seeds = [TREASURY_SEED], bump)]
+    pub treasury_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimStream<'info> {
+    #[account(mut)]
+    pub stream: Account<'info, Stream>,
+
+    #[account(mut, address = stream.escrow @ VotingError::InvalidTreasuryVault)]
+    pub stream_escrow: Account<'info, TokenAccount>,
+
+    #[account(mut, address = stream.recipient @ VotingError::InvalidRecipient)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    /// Pays rent for the grant, stream and obligation accounts actions open
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [EXECUTION_PAYLOAD_SEED, proposal.key().as_ref()],
+        bump = execution_payload.bump,
+        constraint = execution_payload.proposal == proposal.key()
+    )]
+    pub execution_payload: Account<'info, ExecutionPayload>,
+
+    /// CHECK: Signing PDA for the action (treasury, committee vault, or mint
+    /// authority) — validated against the action and proposal scope
+    pub vault_authority: AccountInfo<'info>,
+
+    /// Committee the proposal is scoped to, or the committee being re-budgeted
+    #[account(mut)]
+    pub committee: Option<Account<'info, Committee>>,
+
+    #[account(mut)]
+    pub source_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub recipient_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Proposal whose execution is cancelled by a `CancelExecution` action
+    #[account(mut)]
+    pub target_proposal: Option<Account<'info, Proposal>>,
+
+    /// DAO configuration for `UpdateConfig` actions
+    #[account(mut, seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Option<Account<'info, DaoConfig>>,
+
+    /// Mint for `MintTokens` actions — mint authority must be the program PDA
+    #[account(mut)]
+    pub mint: Option<Account<'info, Mint>>,
+
+    /// SOL treasury PDA (`["sol_treasury"]`) for native lamport actions
+    #[account(mut)]
+    pub sol_treasury: Option<SystemAccount<'info>>,
+
+    /// CHECK: Lamport recipient — validated against the execution payload
+    #[account(mut)]
+    pub recipient: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: `["spend_tracker", mint]` PDA for treasury spends (empty when the
+    /// mint has no limit) or a `spend_limit` change — validated where used
+    #[account(mut)]
+    pub spend_tracker: Option<UncheckedAccount<'info>>,
+
+    /// Grant opened by a `MilestoneGrant` action, keyed by its payload index
+    #[account(
+        init,
+        payer = executor,
+        space = 8 + Grant::INIT_SPACE,
+        seeds = [
+            GRANT_SEED,
+            proposal.key().as_ref(),
+            &[execution_payload.actions_executed]
+        ],
+        bump
+    )]
+    pub grant: Option<Account<'info, Grant>>,
+
+    /// Grant released by a `ReleaseTranche` action
+    #[account(mut)]
+    pub target_grant: Option<Account<'info, Grant>>,
+
+    /// Token account owned by the grant PDA holding its unreleased tranches
+    #[account(mut)]
+    pub grant_escrow: Option<Account<'info, TokenAccount>>,
+
+    /// Stream opened by a `StreamPayment` action, keyed by its payload index
+    #[account(
+        init,
+        payer = executor,
+        space = 8 + Stream::INIT_SPACE,
+        seeds = [
+            STREAM_SEED,
+            proposal.key().as_ref(),
+            &[execution_payload.actions_executed]
+        ],
+        bump
+    )]
+    pub stream: Option<Account<'info, Stream>>,
+
+    /// Token account owned by the stream PDA holding its unclaimed amount
+    #[account(mut)]
+    pub stream_escrow: Option<Account<'info, TokenAccount>>,
+
+    /// Outbox sequencing `PostObligation` actions
+    #[account(mut, seeds = [OUTBOX_SEED], bump = outbox.bump)]
+    pub outbox: Option<Account<'info, Outbox>>,
+
+    /// Obligation posted by a `PostObligation` action, keyed by its payload
+    /// index
+    #[account(
+        init,
+        payer = executor,
+        space = 8 + Obligation::INIT_SPACE,
+        seeds = [
+            OBLIGATION_SEED,
+            proposal.key().as_ref(),
+            &[execution_payload.actions_executed]
+        ],
+        bump
+    )]
+    pub obligation: Option<Account<'info, Obligation>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitOutbox<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Outbox::INIT_SPACE,
+        seeds = [OUTBOX_SEED],
+        bump
+    )]
+    pub outbox: Account<'info, Outbox>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcknowledgeObligation<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        constraint = dao_config.outbox_executor == executor.key() @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            OBLIGATION_SEED,
+            obligation.proposal.as_ref(),
+            &[obligation.action_index]
+        ],
+        bump = obligation.bump
+    )]
+    pub obligation: Account<'info, Obligation>,
+}
+
+#[derive(Accounts)]
+pub struct CancelExecution<'info> {
+    pub council: Signer<'info>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+#[instruction(decimals: u8)]
+pub struct InitGovernanceMint<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = authority @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [GOVERNANCE_MINT_SEED],
+        bump,
+        mint::decimals = decimals,
+        mint::authority = power_authority,
+        mint::freeze_authority = power_authority
+    )]
+    pub governance_mint: Account<'info, Mint>,
+
+    /// CHECK: Power authority PDA — mint and freeze authority of the power token
+    #[account(seeds = [POWER_AUTHORITY_SEED], bump)]
+    pub power_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterPowerAsset<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = authority @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    pub asset_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PowerAsset::INIT_SPACE,
+        seeds = [POWER_ASSET_SEED, asset_mint.key().as_ref()],
+        bump
+    )]
+    pub power_asset: Account<'info, PowerAsset>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [POWER_VAULT_SEED, asset_mint.key().as_ref()],
+        bump,
+        token::mint = asset_mint,
+        token::authority = power_authority
+    )]
+    pub power_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Power authority PDA — owner of the asset vaults
+    #[account(seeds = [POWER_AUTHORITY_SEED], bump)]
+    pub power_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LockVesting<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ VotingError::InvalidTokenAccount,
+        constraint = owner_token_account.mint == mint.key() @ VotingError::InvalidTokenMint
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + VestingPosition::INIT_SPACE,
+        seeds = [VESTING_SEED, owner.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vesting_position: Account<'info, VestingPosition>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [VESTING_VAULT_SEED, vesting_position.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vesting_position
+    )]
+    pub vesting_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawVested<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VESTING_SEED, owner.key().as_ref(), vesting_position.mint.as_ref()],
+        bump = vesting_position.bump,
+        has_one = owner @ VotingError::Unauthorized
+    )]
+    pub vesting_position: Account<'info, VestingPosition>,
+
+    #[account(mut, address = vesting_position.vault)]
+    pub vesting_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ VotingError::InvalidTokenAccount,
+        constraint = owner_token_account.mint == vesting_position.mint @ VotingError::InvalidTokenMint
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DepositForPower<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        mut,
+        seeds = [POWER_ASSET_SEED, power_asset.asset_mint.as_ref()],
+        bump = power_asset.bump
+    )]
+    pub power_asset: Account<'info, PowerAsset>,
+
+    #[account(mut, address = power_asset.vault)]
+    pub power_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = owner_asset_account.owner == owner.key() @ VotingError::InvalidTokenAccount,
+        constraint = owner_asset_account.mint == power_asset.asset_mint @ VotingError::InvalidTokenMint
+    )]
+    pub owner_asset_account: Account<'info, TokenAccount>,
+
+    #[account(mut, address = dao_config.governance_mint @ VotingError::InvalidTokenMint)]
+    pub governance_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = owner_power_account.owner == owner.key() @ VotingError::InvalidTokenAccount,
+        constraint = owner_power_account.mint == governance_mint.key() @ VotingError::InvalidTokenMint
+    )]
+    pub owner_power_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + PowerDeposit::INIT_SPACE,
+        seeds = [POWER_DEPOSIT_SEED, power_asset.asset_mint.as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub power_deposit: Account<'info, PowerDeposit>,
+
+    /// CHECK: Power authority PDA
+    #[account(seeds = [POWER_AUTHORITY_SEED], bump)]
+    pub power_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPower<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        mut,
+        seeds = [POWER_ASSET_SEED, power_asset.asset_mint.as_ref()],
+        bump = power_asset.bump
+    )]
+    pub power_asset: Account<'info, PowerAsset>,
+
+    #[account(mut, address = power_asset.vault)]
+    pub power_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = owner_asset_account.owner == owner.key() @ VotingError::InvalidTokenAccount,
+        constraint = owner_asset_account.mint == power_asset.asset_mint @ VotingError::InvalidTokenMint
+    )]
+    pub owner_asset_account: Account<'info, TokenAccount>,
+
+    #[account(mut, address = dao_config.governance_mint @ VotingError::InvalidTokenMint)]
+    pub governance_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = owner_power_account.owner == owner.key() @ VotingError::InvalidTokenAccount,
+        constraint = owner_power_account.mint == governance_mint.key() @ VotingError::InvalidTokenMint
+    )]
+    pub owner_power_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [POWER_DEPOSIT_SEED, power_asset.asset_mint.as_ref(), owner.key().as_ref()],
+        bump = power_deposit.bump,
+        has_one = owner @ VotingError::Unauthorized
+    )]
+    pub power_deposit: Account<'info, PowerDeposit>,
+
+    /// CHECK: Power authority PDA
+    #[account(seeds = [POWER_AUTHORITY_SEED], bump)]
+    pub power_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CompactVoteRecords<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+#[instruction(locale: [u8; 8])]
+pub struct RegisterTranslation<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(has_one = authority @ VotingError::Unauthorized)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProposalTranslation::INIT_SPACE,
+        seeds = [TRANSLATION_SEED, proposal.key().as_ref(), locale.as_ref()],
+        bump
+    )]
+    pub translation: Account<'info, ProposalTranslation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttestResults<'info> {
+    #[account(mut)]
+    pub observer: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    pub tally: Account<'info, Tally>,
+
+    #[account(
+        init,
+        payer = observer,
+        space = 8 + Attestation::INIT_SPACE,
+        seeds = [ATTESTATION_SEED, proposal.key().as_ref(), observer.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ArchiveProposal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(has_one = authority @ VotingError::Unauthorized)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProposalArchive::INIT_SPACE,
+        seeds = [PROPOSAL_ARCHIVE_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_archive: Account<'info, ProposalArchive>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuditBundle::INIT_SPACE,
+        seeds = [AUDIT_BUNDLE_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub audit_bundle: Account<'info, AuditBundle>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority @ VotingError::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// The archive must exist — closing is only allowed after archival
+    #[account(
+        seeds = [PROPOSAL_ARCHIVE_SEED, proposal.key().as_ref()],
+        bump = proposal_archive.bump
+    )]
+    pub proposal_archive: Account<'info, ProposalArchive>,
+
+    #[account(
+        mut,
+        close = authority,
+        constraint = tally.proposal == proposal.key() @ VotingError::InvalidTargetProposal
+    )]
+    pub tally: Option<Account<'info, Tally>>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct DepositRevenue<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RevenueEpoch::INIT_SPACE,
+        seeds = [REVENUE_EPOCH_SEED, epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub revenue_epoch: Account<'info, RevenueEpoch>,
+
+    #[account(mut, seeds = [SOL_TREASURY_SEED], bump)]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct RegisterRevenueProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RevenueEpoch::INIT_SPACE,
+        seeds = [REVENUE_EPOCH_SEED, epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub revenue_epoch: Account<'info, RevenueEpoch>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct ClaimRevenue<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        mut,
+        seeds = [REVENUE_EPOCH_SEED, epoch.to_le_bytes().as_ref()],
+        bump = revenue_epoch.bump
+    )]
+    pub revenue_epoch: Account<'info, RevenueEpoch>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + RevenueClaim::INIT_SPACE,
+        seeds = [REVENUE_CLAIM_SEED, epoch.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub revenue_claim: Account<'info, RevenueClaim>,
+
+    #[account(
+        seeds = [VOTE_RECORD_SEED, vote_record.proposal.as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump,
+        constraint = vote_record.voter == voter.key() @ VotingError::Unauthorized
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(mut, seeds = [SOL_TREASURY_SEED], bump)]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PingDeadline<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(mut, seeds = [SOL_TREASURY_SEED], bump)]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestRandomness<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority @ VotingError::Unauthorized)]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Switchboard randomness account, parsed in `switchboard_randomness`
+    pub randomness_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConsumeRandomness<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Must be the account bound by `request_randomness`
+    #[account(address = proposal.randomness_account @ VotingError::InvalidRandomness)]
+    pub randomness_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTallyShards<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority @ VotingError::Unauthorized)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct SetSnapshotRoot<'info> {
+    pub snapshot_oracle: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = snapshot_oracle @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct PostOracleSnapshot<'info> {
+    pub snapshot_oracle: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = snapshot_oracle @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterWalletProfile<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub wallet: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = 8 + WalletProfile::INIT_SPACE,
+        seeds = [WALLET_PROFILE_SEED, wallet.key().as_ref()],
+        bump
+    )]
+    pub wallet_profile: Account<'info, WalletProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositEscrow<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ VotingError::InvalidTokenAccount,
+        constraint = owner_token_account.mint == mint.key() @ VotingError::InvalidTokenMint
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + VoterEscrow::INIT_SPACE,
+        seeds = [VOTER_ESCROW_SEED, owner.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub voter_escrow: Account<'info, VoterEscrow>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [ESCROW_VAULT_SEED, voter_escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = voter_escrow
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawEscrow<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VOTER_ESCROW_SEED, owner.key().as_ref(), voter_escrow.mint.as_ref()],
+        bump = voter_escrow.bump,
+        has_one = owner @ VotingError::Unauthorized
+    )]
+    pub voter_escrow: Account<'info, VoterEscrow>,
+
+    #[account(mut, address = voter_escrow.vault)]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ VotingError::InvalidTokenAccount,
+        constraint = owner_token_account.mint == voter_escrow.mint @ VotingError::InvalidTokenMint
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateLinks<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority @ VotingError::Unauthorized)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct InitTallyShard<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(has_one = authority @ VotingError::Unauthorized)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    /// CHECK: Sign PDA
+    #[account(seeds = [SIGN_SEED], bump)]
+    pub sign_seed: AccountInfo<'info>,
+
+    // Arcium accounts
+    pub arcium_program: Program<'info, Arcium>,
+    /// CHECK: MXE account
+    pub mxe_account: AccountInfo<'info>,
+    /// CHECK: Cluster account
+    pub cluster_account: AccountInfo<'info>,
+    /// CHECK: Fee pool
+    pub pool_account: AccountInfo<'info>,
+    /// CHECK: Clock account
+    pub clock_account: AccountInfo<'info>,
+    /// CHECK: Mempool
+    pub mempool_account: AccountInfo<'info>,
+    /// CHECK: Executing pool
+    pub executing_pool: AccountInfo<'info>,
+    /// CHECK: Computation account
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    /// CHECK: Comp def account
+    pub comp_def_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(shard: u8)]
+pub struct InitTallyShardCallback<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Tally::INIT_SPACE,
+        seeds = [TALLY_SEED, proposal.key().as_ref(), &[shard]],
+        bump
+    )]
+    pub tally: Account<'info, Tally>,
+
+    /// CHECK: Sign PDA ensures this callback was invoked via Arcium CPI
+    #[account(
+        seeds = [SIGN_SEED],
+        bump,
+        signer
+    )]
+    pub sign_seed: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(shard: u8)]
+pub struct MergeTallyShard<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority @ VotingError::Unauthorized)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        seeds = [TALLY_SEED, proposal.key().as_ref()],
+        bump = tally.bump
+    )]
+    pub tally: Account<'info, Tally>,
+
+    #[account(
+        seeds = [TALLY_SEED, proposal.key().as_ref(), &[shard]],
+        bump = shard_tally.bump
+    )]
+    pub shard_tally: Account<'info, Tally>,
+
+    /// CHECK: Sign PDA
+    #[account(seeds = [SIGN_SEED], bump)]
+    pub sign_seed: AccountInfo<'info>,
+
+    // Arcium accounts
+    pub arcium_program: Program<'info, Arcium>,
+    /// CHECK: MXE account
+    pub mxe_account: AccountInfo<'info>,
+    /// CHECK: Cluster account
+    pub cluster_account: AccountInfo<'info>,
+    /// CHECK: Fee pool
+    pub pool_account: AccountInfo<'info>,
+    /// CHECK: Clock account
+    pub clock_account: AccountInfo<'info>,
+    /// CHECK: Mempool
+    pub mempool_account: AccountInfo<'info>,
+    /// CHECK: Executing pool
+    pub executing_pool: AccountInfo<'info>,
+    /// CHECK: Computation account
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    /// CHECK: Comp def account
+    pub comp_def_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MergeTallyShardCallback<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [TALLY_SEED, proposal.key().as_ref()],
+        bump = tally.bump
+    )]
+    pub tally: Account<'info, Tally>,
+
+    /// Shard the merge was queued for
+    #[account(
+        seeds = [TALLY_SEED, proposal.key().as_ref(), &[proposal.merging_shard]],
+        bump = shard_tally.bump
+    )]
+    pub shard_tally: Account<'info, Tally>,
+
+    /// CHECK: Sign PDA ensures this callback was invoked via Arcium CPI
+    #[account(
+        seeds = [SIGN_SEED],
+        bump,
+        signer
+    )]
+    pub sign_seed: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_mxe_program_id: Pubkey)]
+pub struct MigrateTally<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority @ VotingError::Unauthorized)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        seeds = [TALLY_SEED, proposal.key().as_ref()],
+        bump = tally.bump
+    )]
+    pub tally: Account<'info, Tally>,
+
+    /// CHECK: Cluster the tally is migrated to
+    pub new_cluster_account: AccountInfo<'info>,
+
+    /// MXE account of the target program; supplies the re-encryption key
+    #[account(address = mxe_acc(&new_mxe_program_id) @ VotingError::MxeNotApproved)]
+    pub new_mxe_account: Box<Account<'info, MXEAccount>>,
+
+    /// CHECK: Sign PDA
+    #[account(seeds = [SIGN_SEED], bump)]
+    pub sign_seed: AccountInfo<'info>,
+
+    // Arcium accounts
+    pub arcium_program: Program<'info, Arcium>,
+    /// CHECK: MXE account
+    pub mxe_account: AccountInfo<'info>,
+    /// CHECK: Cluster account
+    pub cluster_account: AccountInfo<'info>,
+    /// CHECK: Fee pool
+    pub pool_account: AccountInfo<'info>,
+    /// CHECK: Clock account
+    pub clock_account: AccountInfo<'info>,
+    /// CHECK: Mempool
+    pub mempool_account: AccountInfo<'info>,
+    /// CHECK: Executing pool
+    pub executing_pool: AccountInfo<'info>,
+    /// CHECK: Computation account
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    /// CHECK: Comp def account
+    pub comp_def_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTallyCallback<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [TALLY_SEED, proposal.key().as_ref()],
+        bump = tally.bump
+    )]
+    pub tally: Account<'info, Tally>,
+
+    /// CHECK: Sign PDA ensures this callback was invoked via Arcium CPI
+    #[account(
+        seeds = [SIGN_SEED],
+        bump,
+        signer
+    )]
+    pub sign_seed: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelTallyMigration<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct OpenJuryPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ VotingError::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + JuryPool::INIT_SPACE,
+        seeds = [JURY_POOL_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub jury_pool: Account<'info, JuryPool>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterJuror<'info> {
+    pub candidate: Signer<'info>,
+
+    #[account(address = jury_pool.proposal)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [JURY_POOL_SEED, jury_pool.proposal.as_ref()],
+        bump = jury_pool.bump
+    )]
+    pub jury_pool: Account<'info, JuryPool>,
+
+    /// Candidate's gate-mint escrow, proving its eligibility
+    #[account(
+        seeds = [VOTER_ESCROW_SEED, candidate.key().as_ref(), proposal.gate_mint.as_ref()],
+        bump = candidate_escrow.bump
+    )]
+    pub candidate_escrow: Account<'info, VoterEscrow>,
+}
+
+#[derive(Accounts)]
+pub struct SelectJury<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(has_one = authority @ VotingError::Unauthorized)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [JURY_POOL_SEED, proposal.key().as_ref()],
+        bump = jury_pool.bump
+    )]
+    pub jury_pool: Account<'info, JuryPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + JuryRoster::INIT_SPACE,
+        seeds = [JURY_ROSTER_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub jury_roster: Account<'info, JuryRoster>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct InitSpendTracker<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SpendTracker::INIT_SPACE,
+        seeds = [SPEND_TRACKER_SEED, mint.as_ref()],
+        bump
+    )]
+    pub spend_tracker: Account<'info, SpendTracker>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetReputation<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = authority @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    /// CHECK: Wallet whose reputation is set, only used as a seed
+    pub voter: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Reputation::INIT_SPACE,
+        seeds = [REPUTATION_SEED, voter.key().as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseProposalSlot<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_COUNTER_SEED, proposal.authority.as_ref()],
+        bump = proposal_counter.bump
+    )]
+    pub proposal_counter: Account<'info, ProposalCounter>,
+
+    #[account(mut, seeds = [SLOT_STAKE_SEED, proposal.key().as_ref()], bump)]
+    pub slot_stake: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == proposal.authority @ VotingError::InvalidRecipient,
+        constraint = creator_token_account.mint == slot_stake.mint @ VotingError::InvalidTokenMint
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct FlagProposal<'info> {
+    #[account(mut)]
+    pub moderator: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = moderator @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = moderator,
+        space = 8 + ModerationCase::INIT_SPACE,
+        seeds = [
+            MODERATION_SEED,
+            proposal.key().as_ref(),
+            &proposal.moderation_cases.to_le_bytes()
+        ],
+        bump
+    )]
+    pub moderation_case: Account<'info, ModerationCase>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AppealModeration<'info> {
+    #[account(mut, address = proposal.authority @ VotingError::Unauthorized)]
+    pub creator: Signer<'info>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [
+            MODERATION_SEED,
+            proposal.key().as_ref(),
+            &moderation_case.number.to_le_bytes()
+        ],
+        bump = moderation_case.bump
+    )]
+    pub moderation_case: Account<'info, ModerationCase>,
+
+    #[account(
+        mut,
+        seeds = [APPEAL_BOND_SEED, moderation_case.key().as_ref()],
+        bump
+    )]
+    pub appeal_bond: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveModeration<'info> {
+    pub council: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = council @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [
+            MODERATION_SEED,
+            proposal.key().as_ref(),
+            &moderation_case.number.to_le_bytes()
+        ],
+        bump = moderation_case.bump
+    )]
+    pub moderation_case: Account<'info, ModerationCase>,
+
+    #[account(
+        mut,
+        seeds = [APPEAL_BOND_SEED, moderation_case.key().as_ref()],
+        bump
+    )]
+    pub appeal_bond: SystemAccount<'info>,
+
+    #[account(mut, address = proposal.authority @ VotingError::InvalidRecipient)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(mut, seeds = [SOL_TREASURY_SEED], bump)]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireModeration<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [
+            MODERATION_SEED,
+            proposal.key().as_ref(),
+            &moderation_case.number.to_le_bytes()
+        ],
+        bump = moderation_case.bump
+    )]
+    pub moderation_case: Account<'info, ModerationCase>,
+}
+
+#[derive(Accounts)]
+pub struct SignPetition<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + Petition::INIT_SPACE,
+        seeds = [PETITION_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub petition: Account<'info, Petition>,
+
+    /// One signature per wallet
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + PetitionSignature::INIT_SPACE,
+        seeds = [PETITION_SIGNATURE_SEED, proposal.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub petition_signature: Account<'info, PetitionSignature>,
+
+    /// Signer's `tier_mint` escrow, resolving its voter tier
+    #[account(
+        seeds = [VOTER_ESCROW_SEED, signer.key().as_ref(), dao_config.tier_mint.as_ref()],
+        bump = tier_escrow.bump
+    )]
+    pub tier_escrow: Option<Account<'info, VoterEscrow>>,
+
+    /// Signer's gate-mint escrow, weighing the signature
+    #[account(
+        seeds = [VOTER_ESCROW_SEED, signer.key().as_ref(), proposal.gate_mint.as_ref()],
+        bump = signer_escrow.bump
+    )]
+    pub signer_escrow: Account<'info, VoterEscrow>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EndorseProposal<'info> {
+    #[account(mut)]
+    pub endorser: Signer<'info>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    /// One endorsement per wallet
+    #[account(
+        init,
+        payer = endorser,
+        space = 8 + Endorsement::INIT_SPACE,
+        seeds = [ENDORSEMENT_SEED, proposal.key().as_ref(), endorser.key().as_ref()],
+        bump
+    )]
+    pub endorsement: Account<'info, Endorsement>,
+
+    /// Endorser's gate-mint escrow, weighing the endorsement
+    #[account(
+        seeds = [VOTER_ESCROW_SEED, endorser.key().as_ref(), proposal.gate_mint.as_ref()],
+        bump = endorser_escrow.bump
+    )]
+    pub endorser_escrow: Account<'info, VoterEscrow>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireUnsponsored<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewPetition<'info> {
+    pub council: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = council @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [PETITION_SEED, proposal.key().as_ref()],
+        bump = petition.bump
+    )]
+    pub petition: Account<'info, Petition>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct DenyWallet<'info> {
+    #[account(mut)]
+    pub compliance_authority: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = compliance_authority @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        init,
+        payer = compliance_authority,
+        space = 8 + DenylistEntry::INIT_SPACE,
+        seeds = [DENYLIST_SEED, wallet.as_ref()],
+        bump
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AllowWallet<'info> {
+    #[account(mut)]
+    pub compliance_authority: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = compliance_authority @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        mut,
+        close = compliance_authority,
+        seeds = [DENYLIST_SEED, denylist_entry.wallet.as_ref()],
+        bump = denylist_entry.bump
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+}
+
+#[derive(Accounts)]
+pub struct ManageProgramAllowlist<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = authority @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterPriceFeed<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump = dao_config.bump,
+        has_one = authority @ VotingError::Unauthorized
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PriceFeed::INIT_SPACE,
+        seeds = [PRICE_FEED_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProveNoVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [DAO_CONFIG_SEED], bump = dao_config.bump)]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        seeds = [EXECUTION_PAYLOAD_SEED, proposal.key().as_ref()],
+        bump = execution_payload.bump
+    )]
+    pub execution_payload: Account<'info, ExecutionPayload>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    /// CHECK: Sign PDA
+    #[account(seeds = [SIGN_SEED], bump)]
+    pub sign_seed: AccountInfo<'info>,
+
+    // Arcium accounts
+    pub arcium_program: Program<'info, Arcium>,
+    /// CHECK: MXE account
+    pub mxe_account: AccountInfo<'info>,
+    /// CHECK: Cluster account
+    pub cluster_account: AccountInfo<'info>,
+    /// CHECK: Fee pool
+    pub pool_account: AccountInfo<'info>,
+    /// CHECK: Clock account
+    pub clock_account: AccountInfo<'info>,
+    /// CHECK: Mempool
+    pub mempool_account: AccountInfo<'info>,
+    /// CHECK: Executing pool
+    pub executing_pool: AccountInfo<'info>,
+    /// CHECK: Computation account
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    /// CHECK: Comp def account
+    pub comp_def_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyNoVoteCallback<'info> {
+    #[account(mut)]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    /// CHECK: Sign PDA ensures this callback was invoked via Arcium CPI
+    #[account(
+        seeds = [SIGN_SEED],
+        bump,
+        signer
+    )]
+    pub sign_seed: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Ragequit<'info> {
+    pub member: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [EXECUTION_PAYLOAD_SEED, proposal.key().as_ref()],
+        bump = execution_payload.bump
+    )]
+    pub execution_payload: Account<'info, ExecutionPayload>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), member.key().as_ref()],
+        bump = vote_record.bump,
+        constraint = vote_record.no_vote_proven @ VotingError::NoVoteNotProven
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(mut, address = proposal.gate_mint @ VotingError::InvalidTokenMint)]
+    pub gate_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = member_token_account.owner == member.key() @ VotingError::InvalidTokenAccount,
+        constraint = member_token_account.mint == gate_mint.key() @ VotingError::InvalidTokenMint,
+        constraint = !member_token_account.is_frozen() @ VotingError::GateTokensFrozen
+    )]
+    pub member_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = treasury_vault.owner == treasury_authority.key() @ VotingError::InvalidTreasuryVault
+    )]
+    pub treasury_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = member_receive_account.owner == member.key() @ VotingError::InvalidTokenAccount,
+        constraint = member_receive_account.mint == treasury_vault.mint @ VotingError::InvalidTokenMint
+    )]
+    pub member_receive_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Treasury PDA that owns the treasury vaults
+    #[account(seeds = [TREASURY_SEED], bump)]
+    pub treasury_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// ==================== STATE ACCOUNTS ====================
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub id: u64,
+    pub authority: Pubkey,
+    #[max_len(100)]
+    pub title: String,
+    #[max_len(5000)]
+    pub description: String,
+    pub voting_ends_at: i64,
+    /// Deadline slot derived from `voting_ends_at` at creation; voting needs
+    /// both clocks before their deadline, reveal needs both past it (0 = unset)
+    pub voting_ends_slot: u64,
+    /// Slot mode: `voting_ends_slot` alone bounds voting, reveal and callback
+    /// grace; `voting_ends_at` is a nominal estimate
+    pub slot_deadline: bool,
+    pub is_active: bool,
+    pub is_revealed: bool,
+    pub total_votes: u64,
+    /// Gate target: token mint, validator vote account (default = any) or stake pool
+    pub gate_mint: Pubkey,
+    /// Mints whose price feeds a `UsdValue` gate counts (empty otherwise)
+    #[max_len(MAX_GATE_MINTS)]
+    pub gate_mints: Vec<Pubkey>,
+    pub gate_kind: GateKind,
+    pub min_balance: u64,
+    pub weighting_mode: WeightingMode,
+    /// Revealed total: ballots under Equal weighting, summed weight otherwise
+    pub total_weight: u64,
+    /// Maximum number of ballots accepted (0 = uncapped)
+    pub max_voters: u64,
+    /// Ballots accepted by `cast_vote`, counted before the MXE callback lands so
+    /// queued votes cannot overshoot `max_voters`
+    pub ballots_cast: u64,
+    /// Sortition jury size (0 = open electorate)
+    pub jury_size: u8,
+    /// Snapshot of `DaoConfig.random_tie_break` at creation
+    pub random_tie_break: bool,
+    /// Revealed as an exact tie; `passed` is decided by `resolve_tie`
+    pub tie_pending: bool,
+    /// Computation offset (= queue slot) of the in-flight coin flip (0 = none)
+    pub tie_break_offset: u64,
+    /// Root of the `(voter, weight)` eligibility snapshot (zero = unset)
+    pub snapshot_root: [u8; 32],
+    /// Total weight of the eligibility snapshot
+    pub snapshot_total_weight: u64,
+    /// Slot at whose start Token-gate balances are measured from `VoterEscrow`
+    /// checkpoints (0 = live balances)
+    pub snapshot_slot: u64,
+    /// Slot the proposal was created in
+    pub created_slot: u64,
+    /// Root of the oracle-posted `(voter, weight)` snapshot (zero = unset)
+    pub oracle_root: [u8; 32],
+    /// Total weight of the oracle snapshot
+    pub oracle_total_weight: u64,
+    /// Voters may replace their ballot with `recast_vote`
+    pub revoting: bool,
+    /// Addresses barred from voting, directly or through delegation
+    /// (e.g. conflicted parties or the proposer)
+    #[max_len(MAX_EXCLUDED_VOTERS)]
+    pub excluded: Vec<Pubkey>,
+    /// Non-voting accepted delegators count with their delegate's ballot
+    pub delegate_fallback: bool,
+    /// Delegators and weight added by `apply_delegate_fallback`
+    pub fallback_delegators: u32,
+    pub fallback_weight: u64,
+    /// Position among the DAO's revealed proposals, from 1 (0 = unrevealed)
+    pub reveal_number: u64,
+    /// Voting stays closed until endorsements reach `sponsorship_bps`
+    pub awaiting_sponsorship: bool,
+    /// When voting opened; the creation time while awaiting sponsorship
+    pub voting_starts_at: i64,
+    /// End of the sponsorship period (0 = no sponsorship stage)
+    pub sponsorship_deadline: i64,
+    /// Total weight of `Endorsement`s recorded
+    pub endorsement_weight: u64,
+    /// Expired without sponsorship; the deposit settles at `unsponsored_slash_bps`
+    pub sponsorship_expired: bool,
+    /// Switchboard randomness account bound by `request_randomness`
+    pub randomness_account: Pubkey,
+    pub randomness_seed_slot: u64,
+    /// Revealed verifiable randomness (tie-breaks, jury selection, raffles)
+    pub randomness: Option<[u8; 32]>,
+    /// Unix timestamp of the last `ping_deadline` reminder (0 = none)
+    pub last_ping_at: i64,
+    /// Chained Merkle root of vote records closed by `compact_vote_records`
+    pub vote_records_root: [u8; 32],
+    pub compacted_records: u64,
+    /// Hash chain over every tally ciphertext update (see `extend_tally_lineage`)
+    pub tally_lineage: [u8; 32],
+    /// Hash chain over the computation offsets of accepted ballot callbacks
+    /// (see `extend_ballot_offsets`)
+    pub ballot_offsets_hash: [u8; 32],
+    /// Computation offset of the accepted reveal
+    pub reveal_computation_offset: u64,
+    /// Number of tally shards ballots are routed across (1 = single tally)
+    pub tally_shards: u8,
+    /// Bitmask of secondary shards already folded into the primary tally
+    pub shards_merged: u8,
+    /// Shard whose merge computation is in flight (0 = none)
+    pub merging_shard: u8,
+    /// Computation offset (= queue slot) of the in-flight merge
+    pub merge_offset: u64,
+    /// Vote computations queued whose callback has not landed yet
+    pub pending_computations: u32,
+    /// Vote computations abandoned by a forced reveal
+    pub dropped_computations: u32,
+    pub mxe_program_id: Pubkey,
+    /// Arcium cluster whose MXE key currently encrypts the tally
+    pub mxe_cluster: Pubkey,
+    /// Target of an in-flight `migrate_tally` (default = no migration)
+    pub pending_mxe_program_id: Pubkey,
+    pub pending_mxe_cluster: Pubkey,
+    /// Computation offset (= queue slot) of the in-flight `migrate_tally`
+    pub pending_mxe_offset: u64,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub abstain_votes: u64,
+    /// Minimum number of votes required for the result to be valid (0 = no quorum).
+    /// Measured in summed weight for weighted modes; lowered to the decayed
+    /// value at reveal
+    pub quorum: u64,
+    /// Decay schedule snapshotted from `DaoConfig` (0 = fixed quorum)
+    pub quorum_decay_period: i64,
+    pub quorum_floor_bps: u16,
+    /// Gate-mint supply frozen at creation; denominator for turnout and
+    /// `quorum_bps` (0 = not a token gate)
+    pub eligible_supply: u64,
+    /// Gate tokens burned through `ragequit`, netted out of `eligible_supply`
+    /// for later exits
+    pub ragequit_burned: u64,
+    /// V2: Passing threshold in basis points (e.g., 5001 = simple majority, 6667 = two-thirds)
+    pub threshold_bps: u16,
+    /// Denominator `threshold_bps` is measured against
+    pub threshold_base: ThresholdBase,
+    /// V2: Privacy level (0 = Full, 1 = Partial, 2 = Transparent)
+    pub privacy_level: u8,
+    /// V2: Whether the proposal passed its threshold check
+    pub passed: bool,
+    /// Typed links to external discussion, specs and audits
+    #[max_len(MAX_PROPOSAL_LINKS)]
+    pub links: Vec<ProposalLink>,
+    /// V2: Deposit amount locked by creator (returned if quorum met)
+    pub deposit_amount: u64,
+    /// V2: Whether the deposit has been returned or slashed
+    pub deposit_returned: bool,
+    /// Deposit is a lamport bond in the `["sol_bond", proposal]` PDA
+    pub bond_in_sol: bool,
+    /// Counts against the creator's `ProposalCounter` until `release_proposal_slot`
+    pub holds_rate_slot: bool,
+    /// V2: Execution delay in seconds after reveal (timelock for payload execution)
+    pub execution_delay: i64,
+    /// Binding proposals execute a payload; signaling ones only record
+    /// sentiment and are final as soon as they are revealed
+    pub kind: ProposalKind,
+    /// V2: Whether the on-chain action payload has been executed
+    pub executed: bool,
+    /// Whether queued execution was cancelled during the timelock
+    pub cancelled: bool,
+    /// Voting paused by a moderator flag until its `ModerationCase` resolves
+    pub flagged: bool,
+    /// `ModerationCase`s opened so far; the next case's number
+    pub moderation_cases: u16,
+    /// Wallet that executed the final payload action
+    pub executed_by: Pubkey,
+    /// Slot and unix timestamp at which execution completed
+    pub executed_slot: u64,
+    pub executed_at: i64,
+    /// Committee this proposal is scoped to (None = parent DAO)
+    pub committee: Option<Pubkey>,
+    /// Unix timestamp at which results were revealed (start of the timelock)
+    pub revealed_at: i64,
+    /// Matching `Attestation`s of the revealed results from registered
+    /// observers
+    pub attestations: u16,
+    /// Registered observers' `Attestation`s disputing the revealed results
+    pub attestation_disputes: u16,
+    /// Unix timestamp at which revealed results became final and executable
+    /// (0 = not final)
+    pub finalized_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct DaoConfig {
+    pub authority: Pubkey,
+    /// Token mint used for proposal deposits
+    pub deposit_mint: Pubkey,
+    /// Required deposit amount to create a proposal (USD if `deposit_in_usd`)
+    pub proposal_deposit: u64,
+    /// Whether `proposal_deposit` is denominated in USD (`USD_DECIMALS`) and
+    /// converted to deposit-mint units via its `PriceFeed` at creation time
+    pub deposit_in_usd: bool,
+    /// Take `proposal_deposit` as a lamport bond instead of deposit-mint tokens
+    /// (`deposit_in_usd` is ignored); slashed bonds go to the SOL treasury PDA
+    pub deposit_in_sol: bool,
+    /// Treasury address where slashed deposits go
+    pub treasury: Pubkey,
+    /// Whether to slash deposits when quorum is not met
+    pub slash_if_no_quorum: bool,
+    /// Quorum applied to new proposals created through `create_proposal`
+    pub default_quorum: u64,
+    /// Council key allowed to cancel queued executions (default = no council)
+    pub council: Pubkey,
+    /// Key managing the denylist (default = screening disabled)
+    pub compliance_authority: Pubkey,
+    /// Key allowed to flag proposals for moderation (default = no moderator)
+    pub moderator: Pubkey,
+    /// Key posting off-chain electorate snapshots (default = oracle mode off)
+    pub snapshot_oracle: Pubkey,
+    /// Off-chain executor key acknowledging `Outbox` obligations
+    pub outbox_executor: Pubkey,
+    /// Lamports a creator posts to appeal a moderation flag to the council
+    pub appeal_bond: u64,
+    /// Lamports posted to dispute a revealed tally (0 = recounts disabled)
+    pub recount_bond: u64,
+    /// Seconds after reveal during which results can be disputed before
+    /// `finalize_results`
+    pub dispute_window: i64,
+    /// Longest a signed ballot may stay valid, in seconds (0 = no cap)
+    pub max_ballot_validity: i64,
+    /// Lamports a relayer bonds in `register_relayer`
+    pub relayer_bond: u64,
+    /// Deposit-mint tokens each active proposal locks until its slot is
+    /// released (0 = holdings-based rate limit disabled)
+    pub tokens_per_active_proposal: u64,
+    /// Votes a creator needs among the last `proposer_vote_window` revealed
+    /// proposals (0 = participation gate disabled)
+    pub proposer_min_votes: u8,
+    pub proposer_vote_window: u8,
+    /// Proposals revealed so far; numbers `Proposal.reveal_number`
+    pub revealed_proposals: u64,
+    /// Endorsement weight (bps of eligible supply) a Token-gate proposal
+    /// needs before voting opens (0 = sponsorship disabled)
+    pub sponsorship_bps: u16,
+    /// Seconds a proposal may wait for sponsorship before it can be expired
+    pub sponsorship_period: i64,
+    /// Share of an expired unsponsored proposal's deposit forfeited to the treasury (bps)
+    pub unsponsored_slash_bps: u16,
+    /// Weigh live Token-gate ballots by escrowed balance as of the voting
+    /// slot's start instead of the wallet balance
+    pub same_slot_guard: bool,
+    /// Non-transferable governance-power mint (default = not initialized)
+    pub governance_mint: Pubkey,
+    /// Seconds before a voting deadline during which `ping_deadline` may fire
+    /// (0 = reminders disabled)
+    pub reminder_window: i64,
+    /// Minimum seconds between reminders for the same proposal
+    pub reminder_interval: i64,
+    /// Lamports paid from the SOL treasury to each successful reminder cranker
+    pub crank_reward: u64,
+    /// Seconds after `voting_ends_at` that late MXE vote callbacks are still
+    /// accepted and `reveal_results` waits for them
+    pub callback_grace_period: i64,
+    /// MXE program / cluster pairs new proposals and migrations may bind to
+    /// (empty = any)
+    #[max_len(MAX_APPROVED_MXES)]
+    pub approved_mxes: Vec<ApprovedMxe>,
+    /// Settle exact YES/NO ties on new proposals with an MPC coin flip
+    pub random_tie_break: bool,
+    /// Slash deposits on a turnout schedule instead of all-or-nothing:
+    /// full return at quorum, `graded_slash_partial_bps` slashed at half
+    /// quorum, full slash below
+    pub graded_slash: bool,
+    /// Share of the deposit slashed at half quorum under `graded_slash`
+    pub graded_slash_partial_bps: u16,
+    /// Share of `deposit_revenue` inflows set aside for that epoch's voters
+    pub revenue_share_bps: u16,
+    /// Seconds per revenue-sharing epoch (0 = revenue sharing disabled)
+    pub revenue_epoch_duration: i64,
+    /// Share of a delegated ballot's revenue claim passed back to the delegators
+    pub delegator_reward_share_bps: u16,
+    /// New delegations stay inactive until the delegate accepts them
+    pub require_delegate_acceptance: bool,
+    /// Delegates must hold `delegate_min_balance` of `delegate_gate_mint`
+    /// to receive a delegation
+    pub require_delegate_eligibility: bool,
+    pub delegate_gate_mint: Pubkey,
+    pub delegate_min_balance: u64,
+    /// Quorum for new token-weighted proposals as basis points of the gate
+    /// mint supply at creation (0 = `default_quorum` only; the higher applies)
+    pub quorum_bps: u16,
+    /// Seconds of voting over which a new proposal's quorum decays to its
+    /// floor (0 = fixed quorum)
+    pub quorum_decay_period: i64,
+    /// Decayed quorum floor as basis points of the starting quorum
+    pub quorum_floor_bps: u16,
+    /// What new proposals measure `threshold_bps` against
+    pub threshold_base: ThresholdBase,
+    /// Per-mint weight multipliers for multi-mint `UsdValue` gates
+    #[max_len(MAX_MINT_MULTIPLIERS)]
+    pub mint_multipliers: Vec<MintMultiplier>,
+    /// Mint whose escrowed balance places wallets in `voter_tiers`
+    pub tier_mint: Pubkey,
+    /// Balance bands by increasing `min_balance` (empty = tiers disabled)
+    #[max_len(MAX_VOTER_TIERS)]
+    pub voter_tiers: Vec<VoterTier>,
+    /// Programs execution payloads may `Invoke` besides token and system
+    #[max_len(MAX_ALLOWED_PROGRAMS)]
+    pub allowed_programs: Vec<Pubkey>,
+    /// Independent observers whose attestations count on proposals
+    #[max_len(MAX_OBSERVERS)]
+    pub observers: Vec<Pubkey>,
+    /// Programs that may create proposals through CPI
+    #[max_len(MAX_PROPOSER_PROGRAMS)]
+    pub proposer_programs: Vec<Pubkey>,
+    /// Turnout-boost schedule revenue epochs take on as proposals register
+    #[max_len(MAX_TURNOUT_BOOSTS)]
+    pub turnout_boosts: Vec<TurnoutBoost>,
+    /// Treasury lamports each revenue epoch may spend matching boosted claims
+    pub turnout_match_budget: u64,
+    /// Weight multiplier in basis points for gate tokens still locked in a
+    /// vesting position (10_000 = no boost)
+    pub vesting_boost_bps: u16,
+    pub bump: u8,
+}
+
+/// A sub-DAO with its own proposer set and a capped budget. Committee proposals
+/// execute against the committee sub-vault; parent-DAO proposals adjust the cap.
+#[account]
+#[derive(InitSpace)]
+pub struct Committee {
+    pub id: u64,
+    /// Parent DAO configuration this committee belongs to
+    pub dao_config: Pubkey,
+    #[max_len(32)]
+    pub name: String,
+    /// Wallets allowed to create committee-scoped proposals
+    #[max_len(MAX_COMMITTEE_PROPOSERS)]
+    pub proposers: Vec<Pubkey>,
+    /// Mint the committee budget is denominated in
+    pub budget_mint: Pubkey,
+    /// Maximum total amount committee proposals may spend
+    pub spending_cap: u64,
+    /// Amount already spent by executed committee proposals
+    pub spent: u64,
+    pub bump: u8,
+}
+
+/// An asset accepted as backing for governance power, held in `vault`.
+#[account]
+#[derive(InitSpace)]
+pub struct PowerAsset {
+    pub asset_mint: Pubkey,
+    pub vault: Pubkey,
+    pub total_deposited: u64,
+    /// Decimals of `asset_mint`, rescaled to the power mint's on deposit
+    pub decimals: u8,
+    pub bump: u8,
+}
+
+/// A holder's locked balance of one power asset.
+#[account]
+#[derive(InitSpace)]
+pub struct PowerDeposit {
+    pub owner: Pubkey,
+    pub asset_mint: Pubkey,
+    pub amount: u64,
+    /// Governance power minted for `amount`
+    pub power: u64,
+    pub bump: u8,
+}
+
+/// Which recently revealed proposals a wallet voted on.
+#[account]
+#[derive(InitSpace)]
+pub struct VoterProfile {
+    pub voter: Pubkey,
+    /// Highest `reveal_number` recorded
+    pub latest: u64,
+    /// Bit `i` set = voted on reveal number `latest - i`
+    pub participation: u64,
+    pub bump: u8,
+}
+
+/// Next sequence number a voter's signed ballot must carry.
+#[account]
+#[derive(InitSpace)]
+pub struct BallotSequence {
+    pub voter: Pubkey,
+    pub next: u64,
+    pub bump: u8,
+}
+
+/// Nonce a delegator's next signed delegation message must carry.
+#[account]
+#[derive(InitSpace)]
+pub struct DelegationNonce {
+    pub delegator: Pubkey,
+    pub next: u64,
+    pub bump: u8,
+}
+
+/// A bonded relayer allowed to submit signed ballots.
+#[account]
+#[derive(InitSpace)]
+pub struct Relayer {
+    pub authority: Pubkey,
+    /// Lamports held in the relayer bond PDA
+    pub bond: u64,
+    pub registered_at: i64,
+    pub ballots_relayed: u64,
+    /// Set by `begin_relayer_unbonding` (0 = active)
+    pub unbonding_at: i64,
+    pub bump: u8,
+}
+
+/// Funding parent and first-seen time of a wallet, for Sybil analytics.
+/// Created by `register_wallet_profile` or by the wallet's first ballot.
+#[account]
+#[derive(InitSpace)]
+pub struct WalletProfile {
+    pub wallet: Pubkey,
+    pub funded_by: Pubkey,
+    pub created_at: i64,
+    pub created_slot: u64,
+    pub bump: u8,
+}
+
+/// Gate tokens held in a program vault for snapshot voting and the
+/// `same_slot_guard`. Only
+/// `deposit_escrow` and `withdraw_escrow` move them, and each change is
+/// checkpointed, so the balance at any retained slot is exact.
+#[account]
+#[derive(InitSpace)]
+pub struct VoterEscrow {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64,
+    /// Slot of the first deposit; the escrow held nothing before it
+    pub created_slot: u64,
+    /// Amount after the last change in each slot, oldest first
+    #[max_len(MAX_ESCROW_CHECKPOINTS)]
+    pub checkpoints: Vec<Checkpoint>,
+    pub bump: u8,
+}
+
+/// `VoterEscrow` amount after the last change in `slot`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct Checkpoint {
+    pub slot: u64,
+    pub amount: u64,
+}
+
+/// Tokens locked by `lock_vesting`, released linearly from `start_at` to `end_at`.
+#[account]
+#[derive(InitSpace)]
+pub struct VestingPosition {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub total: u64,
+    pub withdrawn: u64,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub bump: u8,
+}
+
+/// How each ballot is weighted in the encrypted tally
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum WeightingMode {
+    /// One wallet, one vote
+    Equal,
+    /// Weight = gate balance
+    TokenWeighted,
+    /// Weight = integer square root of the gate balance
+    Quadratic,
+    /// Weight = the voter's `Reputation` score
+    Reputation,
+}
+
+/// Recoverable ballot guard that failed, carried by `VoteRejected`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RejectReason {
+    /// Proposal no longer active
+    VotingClosed,
+    /// Deadline passed
+    VotingEnded,
+    /// Voting halted by a flag, petition or MXE migration
+    Paused,
+    /// Proposal has not gathered its endorsements yet
+    AwaitingSponsorship,
+    /// `max_voters` ballots already cast
+    CapReached,
+    /// Voter is not on the proposal's jury
+    NotOnJury,
+    /// Voter is on the proposal's exclusion list
+    Excluded,
+    /// Gate account missing or not matching the proposal's gate
+    GateMismatch,
+    /// Gate balance below `min_balance`
+    InsufficientBalance,
+    /// Proposal votes only through its eligibility snapshot
+    SnapshotElectorate,
+    /// Proposal has no matching snapshot root, or the voter's proof fails
+    NotInSnapshot,
+    /// The voter's previous re-vote is still being tallied
+    BallotInFlight,
+    /// Petition can't be signed on this proposal (wrong gate, halted or done)
+    PetitionUnavailable,
+    /// Voter's tier lacks the right for this action
+    MissingTierRight,
+}
+
+/// Denominator a proposal's passing threshold is measured against
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ThresholdBase {
+    /// YES + NO; abstentions count toward quorum only
+    NonAbstain,
+    /// YES + NO + ABSTAIN
+    Turnout,
+    /// The proposal's frozen eligible supply
+    Supply,
+}
+
+/// Milestone grant opened by a `MilestoneGrant` action. Its PDA owns the escrow.
+#[account]
+#[derive(InitSpace)]
+pub struct Grant {
+    pub proposal: Pubkey,
+    /// Index of the opening action in the proposal's payload (PDA seed)
+    pub index: u8,
+    pub mint: Pubkey,
+    /// Token account receiving released tranches
+    pub recipient: Pubkey,
+    pub escrow: Pubkey,
+    #[max_len(MAX_GRANT_TRANCHES)]
+    pub tranches: Vec<u64>,
+    /// Tranches released so far
+    pub released: u8,
+    pub opened_at: i64,
+    pub milestone_period: i64,
+    pub clawed_back: bool,
+    pub bump: u8,
+}
+
+/// Linear treasury payment opened by a `StreamPayment` action.
+#[account]
+#[derive(InitSpace)]
+pub struct Stream {
+    pub proposal: Pubkey,
+    /// Payload index of the opening action (PDA seed)
+    pub index: u8,
+    pub mint: Pubkey,
+    /// Token account receiving the payments
+    pub recipient: Pubkey,
+    /// Token account owned by this PDA holding the unclaimed amount
+    pub escrow: Pubkey,
+    pub total: u64,
+    pub claimed: u64,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub bump: u8,
+}
+
+/// Rolling spend limit for one treasury mint (default key = lamports).
+#[account]
+#[derive(InitSpace)]
+pub struct SpendTracker {
+    pub mint: Pubkey,
+    /// Maximum spend per `window`
+    pub limit: u64,
+    /// Seconds over which a full `limit` of allowance refills (0 = no limit)
+    pub window: i64,
+    /// Outstanding spend as of `updated_at`
+    pub spent: u64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+/// A moderator's spam / abuse flag on a proposal and its appeal.
+#[account]
+#[derive(InitSpace)]
+pub struct ModerationCase {
+    pub proposal: Pubkey,
+    /// Index among the proposal's cases (PDA seed)
+    pub number: u16,
+    pub moderator: Pubkey,
+    /// DAO-defined reason (spam, abuse, ...)
+    pub reason_code: u16,
+    pub flagged_at: i64,
+    pub flagged_slot: u64,
+    /// Lamports held in the `["appeal_bond", case]` PDA (0 = not appealed)
+    pub appeal_bond: u64,
+    pub status: ModerationStatus,
+    pub resolved_at: i64,
+    pub bump: u8,
+}
+
+/// A wallet barred from voting and creating proposals by the compliance authority.
+#[account]
+#[derive(InitSpace)]
+pub struct DenylistEntry {
+    pub wallet: Pubkey,
+    /// DAO-defined reason (e.g. sanctions list identifier)
+    pub reason_code: u16,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+/// An observer's attestation of a proposal's revealed results, one per observer.
+#[account]
+#[derive(InitSpace)]
+pub struct Attestation {
+    pub proposal: Pubkey,
+    pub observer: Pubkey,
+    /// SHA-256 of the final tally ciphertext
+    pub tally_hash: [u8; 32],
+    /// Attested `[yes, no, abstain, total]` counts
+    pub results: [u64; 4],
+    /// Observer's audit report (empty = none)
+    #[max_len(MAX_CID_LEN)]
+    pub report_cid: String,
+    /// Whether the attested counts and hash match the proposal
+    pub matches: bool,
+    pub attested_at: i64,
+    pub bump: u8,
+}
+
+/// Content CID of one translated proposal text, keyed by locale.
+#[account]
+#[derive(InitSpace)]
+pub struct ProposalTranslation {
+    pub proposal: Pubkey,
+    /// Zero-padded BCP 47 language tag
+    pub locale: [u8; 8],
+    /// IPFS CID of the translated title and description
+    #[max_len(MAX_CID_LEN)]
+    pub content_cid: String,
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+/// Compact record of a finished proposal, kept after the proposal is closed.
+#[account]
+#[derive(InitSpace)]
+pub struct ProposalArchive {
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub authority: Pubkey,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub abstain_votes: u64,
+    pub total_votes: u64,
+    pub total_weight: u64,
+    pub passed: bool,
+    pub executed: bool,
+    pub cancelled: bool,
+    pub executed_at: i64,
+    pub revealed_at: i64,
+    pub attestations: u16,
+    pub finalized_at: i64,
+    /// SHA-256 of title, description and link URLs
+    pub metadata_hash: [u8; 32],
+    /// Root of compacted vote records at archival time
+    pub vote_records_root: [u8; 32],
+    pub archived_at: i64,
+    pub bump: u8,
+}
+
+/// Everything an external auditor needs to re-verify a finished election,
+/// written next to the `ProposalArchive`.
+#[account]
+#[derive(InitSpace)]
+pub struct AuditBundle {
+    pub proposal: Pubkey,
+    /// Final `Proposal.tally_lineage`
+    pub tally_lineage: [u8; 32],
+    /// Final `Proposal.ballot_offsets_hash`
+    pub ballot_offsets_hash: [u8; 32],
+    /// Root of compacted ballots (vote records) at archival time
+    pub vote_records_root: [u8; 32],
+    pub compacted_records: u64,
+    pub reveal_computation_offset: u64,
+    /// Ballot computations abandoned by a forced reveal
+    pub dropped_computations: u32,
+    /// Ciphertext layout / circuit version the tally was run with
+    pub ballot_version: u8,
+    pub mxe_program_id: Pubkey,
+    pub bump: u8,
+}
+
+/// Registered sortition candidates for a proposal.
+#[account]
+#[derive(InitSpace)]
+pub struct JuryPool {
+    pub proposal: Pubkey,
+    #[max_len(MAX_JURY_POOL)]
+    pub candidates: Vec<Pubkey>,
+    /// Set once the jury has been drawn; registration is closed
+    pub selected: bool,
+    pub bump: u8,
+}
+
+/// Randomly drawn electorate for a sortition proposal.
+#[account]
+#[derive(InitSpace)]
+pub struct JuryRoster {
+    pub proposal: Pubkey,
+    #[max_len(MAX_JURY_SIZE)]
+    pub jurors: Vec<Pubkey>,
+    /// Proposal randomness the draw was seeded with
+    pub seed: [u8; 32],
+    pub selected_at: i64,
+    pub bump: u8,
+}
+
+/// Reputation score assigned by the DAO authority.
+#[account]
+#[derive(InitSpace)]
+pub struct Reputation {
+    pub voter: Pubkey,
+    pub score: u64,
+    pub bump: u8,
+}
+
+/// Pyth price feed used to value a mint in USD.
+#[account]
+#[derive(InitSpace)]
+pub struct PriceFeed {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub feed_id: [u8; 32],
+    /// Maximum age of the oracle price in seconds
+    pub max_staleness: i64,
+    /// Maximum confidence interval relative to price, in basis points
+    pub max_confidence_bps: u16,
+    pub bump: u8,
+}
+
+/// Whether a proposal's outcome can move funds or change the DAO
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ProposalKind {
+    /// Carries an execution payload; posts the DAO deposit and may set a
+    /// timelock
+    Binding,
+    /// Sentiment only: no deposit, timelock, payload or tie-break coin flip,
+    /// and results are final at reveal. Shares the `Proposal` layout, so it
+    /// costs the same rent.
+    Signaling,
+}
+
+/// How voter eligibility is measured against a proposal's `min_balance`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum GateKind {
+    /// SPL token balance of `gate_mint`
+    Token,
+    /// Active delegated lamports in a native stake account
+    StakeAccount,
+    /// SPL stake-pool LST balance, valued in lamports
+    StakePool,
+    /// USD value (`USD_DECIMALS`) across mints with a registered `PriceFeed`
+    UsdValue,
+}
+
+/// What a proposal link points at
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum LinkKind {
+    /// Discussion thread
+    Forum,
+    /// Specification or design document
+    Spec,
+    /// Security audit report
+    Audit,
+    Other,
+}
+
+/// A typed external link on a proposal (`https://` or `ipfs://`)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct ProposalLink {
+    pub kind: LinkKind,
+    #[max_len(MAX_LINK_LEN)]
+    pub url: String,
+}
+
+/// On-chain action executed when a proposal passes (after `execution_delay`).
+// Actions live borsh-encoded in `ExecutionPayload`; the in-memory size only
+// matters while one is being executed
+#[allow(clippy::large_enum_variant)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub enum ProposalAction {
+    /// Transfer SPL tokens from the treasury (or committee sub-vault) to `recipient`
+    TokenTransfer {
+        mint: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+    },
+    /// Set a committee's spending cap (parent-DAO proposals only)
+    SetCommitteeBudget {
+        committee: Pubkey,
+        spending_cap: u64,
+    },
+    /// Transfer lamports from the SOL treasury PDA to `recipient`
+    SolTransfer { recipient: Pubkey, lamports: u64 },
+    /// Fund `recipient` with the rent-exempt minimum for an account of `space` bytes
+    FundRentExempt { recipient: Pubkey, space: u64 },
+    /// Cancel another passed proposal's queued execution (supermajority only)
+    CancelExecution { proposal: Pubkey },
+    /// Apply a governance-approved change to `DaoConfig`
+    UpdateConfig { change: ConfigChange },
+    /// Mint `amount` of a program-controlled mint to the `recipient` token account
+    MintTokens {
+        mint: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+    },
+    /// Move `amount` from the treasury into a stream escrow paying the
+    /// `recipient` token account, vesting linearly over `duration` seconds
+    /// (see `claim_stream`)
+    StreamPayment {
+        mint: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        duration: i64,
+    },
+    /// Move the sum of `tranches` from the treasury into a grant escrow.
+    /// Tranche `i` must be released (by council sign-off or a `ReleaseTranche`
+    /// proposal) within `(i + 1) * milestone_period` seconds of execution, or
+    /// the remainder can be clawed back to the treasury.
+    MilestoneGrant {
+        mint: Pubkey,
+        recipient: Pubkey,
+        #[max_len(MAX_GRANT_TRANCHES)]
+        tranches: Vec<u64>,
+        milestone_period: i64,
+    },
+    /// Release the next tranche of a milestone grant
+    ReleaseTranche { grant: Pubkey },
+    /// Post an off-chain obligation (forum notice, CI deploy, ...) for the
+    /// registered executor as its own `Obligation` account, numbered by the
+    /// `Outbox`. `kind` is executor-defined. Requires an `outbox_executor`.
+    PostObligation {
+        kind: u16,
+        #[max_len(MAX_OBLIGATION_DATA)]
+        data: Vec<u8>,
+    },
+    /// CPI into an allowlisted program, signed by the `[INVOKE_AUTHORITY_SEED]`
+    /// PDA (never the treasury). The target program and every listed account
+    /// are passed as remaining accounts.
+    Invoke {
+        program_id: Pubkey,
+        #[max_len(MAX_INVOKE_ACCOUNTS)]
+        accounts: Vec<InvokeAccount>,
+        #[max_len(MAX_INVOKE_DATA)]
+        data: Vec<u8>,
+    },
+}
+
+/// Account meta of an `Invoke` action
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct InvokeAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// Return data of `get_voting_power`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VotingPower {
+    pub own_weight: u64,
+    pub delegated_weight: u64,
+    pub total: u64,
+    /// Incoming delegations that contributed to `delegated_weight`
+    pub delegations: u8,
+    /// The voter's own power is delegated to someone else
+    pub delegated_away: bool,
+}
+
+/// Lifecycle phase reported by `get_proposal_state`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalPhase {
+    /// Gathering endorsements; voting not yet open
+    AwaitingSponsorship,
+    Voting,
+    /// Halted by a moderation flag or petition
+    Paused,
+    /// Deadline passed, results not yet revealed
+    AwaitingReveal,
+    Revealed,
+    /// Deactivated without a reveal
+    Closed,
+    /// Execution cancelled by the council or an upheld petition
+    Cancelled,
+    /// Lapsed without sponsorship
+    Expired,
+}
+
+/// Return data of `get_proposal_state`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProposalState {
+    pub id: u64,
+    pub phase: ProposalPhase,
+    pub voting_starts_at: i64,
+    pub voting_ends_at: i64,
+    pub voting_ends_slot: u64,
+    pub ballots_cast: u64,
+    pub total_votes: u64,
+    pub quorum: u64,
+    pub threshold_bps: u16,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub abstain_votes: u64,
+    pub passed: bool,
+    pub executed: bool,
+}
+
+/// Return data of `get_tally_status`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TallyStatus {
+    pub ballots_cast: u64,
+    pub total_votes: u64,
+    pub tally_shards: u8,
+    pub shards_merged: u8,
+    pub pending_computations: u32,
+    pub dropped_computations: u32,
+    pub tally_lineage: [u8; 32],
+    pub is_revealed: bool,
+}
+
+/// Token holders' emergency-brake petition against one proposal.
+#[account]
+#[derive(InitSpace)]
+pub struct Petition {
+    pub proposal: Pubkey,
+    /// Summed gate balance of all signers
+    pub weight: u64,
+    pub signers: u32,
+    pub status: PetitionStatus,
+    /// Whether the trigger interrupted open voting (deadline extended on dismissal)
+    pub paused_voting: bool,
+    pub triggered_at: i64,
+    pub triggered_slot: u64,
+    pub reviewed_at: i64,
+    pub bump: u8,
+}
+
+/// A wallet's signature on a `Petition`.
+#[account]
+#[derive(InitSpace)]
+pub struct PetitionSignature {
+    pub proposal: Pubkey,
+    pub signer: Pubkey,
+    pub weight: u64,
+    pub bump: u8,
+}
+
+/// A wallet's endorsement of a proposal awaiting sponsorship.
+#[account]
+#[derive(InitSpace)]
+pub struct Endorsement {
+    pub proposal: Pubkey,
+    pub endorser: Pubkey,
+    pub weight: u64,
+    pub bump: u8,
+}
+
+/// Lifecycle of a `Petition`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PetitionStatus {
+    /// Collecting signatures
+    Open,
+    /// Supermajority reached; voting and execution halted pending the council
+    Triggered,
+    /// Council confirmed the petition; proposal deactivated
+    Upheld,
+    /// Council rejected the petition; proposal resumed
+    Dismissed,
+}
+
+/// Lifecycle of a `ModerationCase`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ModerationStatus {
+    /// Voting paused; the creator may appeal
+    Flagged,
+    /// Appeal bond posted; awaiting the council
+    Appealed,
+    /// Council confirmed the flag; proposal deactivated
+    Upheld,
+    /// Council rejected the flag; voting resumed
+    Overturned,
+}
+
+/// Participation checked against the denylist
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenedAction {
+    Vote,
+    CreateProposal,
+}
+
+/// Vote-weight multiplier for one gate mint (e.g. 20_000 = staked token counts 2x)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct MintMultiplier {
+    pub mint: Pubkey,
+    pub multiplier_bps: u16,
+}
+
+/// Revenue-claim multiplier for proposals whose turnout reached
+/// `min_turnout_bps` (e.g. 15_000 = treasury matches half the share)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct TurnoutBoost {
+    pub min_turnout_bps: u16,
+    pub multiplier_bps: u16,
+}
+
+/// Balance band granting `rights` (`TIER_RIGHT_*` flags) to wallets escrowing
+/// at least `min_balance` of `DaoConfig.tier_mint`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct VoterTier {
+    pub min_balance: u64,
+    pub rights: u8,
+}
+
+/// An approved Arcium MXE program and the cluster it runs on
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct ApprovedMxe {
+    pub program_id: Pubkey,
+    pub cluster: Pubkey,
+}
+
+/// DAO parameter changes applied by an `UpdateConfig` action. `None` fields
+/// are left unchanged.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ConfigChange {
+    pub default_quorum: Option<u64>,
+    pub proposal_deposit: Option<u64>,
+    pub treasury: Option<Pubkey>,
+    pub council: Option<Pubkey>,
+    pub compliance_authority: Option<Pubkey>,
+    pub moderator: Option<Pubkey>,
+    pub snapshot_oracle: Option<Pubkey>,
+    pub outbox_executor: Option<Pubkey>,
+    pub appeal_bond: Option<u64>,
+    pub recount_bond: Option<u64>,
+    pub dispute_window: Option<i64>,
+    pub max_ballot_validity: Option<i64>,
+    pub relayer_bond: Option<u64>,
+    pub tokens_per_active_proposal: Option<u64>,
+    pub proposer_min_votes: Option<u8>,
+    pub proposer_vote_window: Option<u8>,
+    pub sponsorship_bps: Option<u16>,
+    pub sponsorship_period: Option<i64>,
+    pub unsponsored_slash_bps: Option<u16>,
+    pub vesting_boost_bps: Option<u16>,
+    pub same_slot_guard: Option<bool>,
+    pub deposit_in_usd: Option<bool>,
+    pub deposit_in_sol: Option<bool>,
+    pub reminder_window: Option<i64>,
+    pub reminder_interval: Option<i64>,
+    pub crank_reward: Option<u64>,
+    pub callback_grace_period: Option<i64>,
+    pub random_tie_break: Option<bool>,
+    pub graded_slash: Option<bool>,
+    pub graded_slash_partial_bps: Option<u16>,
+    pub revenue_share_bps: Option<u16>,
+    pub revenue_epoch_duration: Option<i64>,
+    pub delegator_reward_share_bps: Option<u16>,
+    pub require_delegate_acceptance: Option<bool>,
+    pub require_delegate_eligibility: Option<bool>,
+    pub delegate_gate_mint: Option<Pubkey>,
+    pub delegate_min_balance: Option<u64>,
+    pub quorum_bps: Option<u16>,
+    pub quorum_decay_period: Option<i64>,
+    pub quorum_floor_bps: Option<u16>,
+    pub threshold_base: Option<ThresholdBase>,
+    #[max_len(MAX_TURNOUT_BOOSTS)]
+    pub turnout_boosts: Option<Vec<TurnoutBoost>>,
+    pub turnout_match_budget: Option<u64>,
+    /// Add a program `Invoke` actions may call
+    pub allow_program: Option<Pubkey>,
+    /// Remove a program from `allowed_programs`
+    pub disallow_program: Option<Pubkey>,
+    /// Add an observer to `observers`
+    pub register_observer: Option<Pubkey>,
+    /// Remove an observer from `observers`; its past attestations stay counted
+    pub remove_observer: Option<Pubkey>,
+    /// Add an MXE program / cluster pair to `approved_mxes`. Once the list is
+    /// non-empty, new proposals and migrations must use an approved pair.
+    pub approve_mxe: Option<ApprovedMxe>,
+    /// Remove a pair from `approved_mxes`
+    pub revoke_mxe: Option<ApprovedMxe>,
+    /// Treasury spend limit for one mint, applied to the `spend_tracker`
+    pub spend_limit: Option<SpendLimit>,
+    pub tier_mint: Option<Pubkey>,
+    /// Replacement voter tiers, by strictly increasing `min_balance` (empty
+    /// disables tiers)
+    #[max_len(MAX_VOTER_TIERS)]
+    pub voter_tiers: Option<Vec<VoterTier>>,
+    /// Weight multiplier applied to a mint's value in multi-mint `UsdValue`
+    /// gates; 10_000 removes its entry
+    pub mint_multiplier: Option<MintMultiplier>,
+}
+
+/// Cap on execution payloads' treasury spends of `mint` (default key =
+/// lamports): `limit` per `window` seconds, refilling linearly.
+/// Supermajority proposals are exempt.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct SpendLimit {
+    pub mint: Pubkey,
+    pub limit: u64,
+    pub window: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ExecutionPayload {
+    pub proposal: Pubkey,
+    /// Actions executed in order, one per `execute_next_action` call
+    #[max_len(MAX_PROPOSAL_ACTIONS)]
+    pub actions: Vec<ProposalAction>,
+    /// Cursor: number of actions already executed
+    pub actions_executed: u8,
+    /// Last successful `validate_payload` dry-check (0 = never validated)
+    pub validated_at: i64,
+    pub bump: u8,
+}
+
+/// Sequence counter numbering the obligations posted by executed proposals.
+#[account]
+#[derive(InitSpace)]
+pub struct Outbox {
+    /// Sequence number of the next posted obligation
+    pub next_seq: u64,
+    pub bump: u8,
+}
+
+/// An off-chain obligation posted by a `PostObligation` action, consumed by
+/// the registered executor.
+#[account]
+#[derive(InitSpace)]
+pub struct Obligation {
+    pub seq: u64,
+    pub proposal: Pubkey,
+    /// Payload index of the posting action (PDA seed)
+    pub action_index: u8,
+    pub kind: u16,
+    #[max_len(MAX_OBLIGATION_DATA)]
+    pub data: Vec<u8>,
+    pub posted_at: i64,
+    /// 0 = pending
+    pub acknowledged_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProposalCounter {
+    pub authority: Pubkey,
+    pub active_count: u8,
+    pub last_created_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Delegation {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub created_at: i64,
+    /// Inactive until the delegate calls `accept_delegation` when the DAO
+    /// requires acceptance; always true otherwise
+    pub accepted: bool,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Tally {
+    pub proposal: Pubkey,
+    /// Shard index (0 = primary tally at `["tally", proposal]`)
+    pub shard: u8,
+    /// Ciphertext layout of `encrypted_data` (see `BALLOT_VERSION`)
+    pub ballot_version: u8,
+    pub encrypted_data: [u8; 128],
+    pub nonce: [u8; 16],
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub voted_at: i64,
+    pub encrypted_choice: [u8; 32],
+    pub nonce: [u8; 16],
+    pub voter_pubkey: [u8; 32],
+    /// Set by `verify_no_vote_callback` once the MXE confirms this ballot was NO
+    pub no_vote_proven: bool,
+    /// Ciphertext layout of `encrypted_choice` (see `BALLOT_VERSION`)
+    pub ballot_version: u8,
+    /// Delegations folded into this ballot by `cast_delegated_vote`; they share
+    /// `delegator_reward_share_bps` of the delegate's revenue claim
+    pub delegators: u8,
+    /// Relayer that submitted the ballot via `cast_vote_signed` (default = none)
+    pub relayer: Pubkey,
+    /// `hash(ballot_message)` of a relayed ballot, checked by `challenge_relayer`
+    pub ballot_hash: [u8; 32],
+    /// `BallotReceipt` (inclusion position, counted weight) encrypted to
+    /// `voter_pubkey`, written by `vote_callback` (zero until tallied).
+    /// Eligibility-proof and re-voting ballots never get one: their circuits
+    /// return an eligibility flag or the counted ballot instead
+    pub receipt: [u8; 64],
+    pub receipt_nonce: [u8; 16],
+    /// MXE-held `CountedBallot` of a re-voting proposal
+    pub counted_ballot: [u8; 96],
+    pub counted_nonce: [u8; 16],
+    /// A computation is updating `counted_ballot`
+    pub ballot_in_flight: bool,
+    /// Ballots submitted through `recast_vote`
+    pub revotes: u16,
+    /// Computation last queued for this ballot, checked by its callback
+    /// (0 once settled)
+    pub computation_offset: u64,
+    /// Delegate whose ballot carries this record's weight (default = the
+    /// voter's own ballot); its revenue is only paid through that ballot
+    pub folded_into: Pubkey,
+    /// Gate balance this ballot was counted with (0 for encrypted weights);
+    /// caps what the voter may burn through `ragequit`
+    pub gate_balance: u64,
+    /// Gate tokens burned through `ragequit` so far
+    pub ragequit_burned: u64,
+    pub bump: u8,
+}
+
+/// Revenue pool and participation for one `revenue_epoch_duration` bucket.
+/// The lamports stay in the SOL treasury; this only tracks the voters' share.
+#[account]
+#[derive(InitSpace)]
+pub struct RevenueEpoch {
+    pub epoch: u64,
+    /// Lamports credited to voters by `deposit_revenue` during the epoch
+    pub revenue: u64,
+    /// Sum of `ballots_cast` over the registered proposals
+    pub total_participation: u64,
+    /// Lamports paid out so far, `matched` included
+    pub claimed: u64,
+    /// Proposals revealed in the epoch, in registration order (bitmap index)
+    #[max_len(MAX_EPOCH_PROPOSALS)]
+    pub proposals: Vec<Pubkey>,
+    /// `turnout_bps` of each registered proposal, parallel to `proposals`
+    #[max_len(MAX_EPOCH_PROPOSALS)]
+    pub turnouts: Vec<u16>,
+    /// `DaoConfig.turnout_boosts` as of the last registration
+    #[max_len(MAX_TURNOUT_BOOSTS)]
+    pub boosts: Vec<TurnoutBoost>,
+    /// Treasury lamports available for matching, and matched so far
+    pub match_budget: u64,
+    pub matched: u64,
+    pub bump: u8,
+}
+
+/// A voter's claim bitmap for one revenue epoch; bit `i` is set once the
+/// voter claimed for `RevenueEpoch.proposals[i]`.
+#[account]
+#[derive(InitSpace)]
+pub struct RevenueClaim {
+    pub epoch: u64,
+    pub voter: Pubkey,
+    pub claimed: u64,
+    pub bump: u8,
+}
+
+/// Spent anonymous-voting nullifier `H(voter_secret, proposal_id)`. Existence
+/// of the PDA is the double-vote check; no voter key is stored.
+#[account]
+#[derive(InitSpace)]
+pub struct NullifierRecord {
+    pub proposal: Pubkey,
+    pub nullifier: [u8; 32],
+    pub recorded_at: i64,
+    pub bump: u8,
+}
+
+/// Binding between a proposal and the reveal computation queued for it.
+/// `reveal_results_callback` only accepts counts for the computation recorded
+/// here; the account is closed once the reveal lands.
+#[account]
+#[derive(InitSpace)]
+pub struct PendingComputation {
+    pub proposal: Pubkey,
+    pub computation_account: Pubkey,
+    pub computation_offset: u64,
+    pub comp_def_offset: u32,
+    /// `sha256` of the tally ciphertext the computation was queued with
+    pub input_commitment: [u8; 32],
+    pub queued_at: i64,
+    pub bump: u8,
+}
+
+/// A bonded dispute re-running the reveal computation of a revealed proposal.
+#[account]
+#[derive(InitSpace)]
+pub struct Recount {
+    pub proposal: Pubkey,
+    pub requester: Pubkey,
+    /// Lamports held in the recount bond PDA
+    pub bond: u64,
+    pub status: RecountStatus,
+    pub requested_at: i64,
+    pub resolved_at: i64,
+    pub bump: u8,
+}
+
+/// Outcome of a `Recount`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum RecountStatus {
+    /// Reveal computation re-queued; execution halted
+    Pending,
+    /// Recount matched the published counts
+    Confirmed,
+    /// Recount disagreed; proposal stays halted until the council reviews it
+    Mismatch,
+    /// Council sided with the recount; the proposal was deactivated
+    Upheld,
+    /// Council let the published tally stand
+    Dismissed,
+    /// Computation never returned within `RECOUNT_TIMEOUT`
+    Expired,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ComputationOffsetState {
+    pub bump: u8,
+}
+
+/// Stores the circuit hash and initialization state for computation definitions.
+/// Created once during `init_comp_def` and used for on-chain integrity verification.
+#[account]
+#[derive(InitSpace)]
+pub struct CompDefState {
+    pub authority: Pubkey,
+    /// SHA-256 hash of the compiled Arcis circuit bytecode
+    #[max_len(64)]
+    pub circuit_hash: String,
+    /// Whether computation definitions have been initialized
+    pub initialized: bool,
+    pub bump: u8,
+}
+
+// ==================== EVENTS ====================
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub authority: Pubkey,
+    pub voting_ends_at: i64,
+    /// Deadline slot of a slot-mode proposal (0 = wall-clock deadline)
+    pub voting_end_slot: u64,
+    pub kind: ProposalKind,
+    /// Addresses barred from voting, fixed at creation
+    pub excluded: Vec<Pubkey>,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+}
+
+#[event]
+pub struct ParticipationRecorded {
+    pub voter: Pubkey,
+    pub proposal: Pubkey,
+    pub reveal_number: u64,
+}
+
+#[event]
+pub struct VoteRecast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub revotes: u16,
+}
+
+#[event]
+pub struct OracleSnapshotPosted {
+    pub proposal: Pubkey,
+    pub oracle: Pubkey,
+    pub root: [u8; 32],
+    pub total_weight: u64,
+}
+
+#[event]
+pub struct RelayerRegistered {
+    pub relayer: Pubkey,
+    pub bond: u64,
+}
+
+#[event]
+pub struct RelayerSlashed {
+    pub relayer: Pubkey,
+    pub challenger: Pubkey,
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub bond: u64,
+}
 
-    #[account(mut)]
-    pub proposal: Account<'info, Proposal>,
+/// Per-ballot inputs for off-chain Sybil analysis, read from the voter's
+/// required `WalletProfile`. `age_bucket` counts from the wallet's first
+/// appearance: 1 = under a day, 2 = under a week, 3 = under 30 days,
+/// 4 = older.
+#[event]
+pub struct SybilSignals {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub funding_parent: Pubkey,
+    pub age_bucket: u8,
+}
 
-    #[account(mut)]
-    pub tally: Account<'info, Tally>,
+#[event]
+pub struct DelegateFallbackApplied {
+    pub proposal: Pubkey,
+    pub delegate: Pubkey,
+    pub delegators: u8,
+    pub weight: u64,
+}
 
-    #[account(
-        constraint = voter_token_account.owner == voter.key(),
-        constraint = voter_token_account.mint == proposal.gate_mint
-    )]
-    pub voter_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct DelegatedVoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub delegators: u8,
+    pub delegated_weight: u64,
+}
 
-    #[account(
-        init,
-        payer = voter,
-        space = 8 + VoteRecord::INIT_SPACE,
-        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
-        bump
-    )]
-    pub vote_record: Account<'info, VoteRecord>,
+#[event]
+pub struct VoteDelegated {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+}
 
-    /// CHECK: Sign PDA
-    #[account(seeds = [SIGN_SEED], bump)]
-    pub sign_seed: AccountInfo<'info>,
+#[event]
+pub struct DelegationAccepted {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+}
 
-    // Arcium accounts (same as CreateProposal)
-    pub arcium_program: Program<'info, Arcium>,
-    /// CHECK: MXE account
-    pub mxe_account: AccountInfo<'info>,
-    /// CHECK: Cluster account
-    pub cluster_account: AccountInfo<'info>,
-    /// CHECK: Fee pool
-    pub pool_account: AccountInfo<'info>,
-    /// CHECK: Clock account
-    pub clock_account: AccountInfo<'info>,
-    /// CHECK: Mempool
-    pub mempool_account: AccountInfo<'info>,
-    /// CHECK: Executing pool
-    pub executing_pool: AccountInfo<'info>,
-    /// CHECK: Computation account
-    #[account(mut)]
-    pub computation_account: AccountInfo<'info>,
-    /// CHECK: Comp def account
-    pub comp_def_account: AccountInfo<'info>,
-    /// CHECK: Computation offset account
-    #[account(
-        mut,
-        seeds = [COMPUTATION_OFFSET_SEED],
-        bump = computation_offset_account.bump
-    )]
-    pub computation_offset_account: Account<'info, ComputationOffsetState>,
+#[event]
+pub struct DelegationRevoked {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+}
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct ResultsRevealed {
+    pub proposal: Pubkey,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub abstain_votes: u64,
+    pub total_votes: u64,
+    pub winner: u8,
+    /// `total_votes` against the proposal's `eligible_supply` (token-weighted
+    /// proposals only, 0 otherwise)
+    pub turnout_bps: u16,
 }
 
-#[derive(Accounts)]
-pub struct VoteCallback<'info> {
-    #[account(mut)]
-    pub proposal: Account<'info, Proposal>,
+#[event]
+pub struct ResultsFinalized {
+    pub proposal: Pubkey,
+    pub passed: bool,
+    pub finalized_at: i64,
+}
 
-    #[account(
-        mut,
-        constraint = tally.proposal == proposal.key()
-    )]
-    pub tally: Account<'info, Tally>,
+#[event]
+pub struct RecountRequested {
+    pub proposal: Pubkey,
+    pub requester: Pubkey,
+    pub bond: u64,
+}
 
-    /// CHECK: Sign PDA ensures this callback was invoked via Arcium CPI
-    #[account(
-        seeds = [SIGN_SEED],
-        bump,
-        signer
-    )]
-    pub sign_seed: AccountInfo<'info>,
+#[event]
+pub struct RecountCompleted {
+    pub proposal: Pubkey,
+    pub matched: bool,
 }
 
-#[derive(Accounts)]
-pub struct RevealResults<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+/// A recount disagreed with the published tally. Counts are
+/// `[yes, no, abstain, total]`.
+#[event]
+pub struct TallyIncident {
+    pub proposal: Pubkey,
+    pub published: [u64; 4],
+    pub recounted: [u64; 4],
+}
 
-    #[account(mut)]
-    pub proposal: Account<'info, Proposal>,
+#[event]
+pub struct RecountReviewed {
+    pub proposal: Pubkey,
+    pub upheld: bool,
+}
 
-    pub tally: Account<'info, Tally>,
+#[event]
+pub struct RecountExpired {
+    pub proposal: Pubkey,
+}
 
-    /// CHECK: Sign PDA
-    #[account(seeds = [SIGN_SEED], bump)]
-    pub sign_seed: AccountInfo<'info>,
+#[event]
+pub struct CommitteeCreated {
+    pub committee: Pubkey,
+    pub committee_id: u64,
+    pub budget_mint: Pubkey,
+    pub spending_cap: u64,
+}
 
-    // Arcium accounts
-    pub arcium_program: Program<'info, Arcium>,
-    /// CHECK: MXE account
-    pub mxe_account: AccountInfo<'info>,
-    /// CHECK: Cluster account
-    pub cluster_account: AccountInfo<'info>,
-    /// CHECK: Fee pool
-    pub pool_account: AccountInfo<'info>,
-    /// CHECK: Clock account
-    pub clock_account: AccountInfo<'info>,
-    /// CHECK: Mempool
-    pub mempool_account: AccountInfo<'info>,
-    /// CHECK: Executing pool
-    pub executing_pool: AccountInfo<'info>,
-    /// CHECK: Computation account
-    #[account(mut)]
-    pub computation_account: AccountInfo<'info>,
-    /// CHECK: Comp def account
-    pub comp_def_account: AccountInfo<'info>,
-    /// CHECK: Computation offset account
-    #[account(
-        mut,
-        seeds = [COMPUTATION_OFFSET_SEED],
-        bump = computation_offset_account.bump
-    )]
-    pub computation_offset_account: Account<'info, ComputationOffsetState>,
+#[event]
+pub struct CommitteeBudgetUpdated {
+    pub committee: Pubkey,
+    pub spending_cap: u64,
+    pub spent: u64,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct DaoConfigUpdated {
+    pub proposal: Pubkey,
+    pub default_quorum: u64,
+    pub proposal_deposit: u64,
+    pub treasury: Pubkey,
 }
 
-#[derive(Accounts)]
-pub struct RevealResultsCallback<'info> {
-    #[account(mut)]
-    pub proposal: Account<'info, Proposal>,
+#[event]
+pub struct TallyMigrated {
+    pub proposal: Pubkey,
+    pub old_mxe_program_id: Pubkey,
+    pub mxe_program_id: Pubkey,
+    pub mxe_cluster: Pubkey,
+}
 
-    /// CHECK: Sign PDA ensures this callback was invoked via Arcium CPI
-    #[account(
-        seeds = [SIGN_SEED],
-        bump,
-        signer
-    )]
-    pub sign_seed: AccountInfo<'info>,
+#[event]
+pub struct TallyMigrationCancelled {
+    pub proposal: Pubkey,
+    pub abandoned_mxe_program_id: Pubkey,
 }
 
-#[derive(Accounts)]
-pub struct InitCompDef<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct NullifierRecorded {
+    pub proposal: Pubkey,
+    pub nullifier: [u8; 32],
+}
 
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + CompDefState::INIT_SPACE,
-        seeds = [b"comp_def_state"],
-        bump
-    )]
-    pub comp_def_state: Account<'info, CompDefState>,
+#[event]
+pub struct RevenueDeposited {
+    pub epoch: u64,
+    pub amount: u64,
+    pub shared: u64,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct RevenueClaimed {
+    pub epoch: u64,
+    pub voter: Pubkey,
+    pub proposal: Pubkey,
+    pub amount: u64,
+    /// Part of the claim matched from the treasury by the turnout boost
+    pub matched: u64,
 }
 
-#[derive(Accounts)]
-pub struct InitComputationOffset<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
+#[event]
+pub struct TurnoutBoostSet {
+    /// Proposal whose `UpdateConfig` action set the schedule
+    pub proposal: Pubkey,
+    pub boosts: Vec<TurnoutBoost>,
+    pub budget: u64,
+}
 
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + ComputationOffsetState::INIT_SPACE,
-        seeds = [COMPUTATION_OFFSET_SEED],
-        bump
-    )]
-    pub computation_offset_account: Account<'info, ComputationOffsetState>,
+#[event]
+pub struct DelegatorRewardsShared {
+    pub epoch: u64,
+    pub delegate: Pubkey,
+    pub proposal: Pubkey,
+    pub delegators: u8,
+    pub amount: u64,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct TieResolved {
+    pub proposal: Pubkey,
+    pub yes_wins: bool,
 }
 
-// ==================== DEV MODE ACCOUNT STRUCTURES ====================
+#[event]
+pub struct ComputationsDropped {
+    pub proposal: Pubkey,
+    pub dropped: u32,
+    pub total_votes: u64,
+}
 
-#[derive(Accounts)]
-#[instruction(proposal_id: u64)]
-pub struct DevCreateProposal<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct VoteRecordsCompacted {
+    pub proposal: Pubkey,
+    pub batch_root: [u8; 32],
+    pub root: [u8; 32],
+    pub count: u64,
+    pub compacted_records: u64,
+}
 
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Proposal::INIT_SPACE,
-        seeds = [PROPOSAL_SEED, proposal_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub proposal: Account<'info, Proposal>,
+#[event]
+pub struct LinksUpdated {
+    pub proposal: Pubkey,
+    pub count: u8,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct CheckpointRecorded {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub slot: u64,
+    pub amount: u64,
 }
 
-#[derive(Accounts)]
-pub struct DevInitTally<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct ResultsAttested {
+    pub proposal: Pubkey,
+    pub observer: Pubkey,
+    pub matches: bool,
+    pub attestations: u16,
+    pub disputes: u16,
+}
 
-    pub proposal: Account<'info, Proposal>,
+#[event]
+pub struct TranslationRegistered {
+    pub proposal: Pubkey,
+    pub locale: [u8; 8],
+    pub content_cid: String,
+}
 
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Tally::INIT_SPACE,
-        seeds = [TALLY_SEED, proposal.key().as_ref()],
-        bump
-    )]
-    pub tally: Account<'info, Tally>,
+#[event]
+pub struct ProposalArchived {
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub metadata_hash: [u8; 32],
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct GrantOpened {
+    pub grant: Pubkey,
+    pub recipient: Pubkey,
+    pub total: u64,
+    pub tranches: u8,
 }
 
-#[derive(Accounts)]
-pub struct DevCastVote<'info> {
-    #[account(mut)]
-    pub voter: Signer<'info>,
+#[event]
+pub struct TrancheReleased {
+    pub grant: Pubkey,
+    pub index: u8,
+    pub amount: u64,
+}
 
-    #[account(mut)]
-    pub proposal: Account<'info, Proposal>,
+#[event]
+pub struct GrantClawedBack {
+    pub grant: Pubkey,
+    pub amount: u64,
+}
 
-    #[account(mut)]
-    pub tally: Account<'info, Tally>,
+#[event]
+pub struct StreamOpened {
+    pub stream: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub end_at: i64,
+}
 
-    #[account(
-        constraint = voter_token_account.owner == voter.key(),
-        constraint = voter_token_account.mint == proposal.gate_mint
-    )]
-    pub voter_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct StreamClaimed {
+    pub stream: Pubkey,
+    pub amount: u64,
+    pub claimed: u64,
+}
 
-    #[account(
-        init,
-        payer = voter,
-        space = 8 + VoteRecord::INIT_SPACE,
-        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
-        bump
-    )]
-    pub vote_record: Account<'info, VoteRecord>,
+#[event]
+pub struct PayloadValidated {
+    pub proposal: Pubkey,
+    pub actions: u8,
+}
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct ProposalFlagged {
+    pub proposal: Pubkey,
+    pub moderator: Pubkey,
+    pub reason_code: u16,
 }
 
-#[derive(Accounts)]
-pub struct DevRevealResults<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct ModerationAppealed {
+    pub proposal: Pubkey,
+    pub bond: u64,
+}
 
-    #[account(mut)]
-    pub proposal: Account<'info, Proposal>,
+#[event]
+pub struct ModerationResolved {
+    pub proposal: Pubkey,
+    pub upheld: bool,
+    pub bond: u64,
 }
 
-#[derive(Accounts)]
-pub struct DelegateVote<'info> {
-    #[account(mut)]
-    pub delegator: Signer<'info>,
+/// A ballot or petition signature failed a recoverable guard; the
+/// transaction reverts with `error_code`. Denylisted voters are reported by
+/// `ScreeningBlocked` instead.
+#[event]
+pub struct VoteRejected {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub reason: RejectReason,
+    pub error_code: u32,
+}
 
-    /// CHECK: The delegate address (any valid pubkey)
-    pub delegate: AccountInfo<'info>,
+#[event]
+pub struct ProposalEndorsed {
+    pub proposal: Pubkey,
+    pub endorser: Pubkey,
+    pub weight: u64,
+    pub total_weight: u64,
+}
 
-    #[account(
-        init,
-        payer = delegator,
-        space = 8 + Delegation::INIT_SPACE,
-        seeds = [DELEGATION_SEED, delegator.key().as_ref()],
-        bump
-    )]
-    pub delegation: Account<'info, Delegation>,
+#[event]
+pub struct ProposalSponsored {
+    pub proposal: Pubkey,
+    pub endorsement_weight: u64,
+    pub voting_ends_at: i64,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct SponsorshipExpired {
+    pub proposal: Pubkey,
+    pub endorsement_weight: u64,
 }
 
-#[derive(Accounts)]
-pub struct RevokeDelegation<'info> {
-    #[account(mut)]
-    pub delegator: Signer<'info>,
+#[event]
+pub struct PetitionSigned {
+    pub proposal: Pubkey,
+    pub signer: Pubkey,
+    pub weight: u64,
+    pub total_weight: u64,
+}
 
-    #[account(
-        mut,
-        close = delegator,
-        seeds = [DELEGATION_SEED, delegator.key().as_ref()],
-        bump = delegation.bump,
-        constraint = delegation.delegator == delegator.key()
-    )]
-    pub delegation: Account<'info, Delegation>,
+#[event]
+pub struct PetitionTriggered {
+    pub proposal: Pubkey,
+    pub weight: u64,
+    pub signers: u32,
 }
 
-#[derive(Accounts)]
-pub struct InitDaoConfig<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct PetitionReviewed {
+    pub proposal: Pubkey,
+    pub upheld: bool,
+}
 
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + DaoConfig::INIT_SPACE,
-        seeds = [DAO_CONFIG_SEED],
-        bump
-    )]
-    pub dao_config: Account<'info, DaoConfig>,
+#[event]
+pub struct WalletDenied {
+    pub wallet: Pubkey,
+    pub reason_code: u16,
+}
 
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct WalletAllowed {
+    pub wallet: Pubkey,
 }
 
-// ==================== STATE ACCOUNTS ====================
+#[event]
+pub struct ScreeningBlocked {
+    pub actor: Pubkey,
+    pub proposal: Pubkey,
+    pub action: ScreenedAction,
+}
 
-#[account]
-#[derive(InitSpace)]
-pub struct Proposal {
-    pub id: u64,
-    pub authority: Pubkey,
-    #[max_len(100)]
-    pub title: String,
-    #[max_len(5000)]
-    pub description: String,
-    pub voting_ends_at: i64,
-    pub is_active: bool,
-    pub is_revealed: bool,
+#[event]
+pub struct DeadlineApproaching {
+    pub proposal: Pubkey,
+    pub seconds_remaining: i64,
     pub total_votes: u64,
-    pub gate_mint: Pubkey,
-    pub min_balance: u64,
-    pub mxe_program_id: Pubkey,
-    pub yes_votes: u64,
-    pub no_votes: u64,
-    pub abstain_votes: u64,
-    /// Minimum number of votes required for the result to be valid (0 = no quorum)
     pub quorum: u64,
-    /// V2: Passing threshold in basis points (e.g., 5001 = simple majority, 6667 = two-thirds)
-    pub threshold_bps: u16,
-    /// V2: Privacy level (0 = Full, 1 = Partial, 2 = Transparent)
-    pub privacy_level: u8,
-    /// V2: Whether the proposal passed its threshold check
-    pub passed: bool,
-    /// V2: Optional discussion URL for linking to external debate forums
-    #[max_len(256)]
-    pub discussion_url: String,
-    /// V2: Deposit amount locked by creator (returned if quorum met)
-    pub deposit_amount: u64,
-    /// V2: Whether the deposit has been returned or slashed
-    pub deposit_returned: bool,
-    /// V2: Execution delay in seconds after reveal (timelock for payload execution)
-    pub execution_delay: i64,
-    /// V2: Whether the on-chain action payload has been executed
-    pub executed: bool,
-    pub bump: u8,
+    pub cranker: Pubkey,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct DaoConfig {
-    pub authority: Pubkey,
-    /// Token mint used for proposal deposits
-    pub deposit_mint: Pubkey,
-    /// Required deposit amount to create a proposal
-    pub proposal_deposit: u64,
-    /// Treasury address where slashed deposits go
-    pub treasury: Pubkey,
-    /// Whether to slash deposits when quorum is not met
-    pub slash_if_no_quorum: bool,
-    pub bump: u8,
+#[event]
+pub struct RandomnessRevealed {
+    pub proposal: Pubkey,
+    pub randomness_account: Pubkey,
+    pub value: [u8; 32],
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct ProposalCounter {
-    pub authority: Pubkey,
-    pub active_count: u8,
-    pub last_created_at: i64,
-    pub bump: u8,
+#[event]
+pub struct JurySelected {
+    pub proposal: Pubkey,
+    pub jurors: u8,
+    pub seed: [u8; 32],
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Delegation {
-    pub delegator: Pubkey,
-    pub delegate: Pubkey,
-    pub created_at: i64,
-    pub bump: u8,
+#[event]
+pub struct DepositSettled {
+    pub proposal: Pubkey,
+    pub amount: u64,
+    pub slashed: bool,
+    /// Portion of `amount` sent to the treasury
+    pub slashed_amount: u64,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Tally {
+#[event]
+pub struct ObligationPosted {
+    pub seq: u64,
     pub proposal: Pubkey,
-    pub encrypted_data: [u8; 128],
-    pub nonce: [u8; 16],
-    pub bump: u8,
+    pub kind: u16,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct VoteRecord {
+#[event]
+pub struct ObligationAcknowledged {
+    pub seq: u64,
     pub proposal: Pubkey,
-    pub voter: Pubkey,
-    pub voted_at: i64,
-    pub encrypted_choice: [u8; 32],
-    pub nonce: [u8; 16],
-    pub voter_pubkey: [u8; 32],
-    pub bump: u8,
+    pub executor: Pubkey,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct ComputationOffsetState {
-    pub bump: u8,
+#[event]
+pub struct ActionExecuted {
+    pub proposal: Pubkey,
+    pub index: u8,
+    pub remaining: u8,
+    pub executor: Pubkey,
 }
 
-/// Stores the circuit hash and initialization state for computation definitions.
-/// Created once during `init_comp_def` and used for on-chain integrity verification.
-#[account]
-#[derive(InitSpace)]
-pub struct CompDefState {
-    pub authority: Pubkey,
-    /// SHA-256 hash of the compiled Arcis circuit bytecode
-    #[max_len(64)]
-    pub circuit_hash: String,
-    /// Whether computation definitions have been initialized
-    pub initialized: bool,
-    pub bump: u8,
+#[event]
+pub struct ExecutionCancelled {
+    pub proposal: Pubkey,
+    pub cancelled_by: Pubkey,
 }
 
-// ==================== EVENTS ====================
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub executor: Pubkey,
+    pub slot: u64,
+    pub executed_at: i64,
+}
 
 #[event]
-pub struct ProposalCreated {
-    pub proposal_id: u64,
-    pub authority: Pubkey,
-    pub voting_ends_at: i64,
+pub struct PowerDeposited {
+    pub owner: Pubkey,
+    pub asset_mint: Pubkey,
+    pub amount: u64,
+    pub power: u64,
 }
 
 #[event]
-pub struct VoteCast {
-    pub proposal: Pubkey,
-    pub voter: Pubkey,
+pub struct PowerWithdrawn {
+    pub owner: Pubkey,
+    pub asset_mint: Pubkey,
+    pub amount: u64,
+    pub power: u64,
 }
 
 #[event]
-pub struct VoteDelegated {
-    pub delegator: Pubkey,
-    pub delegate: Pubkey,
+pub struct VestingLocked {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub end_at: i64,
 }
 
 #[event]
-pub struct DelegationRevoked {
-    pub delegator: Pubkey,
-    pub delegate: Pubkey,
+pub struct VestingWithdrawn {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
 }
 
 #[event]
-pub struct ResultsRevealed {
+pub struct RagequitExecuted {
     pub proposal: Pubkey,
-    pub yes_votes: u64,
-    pub no_votes: u64,
-    pub abstain_votes: u64,
-    pub total_votes: u64,
-    pub winner: u8,
+    pub member: Pubkey,
+    pub burned: u64,
+    pub vault: Pubkey,
+    pub withdrawn: u64,
 }
 
 // ==================== ERRORS ====================
@@ -1435,4 +14445,368 @@ pub enum VotingError {
     CircuitHashMismatch,
     #[msg("Results already revealed")]
     AlreadyRevealed,
+    #[msg("Proposer is not a member of the committee's proposer set")]
+    NotCommitteeProposer,
+    #[msg("Too many committee proposers")]
+    TooManyProposers,
+    #[msg("Action is not permitted for this proposal's committee scope")]
+    CommitteeScopeViolation,
+    #[msg("Committee spending cap exceeded")]
+    CommitteeBudgetExceeded,
+    #[msg("Execution payload is locked once voting has started")]
+    PayloadLocked,
+    #[msg("Proposal did not pass")]
+    ProposalNotPassed,
+    #[msg("Proposal has already been executed")]
+    AlreadyExecuted,
+    #[msg("Execution timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Account required by the execution action was not provided")]
+    MissingExecutionAccount,
+    #[msg("Source vault is not owned by the treasury for this proposal's scope")]
+    InvalidTreasuryVault,
+    #[msg("Recipient does not match the execution payload")]
+    InvalidRecipient,
+    #[msg("Mint authority is not the program mint authority PDA")]
+    InvalidMintAuthority,
+    #[msg("Execution payload must contain between 1 and MAX_PROPOSAL_ACTIONS actions")]
+    InvalidActionCount,
+    #[msg("Proposal execution has been cancelled")]
+    ExecutionCancelled,
+    #[msg("Execution can only be cancelled during the timelock window")]
+    CancellationWindowClosed,
+    #[msg("Cancelling a passed proposal requires a supermajority threshold")]
+    SupermajorityRequired,
+    #[msg("Target proposal does not match the execution payload")]
+    InvalidTargetProposal,
+    #[msg("Proposal does not spend from the DAO treasury")]
+    NotTreasurySpending,
+    #[msg("Ragequit is only possible between reveal and execution")]
+    RagequitWindowClosed,
+    #[msg("Ragequit exceeds the balance the ballot was counted with")]
+    RagequitCapExceeded,
+    #[msg("Frozen gate tokens cannot be burned")]
+    GateTokensFrozen,
+    #[msg("Ballot has not been proven to be a NO vote")]
+    NoVoteNotProven,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Withdrawal exceeds deposited amount")]
+    InsufficientDeposit,
+    #[msg("Account required by the proposal's gate was not provided")]
+    MissingGateAccount,
+    #[msg("Invalid stake account for this voter")]
+    InvalidStakeAccount,
+    #[msg("Stake is not fully active")]
+    InactiveStake,
+    #[msg("Stake is delegated to a different validator")]
+    WrongValidator,
+    #[msg("Invalid stake pool account")]
+    InvalidStakePool,
+    #[msg("Invalid or unverified oracle price account")]
+    InvalidOracle,
+    #[msg("Oracle price is stale")]
+    StalePrice,
+    #[msg("Oracle confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Invalid oracle staleness or confidence bound")]
+    InvalidOracleConfig,
+    #[msg("Gate account counted more than once")]
+    DuplicateGateAccount,
+    #[msg("Account required for the proposal deposit was not provided")]
+    MissingDepositAccount,
+    #[msg("SOL bond must cover rent exemption")]
+    BondBelowRentExemption,
+    #[msg("Recounts are disabled or the proposal is executed or halted")]
+    RecountUnavailable,
+    #[msg("Recount has not completed or awaits council review")]
+    RecountPending,
+    #[msg("Recount is not in the required state")]
+    InvalidRecountStatus,
+    #[msg("Recount computation has not timed out yet")]
+    RecountNotExpired,
+    #[msg("Results are not final yet")]
+    NotFinalized,
+    #[msg("Results are already final")]
+    AlreadyFinalized,
+    #[msg("Dispute window has not passed")]
+    DisputeWindowOpen,
+    #[msg("Dispute window cannot be negative")]
+    InvalidDisputeWindow,
+    #[msg("Signed ballot stays valid longer than the DAO allows")]
+    BallotValidityTooLong,
+    #[msg("Signed ballot validity cannot be negative")]
+    InvalidBallotValidity,
+    #[msg("Signed ballot is not the voter's next in sequence")]
+    BallotOutOfSequence,
+    #[msg("Signed delegation does not carry the delegator's next nonce")]
+    DelegationNonceMismatch,
+    #[msg("Relayer is not registered or is unbonding")]
+    RelayerNotRegistered,
+    #[msg("Relayer bond is still unbonding")]
+    RelayerUnbonding,
+    #[msg("Relayer handled the acknowledged ballot correctly")]
+    RelayerNotAtFault,
+    #[msg("Re-voting can no longer be changed")]
+    RevotingLocked,
+    #[msg("Ballot path is not available on re-voting proposals")]
+    RevotingUnsupported,
+    #[msg("Previous ballot is still being tallied")]
+    BallotInFlight,
+    #[msg("Delegate fallback is not enabled or not supported for this proposal")]
+    DelegateFallbackUnsupported,
+    #[msg("Delegate fallback window has closed")]
+    FallbackWindowClosed,
+    #[msg("Delegate fallback window is still open")]
+    FallbackWindowOpen,
+    #[msg("Creator has not voted in enough recent proposals")]
+    InsufficientParticipation,
+    #[msg("Address is excluded from voting on this proposal")]
+    VoterExcluded,
+    #[msg("Too many excluded addresses")]
+    TooManyExclusions,
+    #[msg("Proposal is not awaiting sponsorship")]
+    SponsorshipUnavailable,
+    #[msg("Sponsorship period has ended")]
+    SponsorshipLapsed,
+    #[msg("Sponsorship period has not ended")]
+    SponsorshipPending,
+    #[msg("Invalid sponsorship settings")]
+    InvalidSponsorshipRule,
+    #[msg("Participation rule needs min votes <= window <= 64")]
+    InvalidParticipationRule,
+    #[msg("Deposit is held in the other deposit mode")]
+    WrongDepositMode,
+    #[msg("Expected an Ed25519 signature check immediately before this instruction")]
+    MissingSignatureVerification,
+    #[msg("Ed25519 signature check does not cover the expected signer and message")]
+    InvalidSignatureVerification,
+    #[msg("Signed message has expired")]
+    SignatureExpired,
+    #[msg("Delegation has not been accepted by the delegate")]
+    DelegationNotAccepted,
+    #[msg("Delegation is already accepted")]
+    DelegationAlreadyAccepted,
+    #[msg("Delegate does not hold the required gate-mint balance")]
+    DelegateNotEligible,
+    #[msg("Revenue sharing is not enabled for this DAO")]
+    RevenueSharingDisabled,
+    #[msg("Revenue share must be at most 10000 bps and the epoch length non-negative")]
+    InvalidRevenueShare,
+    #[msg("Revenue can only be deposited into the current epoch")]
+    NotCurrentRevenueEpoch,
+    #[msg("Proposal was not revealed in this revenue epoch")]
+    ProposalNotInEpoch,
+    #[msg("Proposal is already registered for this revenue epoch")]
+    ProposalAlreadyRegistered,
+    #[msg("Revenue epoch has no room for more proposals")]
+    EpochProposalsFull,
+    #[msg("Registration for this revenue epoch has closed")]
+    EpochRegistrationClosed,
+    #[msg("Claims for this revenue epoch are not open yet")]
+    EpochClaimsNotOpen,
+    #[msg("Ballot was folded into a delegate's and is paid through it")]
+    BallotFolded,
+    #[msg("Revenue for this vote has already been claimed")]
+    RevenueAlreadyClaimed,
+    #[msg("Turnout boosts must be at most MAX_TURNOUT_BOOSTS with increasing turnout and multipliers above 10_000")]
+    InvalidTurnoutBoost,
+    #[msg("SOL treasury cannot cover the payout")]
+    InsufficientTreasury,
+    #[msg("Ballot weight is zero under this proposal's weighting mode")]
+    ZeroVoteWeight,
+    #[msg("Proposal has reached its voter cap")]
+    VoterCapReached,
+    #[msg("Jury can no longer be changed for this proposal")]
+    JuryLocked,
+    #[msg("Invalid jury size")]
+    InvalidJurySize,
+    #[msg("Already registered for this jury pool")]
+    AlreadyRegistered,
+    #[msg("Jury pool is full")]
+    JuryPoolFull,
+    #[msg("Voter was not drawn for this proposal's jury")]
+    NotOnJury,
+    #[msg("Invalid randomness account")]
+    InvalidRandomness,
+    #[msg("Randomness has not been revealed yet")]
+    RandomnessNotReady,
+    #[msg("Randomness account was not freshly committed")]
+    RandomnessAlreadyRevealed,
+    #[msg("Proposal randomness is already set")]
+    RandomnessLocked,
+    #[msg("Deadline is not within the reminder window")]
+    ReminderTooEarly,
+    #[msg("Reminder interval has not elapsed")]
+    ReminderTooSoon,
+    #[msg("A crank reward requires a positive reminder interval")]
+    InvalidReminderInterval,
+    #[msg("Passed proposal has not been executed or cancelled")]
+    ExecutionPending,
+    #[msg("Proposal deposit has not been settled")]
+    DepositNotSettled,
+    #[msg("Compaction batch must hold 1..=16 vote record / voter pairs")]
+    InvalidCompactionBatch,
+    #[msg("Vote record does not belong to this proposal and voter")]
+    InvalidVoteRecord,
+    #[msg("Tally shards can no longer be changed")]
+    ShardingLocked,
+    #[msg("Invalid tally shard count")]
+    InvalidShardCount,
+    #[msg("Wrong tally shard for this ballot")]
+    WrongTallyShard,
+    #[msg("All tally shards must be merged before reveal")]
+    ShardsNotMerged,
+    #[msg("Tally shard already merged")]
+    ShardAlreadyMerged,
+    #[msg("A shard merge is already in progress")]
+    MergeInProgress,
+    #[msg("Callback grace period has closed")]
+    CallbackWindowClosed,
+    #[msg("Vote computations are still pending")]
+    VotesPending,
+    #[msg("Callback grace period cannot be negative")]
+    InvalidGracePeriod,
+    #[msg("Tally cannot be migrated while computations are in flight")]
+    MigrationBlocked,
+    #[msg("A tally migration is in progress")]
+    MigrationInProgress,
+    #[msg("MXE program / cluster is not on the DAO allowlist")]
+    MxeNotApproved,
+    #[msg("Cluster account does not match the proposal's MXE cluster")]
+    WrongMxeCluster,
+    #[msg("MXE allowlist is full")]
+    MxeAllowlistFull,
+    #[msg("Ciphertext uses an unsupported ballot version")]
+    UnsupportedBallotVersion,
+    #[msg("Snapshot root can no longer be changed")]
+    SnapshotLocked,
+    #[msg("Proposal has no eligibility snapshot")]
+    MissingSnapshotRoot,
+    #[msg("Snapshot slot must be in the past and requires a Token gate")]
+    InvalidSnapshotSlot,
+    #[msg("Escrow checkpoint is missing, pruned or does not match the voter")]
+    InvalidCheckpoint,
+    #[msg("Delegated votes are not supported on snapshot proposals")]
+    SnapshotDelegationUnsupported,
+    #[msg("Proposal votes only through its eligibility snapshot")]
+    SnapshotElectorate,
+    #[msg("Gate tokens must be escrowed before the current slot")]
+    UnsettledBalance,
+    #[msg("Signaling proposals cannot carry an execution payload")]
+    SignalingProposal,
+    #[msg("Target MXE has no published x25519 key")]
+    MxeKeyUnavailable,
+    #[msg("Merkle proof has the wrong depth")]
+    InvalidMerkleProof,
+    #[msg("Proposal is waiting on a tie-break")]
+    TiePending,
+    #[msg("Proposal has no pending tie-break")]
+    NoTiePending,
+    #[msg("Delegation does not point at this voter")]
+    InvalidDelegation,
+    #[msg("Delegator accounts do not form complete groups or exceed the batch limit")]
+    InvalidDelegationBatch,
+    #[msg("Callback does not match the computation queued for this proposal")]
+    ComputationMismatch,
+    #[msg("Too many proposal links")]
+    TooManyLinks,
+    #[msg("Link URL is empty, too long or uses an unsupported scheme")]
+    InvalidLink,
+    #[msg("Links can only be appended once voting has started")]
+    LinksLocked,
+    #[msg("Locale must be a zero-padded BCP 47 language tag")]
+    InvalidLocale,
+    #[msg("Content CID is empty, too long or not alphanumeric")]
+    InvalidContentCid,
+    #[msg("Mint multiplier must be between 1 and MAX_MULTIPLIER_BPS basis points")]
+    InvalidMultiplier,
+    #[msg("Mint multiplier table is full")]
+    MultiplierTableFull,
+    #[msg("Voter tiers must be at most MAX_VOTER_TIERS with increasing balances")]
+    InvalidVoterTiers,
+    #[msg("Voter tier does not grant this right")]
+    MissingTierRight,
+    #[msg("Wallet is on the DAO denylist")]
+    ActorDenied,
+    #[msg("Moderation requires a council to hear appeals")]
+    ModerationDisabled,
+    #[msg("Moderation case is not in the required state")]
+    InvalidModerationStatus,
+    #[msg("Moderation appeal window has closed")]
+    AppealWindowClosed,
+    #[msg("Proposal cannot be petitioned")]
+    PetitionUnavailable,
+    #[msg("Petition is not awaiting review")]
+    InvalidPetitionStatus,
+    #[msg("Proposal is halted pending review")]
+    ProposalHalted,
+    #[msg("Proposal counter and slot-stake accounts are required by the rate limit")]
+    MissingProposalCounter,
+    #[msg("Holdings do not cover another active proposal")]
+    TooManyActiveProposals,
+    #[msg("Proposal is still active")]
+    ProposalStillActive,
+    #[msg("Proposal does not hold a rate-limit slot")]
+    RateSlotNotHeld,
+    #[msg("Release the proposal's rate-limit slot first")]
+    RateSlotHeld,
+    #[msg("Payload spends more than the treasury currently holds")]
+    PayloadExceedsTreasury,
+    #[msg("Program is not on the execution allowlist")]
+    ProgramNotAllowed,
+    #[msg("Execution program allowlist is full")]
+    ProgramAllowlistFull,
+    #[msg("Calling program may not create proposals")]
+    ProposerProgramNotAllowed,
+    #[msg("Proposer program allowlist is full")]
+    ProposerAllowlistFull,
+    #[msg("CPI proposals must be signed by the caller's proposer PDA")]
+    InvalidProgramProposer,
+    #[msg("Spend tracker account does not match the spent mint")]
+    InvalidSpendTracker,
+    #[msg("Spend limit window must be positive")]
+    InvalidSpendLimit,
+    #[msg("Treasury spend exceeds the rolling limit for this mint")]
+    SpendLimitExceeded,
+    #[msg("Stream amount and duration must be positive")]
+    InvalidStream,
+    #[msg("Nothing has vested since the last claim")]
+    NothingVested,
+    #[msg("Grant tranches or milestone period are invalid")]
+    InvalidGrant,
+    #[msg("Grant is fully released or clawed back")]
+    GrantClosed,
+    #[msg("Milestone deadline has lapsed")]
+    MilestoneLapsed,
+    #[msg("Milestone deadline has not lapsed yet")]
+    MilestoneNotLapsed,
+    #[msg("Vesting position is invalid or does not match the gate")]
+    InvalidVestingPosition,
+    #[msg("Invoke action has too many accounts, too much data or a foreign signer")]
+    InvalidInvokeAccounts,
+    #[msg("Obligation payload is too large")]
+    InvalidObligation,
+    #[msg("Obligations need a registered outbox executor")]
+    NoOutboxExecutor,
+    #[msg("Obligation has already been acknowledged")]
+    UnknownObligation,
+    #[msg("Observer registry is full")]
+    ObserverRegistryFull,
+    #[msg("Observer is not registered")]
+    ObserverNotRegistered,
+    #[msg("Voting end slot must be in the future")]
+    InvalidEndSlot,
+    #[msg("Moderation appeal window is still open")]
+    AppealWindowOpen,
+    #[msg("Sortition requires a Token gate")]
+    SortitionRequiresToken,
+    #[msg("Delegated votes require a Token gate")]
+    DelegationRequiresToken,
+    #[msg("USD-value gates need 1 to 8 distinct mints; other gates take none")]
+    InvalidGateMints,
+    #[msg("Graded slash share must be at most 10000 basis points")]
+    InvalidSlashSchedule,
+    #[msg("Callback outputs are not a verified Arcium computation result")]
+    UnverifiedComputationOutput,
 }